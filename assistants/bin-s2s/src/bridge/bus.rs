@@ -1,3 +1,47 @@
 use lifeline::prelude::*;
+use lifeline::Storage;
+use relay_s2s::indexer::Indexer;
+use relay_s2s::keepstate::Keepstate;
+
+use crate::bridge::config::FeemarketConfig;
+use crate::traits::S2SFeemarketChainInfo;
 
 lifeline_bus!(pub struct BridgeBus);
+
+/// Keepstate shared by all relay services of this bridge
+#[derive(Clone, Debug)]
+pub struct BridgeKeepstate(pub Keepstate);
+
+lifeline::impl_storage_clone!(BridgeKeepstate);
+
+impl Resource<BridgeBus> for BridgeKeepstate {}
+
+/// Indexers of both chains of this bridge
+#[derive(Clone, Debug)]
+pub struct BridgeIndexer {
+    pub source: Indexer,
+    pub target: Indexer,
+}
+
+lifeline::impl_storage_clone!(BridgeIndexer);
+
+impl Resource<BridgeBus> for BridgeIndexer {}
+
+/// Fee market of both sides of this bridge
+#[derive(Clone, Debug)]
+pub struct BridgeFeemarket<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> {
+    pub config: FeemarketConfig,
+    pub source: SCI,
+    pub target: TCI,
+}
+
+impl<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> Storage for BridgeFeemarket<SCI, TCI> {
+    fn take_or_clone(res: &mut Option<Self>) -> Option<Self> {
+        res.clone()
+    }
+}
+
+impl<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> Resource<BridgeBus>
+    for BridgeFeemarket<SCI, TCI>
+{
+}
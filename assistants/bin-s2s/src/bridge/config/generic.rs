@@ -1,5 +1,20 @@
+use std::collections::HashMap;
+
+use bridge_s2s_traits::types::MessagesDeliveryLimits;
+use component_state::state::{BridgeState, StateOptions};
+use feemarket_s2s::quote::QuotePolicy;
+use feemarket_s2s::relay::basic::BasicRelayStrategy;
+use feemarket_s2s::relay::profitable::{ChainEconomy, ChainEconomyConfig, ProfitableRelayStrategy};
+use feemarket_s2s::relay::strategy::FeemarketRelayStrategy;
+use feemarket_s2s_traits::api::FeemarketApiRelay;
+use feemarket_s2s_traits::types::Chain;
+use microkv::namespace::NamespaceMicroKV;
+use relay_s2s::keepstate::Keepstate;
+use relay_s2s::types::{ConfirmationPolicy, JustificationSource};
 use serde::{Deserialize, Serialize};
+use support_types::mark::ChainName;
 
+use crate::error::BinS2SResult;
 use crate::types::HexLaneId;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -8,18 +23,183 @@ pub struct RelayConfig {
     pub lanes: Vec<HexLaneId>,
     #[serde(default)]
     pub enable_mandatory: bool,
+    /// Where to find justifications of mandatory headers, in priority order
+    #[serde(default = "JustificationSource::default_priority")]
+    pub justification_sources: Vec<JustificationSource>,
+    /// Go through the whole relay decision path but only log the calls, nothing is signed.
+    /// Usually set by `--dry-run` of start command.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Submit the missing header with messages proof in one `utility.batch_all` extrinsic
+    /// instead of waiting for header relay, it's only supported by solo with solo bridge.
+    #[serde(default)]
+    pub bundle_header: bool,
+    /// When to confirm delivered messages, confirm them as soon as delivered if not set.
+    #[serde(default)]
+    pub confirmation_policy: ConfirmationPolicy,
+    /// Where to keep relay state (last relayed nonces, recently justifications)
+    #[serde(default)]
+    pub keepstate: KeepstateKind,
+    /// Where to find the blocks need to relay
+    #[serde(default)]
+    pub indexer: IndexerKind,
+    /// Token economy of chains, keyed by chain name. When both chains of a message direction
+    /// are configured, the delivery only relays profitable orders, otherwise use basic strategy.
+    #[serde(default)]
+    pub economy: HashMap<String, ChainEconomyConfig>,
+    /// Limits of `receive_messages_proof` keyed by the name of chain which receives messages,
+    /// they override the limits read from runtime, and fill the limits it doesn't expose.
+    #[serde(default)]
+    pub delivery_limits: HashMap<String, MessagesDeliveryLimits>,
 }
 
 impl RelayConfig {
     pub fn raw_lanes(&self) -> Vec<[u8; 4]> {
         self.lanes.iter().map(|item| item.0).collect()
     }
+
+    /// Create keepstate for this bridge, the microkv keepstate is stored in `<bridge>.kv`
+    pub fn keepstate(&self, bridge: impl AsRef<str>) -> color_eyre::Result<Keepstate> {
+        match self.keepstate {
+            KeepstateKind::Memory => Ok(Keepstate::memory()),
+            KeepstateKind::Microkv => {
+                let bridge = bridge.as_ref();
+                let state = BridgeState::new(StateOptions {
+                    db_name: bridge.to_string(),
+                })?;
+                Ok(Keepstate::microkv(state.microkv_with_namespace(bridge)))
+            }
+        }
+    }
+
+    /// Microkv of chain scan indexer for this bridge, it's stored in `<bridge>-chain-scan.kv`.
+    /// None if the blocks are indexed by subquery.
+    pub fn chain_scan_state(
+        &self,
+        bridge: impl AsRef<str>,
+    ) -> color_eyre::Result<Option<NamespaceMicroKV>> {
+        match self.indexer {
+            IndexerKind::Subquery => Ok(None),
+            IndexerKind::ChainScan => {
+                let bridge = bridge.as_ref();
+                let state = BridgeState::new(StateOptions {
+                    db_name: format!("{bridge}-chain-scan"),
+                })?;
+                Ok(Some(state.microkv_with_namespace(bridge)))
+            }
+        }
+    }
+
+    /// Configured limits of target chain for messages delivery
+    pub fn target_delivery_limits(&self, target: &ChainName) -> MessagesDeliveryLimits {
+        self.delivery_limits
+            .get(target.name())
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Create relay strategy for messages delivery from source chain to target chain
+    pub fn relay_strategy<A: FeemarketApiRelay>(
+        &self,
+        api: A,
+        account: <A::Chain as Chain>::AccountId,
+        source: &ChainName,
+        target: &ChainName,
+    ) -> BinS2SResult<FeemarketRelayStrategy<A>> {
+        let economy_source = self.economy.get(source.name());
+        let economy_target = self.economy.get(target.name());
+        match (economy_source, economy_target) {
+            (Some(economy_source), Some(economy_target)) => {
+                let strategy = ProfitableRelayStrategy::new(
+                    api,
+                    account,
+                    ChainEconomy::new(economy_source.clone())?,
+                    ChainEconomy::new(economy_target.clone())?,
+                );
+                Ok(strategy.into())
+            }
+            _ => Ok(BasicRelayStrategy::new(api, account).into()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FeemarketConfig {
+    /// Seconds between two checks of relayer fee and collateral
+    #[serde(default = "default_feemarket_interval")]
+    pub interval: u64,
+    /// Quote policy keyed by chain name, the fee market of chain which isn't configured is
+    /// not managed.
+    #[serde(default)]
+    pub quote: HashMap<String, QuotePolicy>,
+}
+
+fn default_feemarket_interval() -> u64 {
+    60
+}
+
+impl Default for FeemarketConfig {
+    fn default() -> Self {
+        Self {
+            interval: default_feemarket_interval(),
+            quote: Default::default(),
+        }
+    }
+}
+
+impl FeemarketConfig {
+    pub fn quote_policy(&self, chain: &ChainName) -> Option<QuotePolicy> {
+        self.quote.get(chain.name()).cloned()
+    }
+}
+
+/// Keepstate backend
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KeepstateKind {
+    /// Keep state in memory, lost after restart
+    Memory,
+    /// Persist state to microkv
+    Microkv,
+}
+
+impl Default for KeepstateKind {
+    fn default() -> Self {
+        Self::Memory
+    }
+}
+
+/// Indexer of blocks need to relay
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexerKind {
+    /// Query subquery of `index` config
+    Subquery,
+    /// Scan finalized blocks over chain rpc, only solo with solo bridge supported
+    ChainScan,
+}
+
+impl Default for IndexerKind {
+    fn default() -> Self {
+        Self::Subquery
+    }
 }
 
 #[cfg(feature = "solo-with-para")]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct SoloWithParaConfig {
     pub para_id: u32,
+    /// Other parachains of the relay chain, their heads are relayed with the bridged parachain
+    #[serde(default)]
+    pub extra_para_ids: Vec<u32>,
+}
+
+#[cfg(feature = "solo-with-para")]
+impl SoloWithParaConfig {
+    /// Parachains whose heads are relayed to solo chain
+    pub fn header_para_ids(&self) -> Vec<u32> {
+        merge_para_ids(self.para_id, &self.extra_para_ids)
+    }
 }
 
 #[cfg(any(feature = "para-with-para"))]
@@ -27,4 +207,35 @@ pub struct SoloWithParaConfig {
 pub struct ParaWithParaConfig {
     pub source_para_id: u32,
     pub target_para_id: u32,
+    /// Other parachains of source relay chain, their heads are relayed to target parachain
+    #[serde(default)]
+    pub source_extra_para_ids: Vec<u32>,
+    /// Other parachains of target relay chain, their heads are relayed to source parachain
+    #[serde(default)]
+    pub target_extra_para_ids: Vec<u32>,
+}
+
+#[cfg(any(feature = "para-with-para"))]
+impl ParaWithParaConfig {
+    /// Parachains whose heads are relayed from source relay chain to target parachain
+    pub fn source_header_para_ids(&self) -> Vec<u32> {
+        merge_para_ids(self.source_para_id, &self.source_extra_para_ids)
+    }
+
+    /// Parachains whose heads are relayed from target relay chain to source parachain
+    pub fn target_header_para_ids(&self) -> Vec<u32> {
+        merge_para_ids(self.target_para_id, &self.target_extra_para_ids)
+    }
+}
+
+/// The bridged parachain goes first, duplicated ids are removed
+#[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
+fn merge_para_ids(para_id: u32, extra_para_ids: &[u32]) -> Vec<u32> {
+    let mut para_ids = vec![para_id];
+    for extra in extra_para_ids {
+        if !para_ids.contains(extra) {
+            para_ids.push(*extra);
+        }
+    }
+    para_ids
 }
@@ -1,7 +1,7 @@
 use lifeline::Storage;
 use serde::{Deserialize, Serialize};
 
-use crate::bridge::config::{ParaWithParaConfig, RelayConfig};
+use crate::bridge::config::{FeemarketConfig, ParaWithParaConfig, RelayConfig};
 use crate::bridge::BridgeBus;
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
 
@@ -18,6 +18,9 @@ pub struct BridgeConfig<
     pub chain: ChainConfig<SRCI, SPCI, TRCI, TPCI>,
     /// Relay config
     pub relay: RelayConfig,
+    /// Fee market config
+    #[serde(default)]
+    pub feemarket: FeemarketConfig,
     /// Para config
     pub para_config: ParaWithParaConfig,
     /// Index config
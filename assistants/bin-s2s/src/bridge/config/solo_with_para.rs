@@ -1,7 +1,7 @@
 use lifeline::Storage;
 use serde::{Deserialize, Serialize};
 
-use crate::bridge::config::{RelayConfig, SoloWithParaConfig};
+use crate::bridge::config::{FeemarketConfig, RelayConfig, SoloWithParaConfig};
 use crate::bridge::BridgeBus;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
@@ -20,6 +20,9 @@ pub struct BridgeConfig<
     pub chain: ChainConfig<SCI, RCI, PCI>,
     /// Relay config
     pub relay: RelayConfig,
+    /// Fee market config
+    #[serde(default)]
+    pub feemarket: FeemarketConfig,
     /// Para config
     pub para_config: SoloWithParaConfig,
     /// Index config
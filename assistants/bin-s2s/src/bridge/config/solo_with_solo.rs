@@ -1,8 +1,9 @@
 use lifeline::Storage;
+use relay_s2s::indexer::{ChainScanIndexer, Indexer};
 use serde::{Deserialize, Serialize};
 
-use crate::bridge::config::RelayConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::config::{FeemarketConfig, RelayConfig};
+use crate::bridge::{BridgeBus, BridgeIndexer};
 use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
 
 /// Solo with solo bridge config
@@ -16,10 +17,44 @@ pub struct BridgeConfig<
     pub chain: ChainConfig<SCI, TCI>,
     /// Relay config
     pub relay: RelayConfig,
+    /// Fee market config
+    #[serde(default)]
+    pub feemarket: FeemarketConfig,
     /// Index config
     pub index: IndexConfig<SI>,
 }
 
+impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
+    BridgeConfig<SCI, TCI, SI>
+{
+    /// Create indexers of both chains, the on-demand blocks of a chain are needed by the other
+    pub fn indexer(&self) -> color_eyre::Result<BridgeIndexer> {
+        let source = self.chain.source.chain();
+        let target = self.chain.target.chain();
+        let bridge = format!("{}-{}", source.name(), target.name());
+        match self.relay.chain_scan_state(bridge)? {
+            Some(microkv) => Ok(BridgeIndexer {
+                source: ChainScanIndexer::new(
+                    microkv.clone(),
+                    source.name(),
+                    self.chain.target.origin_type(),
+                )
+                .into(),
+                target: ChainScanIndexer::new(
+                    microkv,
+                    target.name(),
+                    self.chain.source.origin_type(),
+                )
+                .into(),
+            }),
+            None => Ok(BridgeIndexer {
+                source: Indexer::Subquery(self.index.source.subquery()?),
+                target: Indexer::Subquery(self.index.target.subquery()?),
+            }),
+        }
+    }
+}
+
 impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo> Storage
     for BridgeConfig<SCI, TCI, SI>
 {
@@ -5,7 +5,9 @@ use lifeline::dyn_bus::DynBus;
 use support_lifeline::task::TaskStack;
 
 use crate::bridge::config::para_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::config::IndexerKind;
+use crate::bridge::{BridgeBus, BridgeFeemarket, BridgeKeepstate};
+use crate::error::BinS2SError;
 use crate::service::feemarket::FeemarketService;
 use crate::service::para_with_para::{
     SourceToTargetMessageRelayService, SourceToTargetParaHeadRelayService,
@@ -14,6 +16,7 @@ use crate::service::para_with_para::{
     TargetToSourceRelaychainGrandpaRelayService,
 };
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
+use crate::types::{BridgeLaneStatus, ParaBridgeSoloChainFeemarketInfo};
 
 #[derive(Debug)]
 pub struct BridgeTask<
@@ -42,10 +45,36 @@ impl<
     pub fn new(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
     ) -> color_eyre::Result<Self> {
+        if matches!(bridge_config.relay.indexer, IndexerKind::ChainScan) {
+            return Err(BinS2SError::Custom(
+                "The chain scan indexer isn't supported by para with para bridge".to_string(),
+            )
+            .into());
+        }
+        if bridge_config.relay.bundle_header {
+            return Err(BinS2SError::Custom(
+                "The bundled header isn't supported by para with para bridge".to_string(),
+            )
+            .into());
+        }
         let bus = BridgeBus::default();
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.source_para.chain().name(),
+            bridge_config.chain.target_para.chain().name(),
+        ))?;
         let mut stack = TaskStack::new(bus);
+        stack.bus().store_resource(BridgeFeemarket {
+            config: bridge_config.feemarket.clone(),
+            source: ParaBridgeSoloChainFeemarketInfo(bridge_config.chain.source_para.clone()),
+            target: ParaBridgeSoloChainFeemarketInfo(bridge_config.chain.target_para.clone()),
+        });
         stack.bus().store_resource(bridge_config);
-        stack.spawn_service::<FeemarketService>()?;
+        stack.bus().store_resource(BridgeKeepstate(keepstate));
+        stack.spawn_service::<FeemarketService<
+            ParaBridgeSoloChainFeemarketInfo<SPCI>,
+            ParaBridgeSoloChainFeemarketInfo<TPCI>,
+        >>()?;
         stack.spawn_service::<SubscribeService<SRCI, SPCI, TRCI, TPCI, SI>>()?;
         stack.spawn_service::<SourceToTargetParaHeadRelayService<SRCI, SPCI, TRCI, TPCI, SI>>()?;
         stack.spawn_service::<SourceToTargetRelaychainGrandpaRelayService<SRCI, SPCI, TRCI, TPCI, SI>>()?;
@@ -63,6 +92,47 @@ impl<
             _subquery_info: Default::default(),
         })
     }
+
+    /// Message delivery status of all lanes in both directions, nothing will be submitted
+    pub async fn status(
+        bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+    ) -> color_eyre::Result<Vec<BridgeLaneStatus>> {
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.source_para.chain().name(),
+            bridge_config.chain.target_para.chain().name(),
+        ))?;
+        let source = bridge_config.chain.source_para.chain();
+        let target = bridge_config.chain.target_para.chain();
+        let lanes = bridge_config.relay.raw_lanes();
+        let source_to_target =
+            SourceToTargetMessageRelayService::<SRCI, SPCI, TRCI, TPCI, SI>::delivery_runner(
+                bridge_config.clone(),
+                keepstate.clone(),
+            )
+            .await?;
+        let target_to_source =
+            TargetToSourceMessageRelayService::<SRCI, SPCI, TRCI, TPCI, SI>::delivery_runner(
+                bridge_config,
+                keepstate,
+            )
+            .await?;
+
+        let mut statuses = Vec::with_capacity(lanes.len() * 2);
+        for lane in lanes {
+            statuses.push(BridgeLaneStatus {
+                source: source.clone(),
+                target: target.clone(),
+                status: source_to_target.status(lane).await?,
+            });
+            statuses.push(BridgeLaneStatus {
+                source: target.clone(),
+                target: source.clone(),
+                status: target_to_source.status(lane).await?,
+            });
+        }
+        Ok(statuses)
+    }
 }
 
 impl<
@@ -5,7 +5,9 @@ use lifeline::dyn_bus::DynBus;
 use support_lifeline::task::TaskStack;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::config::IndexerKind;
+use crate::bridge::{BridgeBus, BridgeFeemarket, BridgeKeepstate};
+use crate::error::BinS2SError;
 use crate::service::feemarket::FeemarketService;
 use crate::service::solo_with_para::{
     ParaHeadToSolochainRelayService, ParachainToSolochainMessageRelayService,
@@ -16,6 +18,7 @@ use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
     SubqueryInfo,
 };
+use crate::types::{BridgeLaneStatus, ParaBridgeSoloChainFeemarketInfo, SoloChainFeemarketInfo};
 
 #[derive(Debug)]
 pub struct BridgeTask<
@@ -39,11 +42,34 @@ impl<
     > BridgeTask<SCI, RCI, PCI, SI>
 {
     pub fn new(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> color_eyre::Result<Self> {
+        if matches!(bridge_config.relay.indexer, IndexerKind::ChainScan) {
+            return Err(BinS2SError::Custom(
+                "The chain scan indexer isn't supported by solo with para bridge".to_string(),
+            )
+            .into());
+        }
+        if bridge_config.relay.bundle_header {
+            return Err(BinS2SError::Custom(
+                "The bundled header isn't supported by solo with para bridge".to_string(),
+            )
+            .into());
+        }
         let bus = BridgeBus::default();
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.solo.chain().name(),
+            bridge_config.chain.para.chain().name(),
+        ))?;
         let mut stack = TaskStack::new(bus);
+        stack.bus().store_resource(BridgeFeemarket {
+            config: bridge_config.feemarket.clone(),
+            source: ParaBridgeSoloChainFeemarketInfo(bridge_config.chain.solo.clone()),
+            target: SoloChainFeemarketInfo(bridge_config.chain.para.clone()),
+        });
         stack.bus().store_resource(bridge_config);
+        stack.bus().store_resource(BridgeKeepstate(keepstate));
         stack.spawn_service::<SubscribeService<SCI, RCI, PCI, SI>>()?;
-        stack.spawn_service::<FeemarketService>()?;
+        stack.spawn_service::<FeemarketService<ParaBridgeSoloChainFeemarketInfo<SCI>, SoloChainFeemarketInfo<PCI>>>()?;
         stack.spawn_service::<SolochainToParachainHeaderRelayService<SCI, RCI, PCI, SI>>()?;
         stack.spawn_service::<RelaychainToSolochainHeaderRelayService<SCI, RCI, PCI, SI>>()?;
         stack.spawn_service::<ParaHeadToSolochainRelayService<SCI, RCI, PCI, SI>>()?;
@@ -58,6 +84,47 @@ impl<
             _subquery_info: Default::default(),
         })
     }
+
+    /// Message delivery status of all lanes in both directions, nothing will be submitted
+    pub async fn status(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+    ) -> color_eyre::Result<Vec<BridgeLaneStatus>> {
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.solo.chain().name(),
+            bridge_config.chain.para.chain().name(),
+        ))?;
+        let source = bridge_config.chain.solo.chain();
+        let target = bridge_config.chain.para.chain();
+        let lanes = bridge_config.relay.raw_lanes();
+        let source_to_target =
+            SolochainToParachainMessageRelayService::<SCI, RCI, PCI, SI>::delivery_runner(
+                bridge_config.clone(),
+                keepstate.clone(),
+            )
+            .await?;
+        let target_to_source =
+            ParachainToSolochainMessageRelayService::<SCI, RCI, PCI, SI>::delivery_runner(
+                bridge_config,
+                keepstate,
+            )
+            .await?;
+
+        let mut statuses = Vec::with_capacity(lanes.len() * 2);
+        for lane in lanes {
+            statuses.push(BridgeLaneStatus {
+                source: source.clone(),
+                target: target.clone(),
+                status: source_to_target.status(lane).await?,
+            });
+            statuses.push(BridgeLaneStatus {
+                source: target.clone(),
+                target: source.clone(),
+                status: target_to_source.status(lane).await?,
+            });
+        }
+        Ok(statuses)
+    }
 }
 
 impl<
@@ -5,14 +5,16 @@ use lifeline::dyn_bus::DynBus;
 use support_lifeline::task::TaskStack;
 
 use crate::bridge::config::solo_with_solo::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::config::IndexerKind;
+use crate::bridge::{BridgeBus, BridgeFeemarket, BridgeKeepstate};
 use crate::service::feemarket::FeemarketService;
-use crate::service::solo_with_solo::SubscribeService;
+use crate::service::solo_with_solo::{ChainScanService, SubscribeService};
 use crate::service::solo_with_solo::{
     SourceToTargetHeaderRelayService, SourceToTargetMessageRelayService,
     TargetToSourceHeaderRelayService, TargetToSourceMessageRelayService,
 };
 use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
+use crate::types::{BridgeLaneStatus, SoloChainFeemarketInfo};
 
 #[derive(Debug)]
 pub struct BridgeTask<
@@ -31,10 +33,27 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 {
     pub fn new(bridge_config: BridgeConfig<SCI, TCI, SI>) -> color_eyre::Result<Self> {
         let bus = BridgeBus::default();
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.source.chain().name(),
+            bridge_config.chain.target.chain().name(),
+        ))?;
+        let indexer = bridge_config.indexer()?;
+        let chain_scan = matches!(bridge_config.relay.indexer, IndexerKind::ChainScan);
         let mut stack = TaskStack::new(bus);
+        stack.bus().store_resource(BridgeFeemarket {
+            config: bridge_config.feemarket.clone(),
+            source: SoloChainFeemarketInfo(bridge_config.chain.source.clone()),
+            target: SoloChainFeemarketInfo(bridge_config.chain.target.clone()),
+        });
         stack.bus().store_resource(bridge_config);
+        stack.bus().store_resource(BridgeKeepstate(keepstate));
+        stack.bus().store_resource(indexer);
         stack.spawn_service::<SubscribeService<SCI, TCI, SI>>()?;
-        stack.spawn_service::<FeemarketService>()?;
+        if chain_scan {
+            stack.spawn_service::<ChainScanService<SCI, TCI, SI>>()?;
+        }
+        stack.spawn_service::<FeemarketService<SoloChainFeemarketInfo<SCI>, SoloChainFeemarketInfo<TCI>>>()?;
         stack.spawn_service::<SourceToTargetHeaderRelayService<SCI, TCI, SI>>()?;
         stack.spawn_service::<TargetToSourceHeaderRelayService<SCI, TCI, SI>>()?;
         stack.spawn_service::<SourceToTargetMessageRelayService<SCI, TCI, SI>>()?;
@@ -47,6 +66,48 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
             _subquery_info: Default::default(),
         })
     }
+
+    /// Message delivery status of all lanes in both directions, nothing will be submitted
+    pub async fn status(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+    ) -> color_eyre::Result<Vec<BridgeLaneStatus>> {
+        let keepstate = bridge_config.relay.keepstate(format!(
+            "{}-{}",
+            bridge_config.chain.source.chain().name(),
+            bridge_config.chain.target.chain().name(),
+        ))?;
+        let indexer = bridge_config.indexer()?;
+        let source = bridge_config.chain.source.chain();
+        let target = bridge_config.chain.target.chain();
+        let lanes = bridge_config.relay.raw_lanes();
+        let source_to_target = SourceToTargetMessageRelayService::<SCI, TCI, SI>::delivery_runner(
+            bridge_config.clone(),
+            keepstate.clone(),
+            indexer.clone(),
+        )
+        .await?;
+        let target_to_source = TargetToSourceMessageRelayService::<SCI, TCI, SI>::delivery_runner(
+            bridge_config,
+            keepstate,
+            indexer,
+        )
+        .await?;
+
+        let mut statuses = Vec::with_capacity(lanes.len() * 2);
+        for lane in lanes {
+            statuses.push(BridgeLaneStatus {
+                source: source.clone(),
+                target: target.clone(),
+                status: source_to_target.status(lane).await?,
+            });
+            statuses.push(BridgeLaneStatus {
+                source: target.clone(),
+                target: source.clone(),
+                status: target_to_source.status(lane).await?,
+            });
+        }
+        Ok(statuses)
+    }
 }
 
 impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
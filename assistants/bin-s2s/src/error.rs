@@ -1,3 +1,4 @@
+use feemarket_s2s::error::FeemarketError;
 use relay_s2s::error::RelayError;
 use thiserror::Error as ThisError;
 
@@ -7,6 +8,8 @@ pub type BinS2SResult<T> = Result<T, BinS2SError>;
 pub enum BinS2SError {
     #[error(transparent)]
     Relay(#[from] RelayError),
+    #[error(transparent)]
+    Feemarket(#[from] FeemarketError),
     #[error("Lifeline: {0}")]
     Lifeline(String),
     #[error("Client: {0}")]
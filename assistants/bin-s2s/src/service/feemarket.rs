@@ -1,22 +1,101 @@
+use std::marker::PhantomData;
+
+use feemarket_s2s::quote::{QuotePolicy, QuoteRunner};
+use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 
 use support_lifeline::service::BridgeService;
 
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeFeemarket};
+use crate::error::BinS2SResult;
+use crate::traits::S2SFeemarketChainInfo;
 
 #[derive(Debug)]
-pub struct FeemarketService {
-    _greet: Lifeline,
+pub struct FeemarketService<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> {
+    _greet_source: Option<Lifeline>,
+    _greet_target: Option<Lifeline>,
+    _source_chain_info: PhantomData<SCI>,
+    _target_chain_info: PhantomData<TCI>,
 }
 
-impl BridgeService for FeemarketService {}
+impl<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> BridgeService
+    for FeemarketService<SCI, TCI>
+{
+}
 
-impl Service for FeemarketService {
+impl<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> Service
+    for FeemarketService<SCI, TCI>
+{
     type Bus = BridgeBus;
     type Lifeline = color_eyre::Result<Self>;
 
-    fn spawn(_bus: &Self::Bus) -> Self::Lifeline {
-        let _greet = Self::try_task("feemarket-service", async move { Ok(()) });
-        Ok(Self { _greet })
+    fn spawn(bus: &Self::Bus) -> Self::Lifeline {
+        let feemarket: BridgeFeemarket<SCI, TCI> = bus.storage().clone_resource()?;
+        let interval = feemarket.config.interval;
+
+        let _greet_source = match feemarket.config.quote_policy(&feemarket.source.chain()) {
+            Some(policy) => {
+                let source = feemarket.source.clone();
+                let task_name = format!("{}-feemarket-service", source.chain().name());
+                Some(Self::try_task(&task_name, async move {
+                    Self::start(source, policy, interval).await;
+                    Ok(())
+                }))
+            }
+            None => None,
+        };
+        let _greet_target = match feemarket.config.quote_policy(&feemarket.target.chain()) {
+            Some(policy) => {
+                let target = feemarket.target.clone();
+                let task_name = format!("{}-feemarket-service", target.chain().name());
+                Some(Self::try_task(&task_name, async move {
+                    Self::start(target, policy, interval).await;
+                    Ok(())
+                }))
+            }
+            None => None,
+        };
+        Ok(Self {
+            _greet_source,
+            _greet_target,
+            _source_chain_info: Default::default(),
+            _target_chain_info: Default::default(),
+        })
+    }
+}
+
+impl<SCI: S2SFeemarketChainInfo, TCI: S2SFeemarketChainInfo> FeemarketService<SCI, TCI> {
+    async fn start<CI: S2SFeemarketChainInfo>(chain_info: CI, policy: QuotePolicy, interval: u64) {
+        while let Err(e) = Self::run(&chain_info, policy.clone(), interval).await {
+            tracing::error!(
+                target: "bin-s2s",
+                "[feemarket] [{}] an error occurred for quote {:?}",
+                chain_info.chain().name(),
+                e,
+            );
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+            tracing::info!(
+                target: "bin-s2s",
+                "[feemarket] [{}] try to restart feemarket service.",
+                chain_info.chain().name(),
+            );
+        }
+    }
+
+    async fn run<CI: S2SFeemarketChainInfo>(
+        chain_info: &CI,
+        policy: QuotePolicy,
+        interval: u64,
+    ) -> BinS2SResult<()> {
+        tracing::info!(
+            target: "bin-s2s",
+            "[feemarket] [{}] SERVICE RESTARTING...",
+            chain_info.chain().name(),
+        );
+        let runner = QuoteRunner::new(chain_info.client().await?, chain_info.account()?, policy);
+        loop {
+            runner.run().await?;
+            tokio::time::sleep(std::time::Duration::from_secs(interval)).await;
+        }
     }
 }
@@ -0,0 +1,47 @@
+use std::future::Future;
+
+use bridge_s2s_traits::client::S2SClientGeneric;
+use relay_s2s::keepstate::Keepstate;
+use relay_s2s::subscribe::SubscribeFinality;
+use relay_s2s::types::FinalityInput;
+
+use support_types::mark::ChainName;
+
+use crate::error::BinS2SResult;
+
+/// Subscribe finalized heads of one chain of the bridge, the relay runners which depend on
+/// this chain are woken by them
+pub(crate) async fn subscribe_finality<C, F, Fut>(chain: ChainName, keepstate: Keepstate, client: F)
+where
+    C: S2SClientGeneric,
+    F: Fn() -> Fut,
+    Fut: Future<Output = BinS2SResult<C>>,
+{
+    while let Err(e) = start_subscribe_finality(keepstate.clone(), &client).await {
+        tracing::error!(
+            target: "bin-s2s",
+            "[subscribe] [{}] failed to start finality subscribe {:?}",
+            chain.name(),
+            e,
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        tracing::info!(
+            target: "bin-s2s",
+            "[subscribe] [{}] try to restart finality subscription service.",
+            chain.name(),
+        );
+    }
+}
+
+async fn start_subscribe_finality<C, F, Fut>(keepstate: Keepstate, client: &F) -> BinS2SResult<()>
+where
+    C: S2SClientGeneric,
+    F: Fn() -> Fut,
+    Fut: Future<Output = BinS2SResult<C>>,
+{
+    let client = client().await?;
+    let input = FinalityInput { client, keepstate };
+    let subscribe = SubscribeFinality::new(input);
+    subscribe.start().await?;
+    Ok(())
+}
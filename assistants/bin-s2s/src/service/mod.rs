@@ -5,3 +5,10 @@ pub mod para_with_para;
 pub mod solo_with_para;
 #[cfg(feature = "solo-with-solo")]
 pub mod solo_with_solo;
+
+#[cfg(any(
+    feature = "solo-with-solo",
+    feature = "solo-with-para",
+    feature = "para-with-para"
+))]
+mod finality;
@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::ParaHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::ParaHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::para_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
 
@@ -53,6 +54,7 @@ impl<
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-parahead-relay-service",
@@ -62,7 +64,7 @@ impl<
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start(bridge_config.clone()).await {
+            while let Err(e) = Self::start(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -109,7 +111,10 @@ impl<
         SI: SubqueryInfo,
     > SourceToTargetParaHeadRelayService<SRCI, SPCI, TRCI, TPCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let para_config = bridge_config.para_config;
         let config_chain = bridge_config.chain;
         tracing::info!(
@@ -122,7 +127,9 @@ impl<
         let input = ParaHeaderInput {
             client_relaychain: config_chain.source_relay.client().await?,
             client_solochain: config_chain.target_para.client().await?,
-            para_id: para_config.source_para_id,
+            para_ids: para_config.source_header_para_ids(),
+            dry_run: bridge_config.relay.dry_run,
+            keepstate,
         };
         let runner = ParaHeaderRunner::new(input);
         Ok(runner.start().await?)
@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::RelaychainHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::RelaychainHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::para_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
 
@@ -53,6 +54,7 @@ impl<
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-header-relay-service",
@@ -62,7 +64,7 @@ impl<
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start(bridge_config.clone()).await {
+            while let Err(e) = Self::start(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -109,7 +111,10 @@ impl<
         SI: SubqueryInfo,
     > TargetToSourceRelaychainGrandpaRelayService<SRCI, SPCI, TRCI, TPCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let relay_config = bridge_config.relay;
         let config_chain = bridge_config.chain;
         let config_index = bridge_config.index;
@@ -123,10 +128,13 @@ impl<
         let input = RelaychainHeaderInput {
             client_relaychain: config_chain.target_relay.client().await?,
             client_solochain: config_chain.source_para.client().await?,
-            subquery_relaychain: config_index.target_relay.subquery()?,
-            subquery_parachain: config_index.target_para.subquery()?,
+            subquery_relaychain: config_index.target_relay.subquery()?.into(),
+            subquery_parachain: config_index.target_para.subquery()?.into(),
             index_origin_type: config_chain.source_para.origin_type(),
             enable_mandatory: relay_config.enable_mandatory,
+            justification_sources: relay_config.justification_sources,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         let runner = RelaychainHeaderRunner::new(input);
         Ok(runner.start().await?)
@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 
-use feemarket_s2s::relay::basic::BasicRelayStrategy;
+use feemarket_s2s::relay::strategy::FeemarketRelayStrategy;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::message::{BridgeParachainDeliveryRunner, BridgeParachainReceivingRunner};
 use relay_s2s::types::{MessageDeliveryInput, MessageReceivingInput};
 
@@ -10,7 +11,7 @@ use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::para_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
 
@@ -55,6 +56,7 @@ impl<
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_delivery_name = format!(
             "{}-{}-message-delivery-service",
@@ -64,7 +66,8 @@ impl<
 
         let _greet_delivery = Self::try_task(&task_delivery_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_delivery(bridge_config.clone()).await {
+            while let Err(e) = Self::start_delivery(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message delivery relay {:?}",
@@ -95,6 +98,7 @@ impl<
 
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_receiving_name = format!(
             "{}-{}-message-receiving-service",
@@ -104,7 +108,8 @@ impl<
 
         let _greet_receiving = Self::try_task(&task_receiving_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_receiving(bridge_config.clone()).await {
+            while let Err(e) = Self::start_receiving(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message receiving relay {:?}",
@@ -154,6 +159,7 @@ impl<
 {
     async fn message_input(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
     ) -> BinS2SResult<
         MessageReceivingInput<
             <SPCI as S2SParaBridgeSoloChainInfo>::Client,
@@ -171,28 +177,36 @@ impl<
             relayer_account: config_chain.source_para.account()?,
             client_source: config_chain.source_para.client().await?,
             client_target: config_chain.target_para.client().await?,
-            subquery_source: config_index.source_para.subquery()?,
-            subquery_target: config_index.target_para.subquery()?,
+            subquery_source: config_index.source_para.subquery()?.into(),
+            subquery_target: config_index.target_para.subquery()?.into(),
+            bundle_header: relay_config.bundle_header,
+            confirmation_policy: relay_config.confirmation_policy,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         Ok(input)
     }
 
-    async fn start_delivery(
+    pub(crate) async fn delivery_runner(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
-    ) -> BinS2SResult<()> {
+        keepstate: Keepstate,
+    ) -> BinS2SResult<
+        BridgeParachainDeliveryRunner<
+            <SPCI as S2SParaBridgeSoloChainInfo>::Client,
+            <TPCI as S2SParaBridgeSoloChainInfo>::Client,
+            FeemarketRelayStrategy<<SPCI as S2SParaBridgeSoloChainInfo>::Client>,
+        >,
+    > {
         let config_chain = bridge_config.chain.clone();
-        tracing::info!(
-            target: "bin-s2s",
-            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
-            config_chain.source_para.chain().name(),
-            config_chain.target_para.chain().name(),
-        );
         let config_para = bridge_config.para_config.clone();
-        let input = Self::message_input(bridge_config).await?;
-        let relay_strategy = BasicRelayStrategy::new(
+        let config_relay = bridge_config.relay.clone();
+        let input = Self::message_input(bridge_config, keepstate).await?;
+        let relay_strategy = config_relay.relay_strategy(
             input.client_source.clone(),
             config_chain.source_para.account()?,
-        );
+            &config_chain.source_para.chain(),
+            &config_chain.target_para.chain(),
+        )?;
         let input = MessageDeliveryInput {
             lanes: input.lanes,
             nonces_limit: 11,
@@ -203,13 +217,35 @@ impl<
             subquery_target: input.subquery_target,
             relay_block_origin: config_chain.target_para.origin_type(),
             relay_strategy,
+            bundle_header: input.bundle_header,
+            dry_run: input.dry_run,
+            delivery_limits: config_relay.target_delivery_limits(&config_chain.target_para.chain()),
+            keepstate: input.keepstate,
         };
-        let runner = BridgeParachainDeliveryRunner::new(input, config_para.source_para_id);
+        Ok(BridgeParachainDeliveryRunner::new(
+            input,
+            config_para.source_para_id,
+        ))
+    }
+
+    async fn start_delivery(
+        bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
+        let config_chain = bridge_config.chain.clone();
+        tracing::info!(
+            target: "bin-s2s",
+            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
+            config_chain.source_para.chain().name(),
+            config_chain.target_para.chain().name(),
+        );
+        let runner = Self::delivery_runner(bridge_config, keepstate).await?;
         Ok(runner.start().await?)
     }
 
     async fn start_receiving(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
     ) -> BinS2SResult<()> {
         tracing::info!(
             target: "bin-s2s",
@@ -218,7 +254,7 @@ impl<
             bridge_config.chain.target_para.chain().name(),
         );
         let config_para = bridge_config.para_config.clone();
-        let input = Self::message_input(bridge_config).await?;
+        let input = Self::message_input(bridge_config, keepstate).await?;
         let runner = BridgeParachainReceivingRunner::new(input, config_para.target_para_id);
         Ok(runner.start().await?)
     }
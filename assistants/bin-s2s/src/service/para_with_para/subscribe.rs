@@ -2,14 +2,16 @@ use std::marker::PhantomData;
 
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::subscribe::SubscribeJustification;
 use relay_s2s::types::JustificationInput;
 
 use support_lifeline::service::BridgeService;
 
 use crate::bridge::config::para_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
+use crate::service::finality::subscribe_finality;
 use crate::traits::{S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, SubqueryInfo};
 
 #[derive(Debug)]
@@ -22,6 +24,10 @@ pub struct SubscribeService<
 > {
     _greet_source: Lifeline,
     _greet_target: Lifeline,
+    _finality_source_relaychain: Lifeline,
+    _finality_source_parachain: Lifeline,
+    _finality_target_relaychain: Lifeline,
+    _finality_target_parachain: Lifeline,
     _source_parachain_info: PhantomData<SPCI>,
     _source_relaychain_info: PhantomData<SRCI>,
     _target_parachain_info: PhantomData<TPCI>,
@@ -53,11 +59,12 @@ impl<
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.source_relay.chain().name(),);
 
         let _greet_source = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_source(bridge_config.clone()).await {
+            while let Err(e) = Self::start_source(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -75,10 +82,11 @@ impl<
         });
         let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
             bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.target_relay.chain().name(),);
         let _greet_target = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_target(bridge_config.clone()).await {
+            while let Err(e) = Self::start_target(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -94,9 +102,69 @@ impl<
             }
             Ok(())
         });
+        let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
+            bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.source_relay.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_source_relaychain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
+            bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.source_para.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_source_parachain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
+            bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.target_relay.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_target_relaychain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI> =
+            bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.target_para.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_target_parachain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
         Ok(Self {
             _greet_source,
             _greet_target,
+            _finality_source_relaychain,
+            _finality_source_parachain,
+            _finality_target_relaychain,
+            _finality_target_parachain,
             _source_parachain_info: Default::default(),
             _source_relaychain_info: Default::default(),
             _target_parachain_info: Default::default(),
@@ -116,11 +184,12 @@ impl<
 {
     async fn start_source(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
     ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.source_relay.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
@@ -128,11 +197,12 @@ impl<
 
     async fn start_target(
         bridge_config: BridgeConfig<SRCI, SPCI, TRCI, TPCI, SI>,
+        keepstate: Keepstate,
     ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.target_relay.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::ParaHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::ParaHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
@@ -51,6 +52,7 @@ impl<
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-parahead-relay-service",
@@ -60,7 +62,7 @@ impl<
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start(bridge_config.clone()).await {
+            while let Err(e) = Self::start(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -105,7 +107,10 @@ impl<
         SI: SubqueryInfo,
     > ParaHeadToSolochainRelayService<SCI, RCI, PCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let para_config = bridge_config.para_config;
         let config_chain = bridge_config.chain;
         tracing::info!(
@@ -118,7 +123,9 @@ impl<
         let input = ParaHeaderInput {
             client_relaychain: config_chain.relay.client().await?,
             client_solochain: config_chain.solo.client().await?,
-            para_id: para_config.para_id,
+            para_ids: para_config.header_para_ids(),
+            dry_run: bridge_config.relay.dry_run,
+            keepstate,
         };
         let runner = ParaHeaderRunner::new(input);
         Ok(runner.start().await?)
@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::RelaychainHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::RelaychainHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
@@ -51,6 +52,7 @@ impl<
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-header-relay-service",
@@ -60,7 +62,7 @@ impl<
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start(bridge_config.clone()).await {
+            while let Err(e) = Self::start(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -105,7 +107,10 @@ impl<
         SI: SubqueryInfo,
     > RelaychainToSolochainHeaderRelayService<SCI, RCI, PCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let relay_config = bridge_config.relay;
         let config_chain = bridge_config.chain;
         let config_index = bridge_config.index;
@@ -119,10 +124,13 @@ impl<
         let input = RelaychainHeaderInput {
             client_relaychain: config_chain.relay.client().await?,
             client_solochain: config_chain.solo.client().await?,
-            subquery_relaychain: config_index.relay.subquery()?,
-            subquery_parachain: config_index.para.subquery()?,
+            subquery_relaychain: config_index.relay.subquery()?.into(),
+            subquery_parachain: config_index.para.subquery()?.into(),
             index_origin_type: config_chain.solo.origin_type(),
             enable_mandatory: relay_config.enable_mandatory,
+            justification_sources: relay_config.justification_sources,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         let runner = RelaychainHeaderRunner::new(input);
         Ok(runner.start().await?)
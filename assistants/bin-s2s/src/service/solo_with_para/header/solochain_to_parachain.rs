@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::SolochainHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::SolochainHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
@@ -51,6 +52,7 @@ impl<
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-header-relay-service",
@@ -60,7 +62,7 @@ impl<
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start(bridge_config.clone()).await {
+            while let Err(e) = Self::start(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -105,7 +107,10 @@ impl<
         SI: SubqueryInfo,
     > SolochainToParachainHeaderRelayService<SCI, RCI, PCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let relay_config = bridge_config.relay;
         let config_chain = bridge_config.chain;
         let config_index = bridge_config.index;
@@ -119,9 +124,12 @@ impl<
         let input = SolochainHeaderInput {
             client_source: config_chain.solo.client().await?,
             client_target: config_chain.para.client().await?,
-            subquery_source: config_index.solo.subquery()?,
+            subquery_source: config_index.solo.subquery()?.into(),
             index_origin_type: config_chain.para.origin_type(),
             enable_mandatory: relay_config.enable_mandatory,
+            justification_sources: relay_config.justification_sources,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         let runner = SolochainHeaderRunner::new(input);
         Ok(runner.start().await?)
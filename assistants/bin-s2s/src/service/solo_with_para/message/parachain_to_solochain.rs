@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 
-use feemarket_s2s::relay::basic::BasicRelayStrategy;
+use feemarket_s2s::relay::strategy::FeemarketRelayStrategy;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::message::{BridgeParachainDeliveryRunner, BridgeSolochainReceivingRunner};
 use relay_s2s::types::{MessageDeliveryInput, MessageReceivingInput};
 
@@ -10,7 +11,7 @@ use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
@@ -53,6 +54,7 @@ impl<
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_delivery_name = format!(
             "{}-{}-message-delivery-service",
@@ -62,7 +64,8 @@ impl<
 
         let _greet_delivery = Self::try_task(&task_delivery_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_delivery(bridge_config.clone()).await {
+            while let Err(e) = Self::start_delivery(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message delivery relay {:?}",
@@ -92,6 +95,7 @@ impl<
         });
 
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_receiving_name = format!(
             "{}-{}-message-receiving-service",
@@ -101,7 +105,8 @@ impl<
 
         let _greet_receiving = Self::try_task(&task_receiving_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_receiving(bridge_config.clone()).await {
+            while let Err(e) = Self::start_receiving(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message receiving relay {:?}",
@@ -149,6 +154,7 @@ impl<
 {
     async fn message_input(
         bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
     ) -> BinS2SResult<
         MessageReceivingInput<
             <PCI as S2SSoloBridgeSoloChainInfo>::Client,
@@ -166,24 +172,36 @@ impl<
             relayer_account: config_chain.para.account()?,
             client_source: config_chain.para.client().await?,
             client_target: config_chain.solo.client().await?,
-            subquery_source: config_index.para.subquery()?,
-            subquery_target: config_index.solo.subquery()?,
+            subquery_source: config_index.para.subquery()?.into(),
+            subquery_target: config_index.solo.subquery()?.into(),
+            bundle_header: relay_config.bundle_header,
+            confirmation_policy: relay_config.confirmation_policy,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         Ok(input)
     }
 
-    async fn start_delivery(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    pub(crate) async fn delivery_runner(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<
+        BridgeParachainDeliveryRunner<
+            <PCI as S2SSoloBridgeSoloChainInfo>::Client,
+            <SCI as S2SParaBridgeSoloChainInfo>::Client,
+            FeemarketRelayStrategy<<PCI as S2SSoloBridgeSoloChainInfo>::Client>,
+        >,
+    > {
         let config_chain = bridge_config.chain.clone();
-        tracing::info!(
-            target: "bin-s2s",
-            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
-            config_chain.para.chain().name(),
-            config_chain.solo.chain().name(),
-        );
         let config_para = bridge_config.para_config.clone();
-        let input = Self::message_input(bridge_config).await?;
-        let relay_strategy =
-            BasicRelayStrategy::new(input.client_source.clone(), config_chain.para.account()?);
+        let config_relay = bridge_config.relay.clone();
+        let input = Self::message_input(bridge_config, keepstate).await?;
+        let relay_strategy = config_relay.relay_strategy(
+            input.client_source.clone(),
+            config_chain.para.account()?,
+            &config_chain.para.chain(),
+            &config_chain.solo.chain(),
+        )?;
         let input = MessageDeliveryInput {
             lanes: input.lanes,
             nonces_limit: 11,
@@ -194,19 +212,43 @@ impl<
             subquery_target: input.subquery_target,
             relay_block_origin: config_chain.solo.origin_type(),
             relay_strategy,
+            bundle_header: input.bundle_header,
+            dry_run: input.dry_run,
+            delivery_limits: config_relay.target_delivery_limits(&config_chain.solo.chain()),
+            keepstate: input.keepstate,
         };
-        let runner = BridgeParachainDeliveryRunner::new(input, config_para.para_id);
+        Ok(BridgeParachainDeliveryRunner::new(
+            input,
+            config_para.para_id,
+        ))
+    }
+
+    async fn start_delivery(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
+        let config_chain = bridge_config.chain.clone();
+        tracing::info!(
+            target: "bin-s2s",
+            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
+            config_chain.para.chain().name(),
+            config_chain.solo.chain().name(),
+        );
+        let runner = Self::delivery_runner(bridge_config, keepstate).await?;
         Ok(runner.start().await?)
     }
 
-    async fn start_receiving(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start_receiving(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         tracing::info!(
             target: "bin-s2s",
             "[message-receiving] [receiving-{}-to-{}] SERVICE RESTARTING...",
             bridge_config.chain.para.chain().name(),
             bridge_config.chain.solo.chain().name(),
         );
-        let input = Self::message_input(bridge_config).await?;
+        let input = Self::message_input(bridge_config, keepstate).await?;
         let runner = BridgeSolochainReceivingRunner::new(input);
         Ok(runner.start().await?)
     }
@@ -2,14 +2,16 @@ use std::marker::PhantomData;
 
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::subscribe::SubscribeJustification;
 use relay_s2s::types::JustificationInput;
 
 use support_lifeline::service::BridgeService;
 
 use crate::bridge::config::solo_with_para::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
+use crate::service::finality::subscribe_finality;
 use crate::traits::{
     S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo, S2SSoloBridgeSoloChainInfo,
     SubqueryInfo,
@@ -24,6 +26,9 @@ pub struct SubscribeService<
 > {
     _greet_solochain: Lifeline,
     _greet_relaychain: Lifeline,
+    _finality_solochain: Lifeline,
+    _finality_relaychain: Lifeline,
+    _finality_parachain: Lifeline,
     _relaychain_info: PhantomData<RCI>,
     _solochain_info: PhantomData<SCI>,
     _parachain_info: PhantomData<PCI>,
@@ -51,11 +56,13 @@ impl<
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.solo.chain().name(),);
 
         let _greet_solochain = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_solochain(bridge_config.clone()).await {
+            while let Err(e) = Self::start_solochain(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -72,10 +79,13 @@ impl<
             Ok(())
         });
         let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.relay.chain().name(),);
         let _greet_relaychain = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_relaychain(bridge_config.clone()).await {
+            while let Err(e) =
+                Self::start_relaychain(bridge_config.clone(), keepstate.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -91,9 +101,51 @@ impl<
             }
             Ok(())
         });
+        let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.solo.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_solochain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.relay.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_relaychain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SCI, RCI, PCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.para.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_parachain = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
         Ok(Self {
             _greet_solochain,
             _greet_relaychain,
+            _finality_solochain,
+            _finality_relaychain,
+            _finality_parachain,
             _relaychain_info: Default::default(),
             _solochain_info: Default::default(),
             _parachain_info: Default::default(),
@@ -109,21 +161,27 @@ impl<
         SI: SubqueryInfo,
     > SubscribeService<SCI, RCI, PCI, SI>
 {
-    async fn start_solochain(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start_solochain(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.solo.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
     }
 
-    async fn start_relaychain(bridge_config: BridgeConfig<SCI, RCI, PCI, SI>) -> BinS2SResult<()> {
+    async fn start_relaychain(
+        bridge_config: BridgeConfig<SCI, RCI, PCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.relay.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
@@ -3,13 +3,14 @@ use std::marker::PhantomData;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
 use relay_s2s::header::SolochainHeaderRunner;
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::types::SolochainHeaderInput;
 
 use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_solo::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeIndexer, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
 
@@ -38,6 +39,8 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let indexer: BridgeIndexer = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!(
             "{}-{}-header-relay-service",
@@ -47,7 +50,9 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 
         let _greet = Self::try_task(&task_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::run(bridge_config.clone()).await {
+            while let Err(e) =
+                Self::start(bridge_config.clone(), keepstate.clone(), indexer.clone()).await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[header-relay] [{}-to-{}] an error occurred for header relay {:?}",
@@ -87,10 +92,13 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
     TargetToSourceHeaderRelayService<SCI, TCI, SI>
 {
-    async fn start(bridge_config: BridgeConfig<SCI, TCI, SI>) -> BinS2SResult<()> {
+    async fn start(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+        indexer: BridgeIndexer,
+    ) -> BinS2SResult<()> {
         let relay_config = bridge_config.relay;
         let config_chain = bridge_config.chain;
-        let config_index = bridge_config.index;
         tracing::info!(
             target: "bin-s2s",
             "[header-{}-to-{}] SERVICE RESTARTING...",
@@ -101,9 +109,12 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
         let input = SolochainHeaderInput {
             client_source: config_chain.target.client().await?,
             client_target: config_chain.source.client().await?,
-            subquery_source: config_index.target.subquery()?,
+            subquery_source: indexer.target,
             index_origin_type: config_chain.source.origin_type(),
             enable_mandatory: relay_config.enable_mandatory,
+            justification_sources: relay_config.justification_sources,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         let runner = SolochainHeaderRunner::new(input);
         Ok(runner.start().await?)
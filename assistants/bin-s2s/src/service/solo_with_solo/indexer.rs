@@ -0,0 +1,134 @@
+use std::marker::PhantomData;
+
+use lifeline::dyn_bus::DynBus;
+use lifeline::{Lifeline, Service, Task};
+use relay_s2s::indexer::{ChainScanIndexer, Indexer};
+
+use support_lifeline::service::BridgeService;
+
+use crate::bridge::config::solo_with_solo::BridgeConfig;
+use crate::bridge::{BridgeBus, BridgeIndexer};
+use crate::error::{BinS2SError, BinS2SResult};
+use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
+
+/// Scan both chains for chain scan indexers, only spawned when `relay.indexer` is `chain-scan`
+#[derive(Debug)]
+pub struct ChainScanService<
+    SCI: S2SSoloBridgeSoloChainInfo,
+    TCI: S2SSoloBridgeSoloChainInfo,
+    SI: SubqueryInfo,
+> {
+    _greet_source: Lifeline,
+    _greet_target: Lifeline,
+    _source_chain_info: PhantomData<SCI>,
+    _target_chain_info: PhantomData<TCI>,
+    _subquery_info: PhantomData<SI>,
+}
+
+impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
+    BridgeService for ChainScanService<SCI, TCI, SI>
+{
+}
+
+impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo> Service
+    for ChainScanService<SCI, TCI, SI>
+{
+    type Bus = BridgeBus;
+    type Lifeline = color_eyre::Result<Self>;
+
+    fn spawn(bus: &Self::Bus) -> Self::Lifeline {
+        let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let indexer: BridgeIndexer = bus.storage().clone_resource()?;
+        let (indexer_source, indexer_target) = match indexer {
+            BridgeIndexer {
+                source: Indexer::ChainScan(source),
+                target: Indexer::ChainScan(target),
+            } => (source, target),
+            _ => {
+                return Err(BinS2SError::Custom(
+                    "The chain scan service requires chain scan indexers".to_string(),
+                )
+                .into())
+            }
+        };
+        let config_chain = bridge_config.chain.clone();
+        let task_name = format!("chain-scan-{}", config_chain.source.chain().name());
+
+        let _greet_source = Self::try_task(&task_name, async move {
+            while let Err(e) =
+                Self::start_source(bridge_config.clone(), indexer_source.clone()).await
+            {
+                tracing::error!(
+                    target: "bin-s2s",
+                    "[chain-scan] [{}] failed to scan chain {:?}",
+                    config_chain.source.chain().name(),
+                    e,
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                tracing::info!(
+                    target: "bin-s2s",
+                    "[chain-scan] [{}] try to restart chain scan service.",
+                    config_chain.source.chain().name(),
+                );
+            }
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let config_chain = bridge_config.chain.clone();
+        let task_name = format!("chain-scan-{}", config_chain.target.chain().name());
+        let _greet_target = Self::try_task(&task_name, async move {
+            while let Err(e) =
+                Self::start_target(bridge_config.clone(), indexer_target.clone()).await
+            {
+                tracing::error!(
+                    target: "bin-s2s",
+                    "[chain-scan] [{}] failed to scan chain {:?}",
+                    config_chain.target.chain().name(),
+                    e,
+                );
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                tracing::info!(
+                    target: "bin-s2s",
+                    "[chain-scan] [{}] try to restart chain scan service.",
+                    config_chain.target.chain().name(),
+                );
+            }
+            Ok(())
+        });
+        Ok(Self {
+            _greet_source,
+            _greet_target,
+            _source_chain_info: Default::default(),
+            _target_chain_info: Default::default(),
+            _subquery_info: Default::default(),
+        })
+    }
+}
+
+impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
+    ChainScanService<SCI, TCI, SI>
+{
+    async fn start_source(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        indexer: ChainScanIndexer,
+    ) -> BinS2SResult<()> {
+        let config_chain = &bridge_config.chain;
+        let client = config_chain.source.client().await?;
+        let client_bridged = config_chain.target.client().await?;
+        let lanes = bridge_config.relay.raw_lanes();
+        indexer.start(client, client_bridged, lanes).await?;
+        Ok(())
+    }
+
+    async fn start_target(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        indexer: ChainScanIndexer,
+    ) -> BinS2SResult<()> {
+        let config_chain = &bridge_config.chain;
+        let client = config_chain.target.client().await?;
+        let client_bridged = config_chain.source.client().await?;
+        let lanes = bridge_config.relay.raw_lanes();
+        indexer.start(client, client_bridged, lanes).await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 use std::marker::PhantomData;
 
-use feemarket_s2s::relay::basic::BasicRelayStrategy;
+use feemarket_s2s::relay::strategy::FeemarketRelayStrategy;
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::message::{BridgeSolochainDeliveryRunner, BridgeSolochainReceivingRunner};
 use relay_s2s::types::{MessageDeliveryInput, MessageReceivingInput};
 
@@ -10,7 +11,7 @@ use support_lifeline::service::BridgeService;
 use support_toolkit::timecount::TimeCount;
 
 use crate::bridge::config::solo_with_solo::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeIndexer, BridgeKeepstate};
 use crate::error::BinS2SResult;
 use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
 
@@ -40,6 +41,8 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let indexer: BridgeIndexer = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_delivery_name = format!(
             "{}-{}-message-delivery-service",
@@ -49,7 +52,10 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 
         let _greet_delivery = Self::try_task(&task_delivery_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_delivery(bridge_config.clone()).await {
+            while let Err(e) =
+                Self::start_delivery(bridge_config.clone(), keepstate.clone(), indexer.clone())
+                    .await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message delivery relay {:?}",
@@ -79,6 +85,8 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
         });
 
         let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let indexer: BridgeIndexer = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_receiving_name = format!(
             "{}-{}-message-receiving-service",
@@ -87,7 +95,10 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
         );
         let _greet_receiving = Self::try_task(&task_receiving_name, async move {
             let mut timecount = TimeCount::new();
-            while let Err(e) = Self::start_receiving(bridge_config.clone()).await {
+            while let Err(e) =
+                Self::start_receiving(bridge_config.clone(), keepstate.clone(), indexer.clone())
+                    .await
+            {
                 tracing::error!(
                     target: "bin-s2s",
                     "[message-relay] [{}-to-{}] an error occurred for message receiving relay {:?}",
@@ -130,6 +141,8 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 {
     async fn message_input(
         bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+        indexer: BridgeIndexer,
     ) -> BinS2SResult<
         MessageReceivingInput<
             <TCI as S2SSoloBridgeSoloChainInfo>::Client,
@@ -138,7 +151,6 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
     > {
         let relay_config = bridge_config.relay;
         let config_chain = bridge_config.chain;
-        let config_index = bridge_config.index;
 
         let lanes = relay_config.raw_lanes();
 
@@ -147,23 +159,36 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
             relayer_account: config_chain.target.account()?,
             client_source: config_chain.target.client().await?,
             client_target: config_chain.source.client().await?,
-            subquery_source: config_index.target.subquery()?,
-            subquery_target: config_index.source.subquery()?,
+            subquery_source: indexer.target,
+            subquery_target: indexer.source,
+            bundle_header: relay_config.bundle_header,
+            confirmation_policy: relay_config.confirmation_policy,
+            dry_run: relay_config.dry_run,
+            keepstate,
         };
         Ok(input)
     }
 
-    async fn start_delivery(bridge_config: BridgeConfig<SCI, TCI, SI>) -> BinS2SResult<()> {
-        tracing::info!(
-            target: "bin-s2s",
-            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
-            bridge_config.chain.target.chain().name(),
-            bridge_config.chain.source.chain().name(),
-        );
+    pub(crate) async fn delivery_runner(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+        indexer: BridgeIndexer,
+    ) -> BinS2SResult<
+        BridgeSolochainDeliveryRunner<
+            <TCI as S2SSoloBridgeSoloChainInfo>::Client,
+            <SCI as S2SSoloBridgeSoloChainInfo>::Client,
+            FeemarketRelayStrategy<<TCI as S2SSoloBridgeSoloChainInfo>::Client>,
+        >,
+    > {
         let config_chain = bridge_config.chain.clone();
-        let input = Self::message_input(bridge_config).await?;
-        let relay_strategy =
-            BasicRelayStrategy::new(input.client_source.clone(), config_chain.target.account()?);
+        let config_relay = bridge_config.relay.clone();
+        let input = Self::message_input(bridge_config, keepstate, indexer).await?;
+        let relay_strategy = config_relay.relay_strategy(
+            input.client_source.clone(),
+            config_chain.target.account()?,
+            &config_chain.target.chain(),
+            &config_chain.source.chain(),
+        )?;
         let input = MessageDeliveryInput {
             lanes: input.lanes,
             nonces_limit: 11,
@@ -174,19 +199,41 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
             subquery_target: input.subquery_target,
             relay_block_origin: config_chain.source.origin_type(),
             relay_strategy,
+            bundle_header: input.bundle_header,
+            dry_run: input.dry_run,
+            delivery_limits: config_relay.target_delivery_limits(&config_chain.source.chain()),
+            keepstate: input.keepstate,
         };
-        let runner = BridgeSolochainDeliveryRunner::new(input);
+        Ok(BridgeSolochainDeliveryRunner::new(input))
+    }
+
+    async fn start_delivery(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+        indexer: BridgeIndexer,
+    ) -> BinS2SResult<()> {
+        tracing::info!(
+            target: "bin-s2s",
+            "[message-delivery] [delivery-{}-to-{}] SERVICE RESTARTING...",
+            bridge_config.chain.target.chain().name(),
+            bridge_config.chain.source.chain().name(),
+        );
+        let runner = Self::delivery_runner(bridge_config, keepstate, indexer).await?;
         Ok(runner.start().await?)
     }
 
-    async fn start_receiving(bridge_config: BridgeConfig<SCI, TCI, SI>) -> BinS2SResult<()> {
+    async fn start_receiving(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+        indexer: BridgeIndexer,
+    ) -> BinS2SResult<()> {
         tracing::info!(
             target: "bin-s2s",
             "[message-receiving] [receiving-{}-to-{}] SERVICE RESTARTING...",
             bridge_config.chain.target.chain().name(),
             bridge_config.chain.source.chain().name(),
         );
-        let input = Self::message_input(bridge_config).await?;
+        let input = Self::message_input(bridge_config, keepstate, indexer).await?;
         let runner = BridgeSolochainReceivingRunner::new(input);
         Ok(runner.start().await?)
     }
@@ -1,7 +1,9 @@
 pub use self::header::*;
+pub use self::indexer::*;
 pub use self::message::*;
 pub use self::subscribe::*;
 
 mod header;
+mod indexer;
 mod message;
 mod subscribe;
@@ -2,14 +2,16 @@ use std::marker::PhantomData;
 
 use lifeline::dyn_bus::DynBus;
 use lifeline::{Lifeline, Service, Task};
+use relay_s2s::keepstate::Keepstate;
 use relay_s2s::subscribe::SubscribeJustification;
 use relay_s2s::types::JustificationInput;
 
 use support_lifeline::service::BridgeService;
 
 use crate::bridge::config::solo_with_solo::BridgeConfig;
-use crate::bridge::BridgeBus;
+use crate::bridge::{BridgeBus, BridgeKeepstate};
 use crate::error::BinS2SResult;
+use crate::service::finality::subscribe_finality;
 use crate::traits::{S2SSoloBridgeSoloChainInfo, SubqueryInfo};
 
 #[derive(Debug)]
@@ -20,6 +22,8 @@ pub struct SubscribeService<
 > {
     _greet_source: Lifeline,
     _greet_target: Lifeline,
+    _finality_source: Lifeline,
+    _finality_target: Lifeline,
     _source_chain_info: PhantomData<SCI>,
     _target_chain_info: PhantomData<TCI>,
     _subquery_info: PhantomData<SI>,
@@ -38,11 +42,12 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 
     fn spawn(bus: &Self::Bus) -> Self::Lifeline {
         let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.source.chain().name(),);
 
         let _greet_source = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_source(bridge_config.clone()).await {
+            while let Err(e) = Self::start_source(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -59,10 +64,11 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
             Ok(())
         });
         let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
         let config_chain = bridge_config.chain.clone();
         let task_name = format!("subscribe-{}", config_chain.target.chain().name(),);
         let _greet_target = Self::try_task(&task_name, async move {
-            while let Err(e) = Self::start_target(bridge_config.clone()).await {
+            while let Err(e) = Self::start_target(bridge_config.clone(), keepstate.clone()).await {
                 tracing::error!(
                     target: "bin-s2s",
                     "[subscribe] [{}] failed to start subscribe {:?}",
@@ -78,9 +84,37 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
             }
             Ok(())
         });
+        let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.source.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_source = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
+        let bridge_config: BridgeConfig<SCI, TCI, SI> = bus.storage().clone_resource()?;
+        let BridgeKeepstate(keepstate) = bus.storage().clone_resource()?;
+        let chain_info = bridge_config.chain.target.clone();
+        let task_name = format!("subscribe-finality-{}", chain_info.chain().name());
+        let _finality_target = Self::try_task(&task_name, async move {
+            let chain = chain_info.chain();
+            subscribe_finality(chain, keepstate, move || {
+                let chain_info = chain_info.clone();
+                async move { chain_info.client().await }
+            })
+            .await;
+            Ok(())
+        });
         Ok(Self {
             _greet_source,
             _greet_target,
+            _finality_source,
+            _finality_target,
             _source_chain_info: Default::default(),
             _target_chain_info: Default::default(),
             _subquery_info: Default::default(),
@@ -91,21 +125,27 @@ impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: Subqu
 impl<SCI: S2SSoloBridgeSoloChainInfo, TCI: S2SSoloBridgeSoloChainInfo, SI: SubqueryInfo>
     SubscribeService<SCI, TCI, SI>
 {
-    async fn start_source(bridge_config: BridgeConfig<SCI, TCI, SI>) -> BinS2SResult<()> {
+    async fn start_source(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.source.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
     }
 
-    async fn start_target(bridge_config: BridgeConfig<SCI, TCI, SI>) -> BinS2SResult<()> {
+    async fn start_target(
+        bridge_config: BridgeConfig<SCI, TCI, SI>,
+        keepstate: Keepstate,
+    ) -> BinS2SResult<()> {
         let config_chain = &bridge_config.chain;
         let client = config_chain.target.client().await?;
 
-        let input = JustificationInput { client };
+        let input = JustificationInput { client, keepstate };
         let subscribe = SubscribeJustification::new(input);
         subscribe.start().await?;
         Ok(())
@@ -5,7 +5,7 @@ use bridge_s2s_traits::client::S2SClientRelay;
 use bridge_s2s_traits::client::{S2SParaBridgeClientRelaychain, S2SParaBridgeClientSolochain};
 use bridge_s2s_traits::types::bp_runtime;
 use client_common_traits::ClientCommon;
-use feemarket_s2s_traits::api::FeemarketApiRelay;
+use feemarket_s2s_traits::api::{FeemarketApiQuote, FeemarketApiRelay};
 use subquery::types::OriginType;
 use subquery::Subquery;
 
@@ -24,7 +24,7 @@ pub trait S2SBasicChainInfo: 'static + Sync + Send + Sized + Clone + Debug {
 /// solo bridge solo chain info
 #[async_trait::async_trait]
 pub trait S2SSoloBridgeSoloChainInfo: S2SBasicChainInfo {
-    type Client: S2SClientRelay + FeemarketApiRelay;
+    type Client: S2SClientRelay + FeemarketApiRelay + FeemarketApiQuote;
 
     fn origin_type(&self) -> OriginType;
 
@@ -39,7 +39,7 @@ pub trait S2SSoloBridgeSoloChainInfo: S2SBasicChainInfo {
 #[async_trait::async_trait]
 #[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
 pub trait S2SParaBridgeSoloChainInfo: S2SBasicChainInfo {
-    type Client: S2SParaBridgeClientSolochain + FeemarketApiRelay;
+    type Client: S2SParaBridgeClientSolochain + FeemarketApiRelay + FeemarketApiQuote;
 
     fn origin_type(&self) -> OriginType;
 
@@ -59,6 +59,18 @@ pub trait S2SParaBridgeRelayChainInfo: S2SBasicChainInfo {
     async fn client(&self) -> BinS2SResult<Self::Client>;
 }
 
+/// chain info of which fee market is managed
+#[async_trait::async_trait]
+pub trait S2SFeemarketChainInfo: S2SBasicChainInfo {
+    type Client: FeemarketApiQuote;
+
+    fn account(
+        &self,
+    ) -> BinS2SResult<<<Self::Client as ClientCommon>::Chain as bp_runtime::Chain>::AccountId>;
+
+    async fn client(&self) -> BinS2SResult<Self::Client>;
+}
+
 pub trait SubqueryInfo: 'static + Sync + Send + Sized + Clone + Debug {
     fn subquery(&self) -> BinS2SResult<Subquery>;
 }
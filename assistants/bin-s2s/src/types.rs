@@ -1,14 +1,22 @@
 use std::fmt::{Debug, Display, Formatter};
 use std::str::FromStr;
 
+use relay_s2s::types::DeliveryStatus;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use subquery::{Subquery, SubqueryComponent, SubqueryConfig};
 
 use support_common::error::BridgerError;
-use support_types::mark::BridgeName;
+use support_types::mark::{BridgeName, ChainName};
+
+use bridge_s2s_traits::types::bp_runtime;
+use client_common_traits::ClientCommon;
 
 use crate::error::BinS2SResult;
-use crate::traits::SubqueryInfo;
+#[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
+use crate::traits::S2SParaBridgeSoloChainInfo;
+use crate::traits::{
+    S2SBasicChainInfo, S2SFeemarketChainInfo, S2SSoloBridgeSoloChainInfo, SubqueryInfo,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct HexLaneId(pub [u8; 4]);
@@ -52,6 +60,15 @@ impl Serialize for HexLaneId {
     }
 }
 
+/// Message delivery status of a lane in one direction
+#[derive(Clone, Debug, Serialize)]
+pub struct BridgeLaneStatus {
+    pub source: ChainName,
+    pub target: ChainName,
+    #[serde(flatten)]
+    pub status: DeliveryStatus,
+}
+
 #[derive(Clone, Debug)]
 pub struct BasicSubqueryInfo {
     bridge_name: BridgeName,
@@ -75,3 +92,54 @@ impl SubqueryInfo for BasicSubqueryInfo {
         ))
     }
 }
+
+/// Fee market of solo bridge solo chain
+#[derive(Clone, Debug)]
+pub struct SoloChainFeemarketInfo<CI: S2SSoloBridgeSoloChainInfo>(pub CI);
+
+impl<CI: S2SSoloBridgeSoloChainInfo> S2SBasicChainInfo for SoloChainFeemarketInfo<CI> {
+    const CHAIN: ChainName = CI::CHAIN;
+}
+
+#[async_trait::async_trait]
+impl<CI: S2SSoloBridgeSoloChainInfo> S2SFeemarketChainInfo for SoloChainFeemarketInfo<CI> {
+    type Client = CI::Client;
+
+    fn account(
+        &self,
+    ) -> BinS2SResult<<<Self::Client as ClientCommon>::Chain as bp_runtime::Chain>::AccountId> {
+        self.0.account()
+    }
+
+    async fn client(&self) -> BinS2SResult<Self::Client> {
+        self.0.client().await
+    }
+}
+
+/// Fee market of para bridge solo chain
+#[derive(Clone, Debug)]
+#[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
+pub struct ParaBridgeSoloChainFeemarketInfo<CI: S2SParaBridgeSoloChainInfo>(pub CI);
+
+#[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
+impl<CI: S2SParaBridgeSoloChainInfo> S2SBasicChainInfo for ParaBridgeSoloChainFeemarketInfo<CI> {
+    const CHAIN: ChainName = CI::CHAIN;
+}
+
+#[async_trait::async_trait]
+#[cfg(any(feature = "solo-with-para", feature = "para-with-para"))]
+impl<CI: S2SParaBridgeSoloChainInfo> S2SFeemarketChainInfo
+    for ParaBridgeSoloChainFeemarketInfo<CI>
+{
+    type Client = CI::Client;
+
+    fn account(
+        &self,
+    ) -> BinS2SResult<<<Self::Client as ClientCommon>::Chain as bp_runtime::Chain>::AccountId> {
+        self.0.account()
+    }
+
+    async fn client(&self) -> BinS2SResult<Self::Client> {
+        self.0.client().await
+    }
+}
@@ -37,10 +37,18 @@ impl BeaconApiClient {
         self.api_client.get(url)
     }
 
+    async fn send(&self, url: &str) -> BeaconApiResult<Response> {
+        self.get(url).send().await.map_err(|e| {
+            support_metrics::metrics::beacon_api_error("request");
+            e.into()
+        })
+    }
+
     async fn parse_reponse<R: DeserializeOwned>(response: Response) -> BeaconApiResult<R> {
         if response.status().is_success() {
             Ok(response.json().await?)
         } else {
+            support_metrics::metrics::beacon_api_error("response");
             let url: String = response.url().as_str().into();
             let status_code = response.status();
             let res: ErrorResponse = response.json().await.map_err(|_| {
@@ -63,7 +71,7 @@ impl BeaconApiClient {
             self.api_base_url,
             id.to_string()
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<GetHeaderResponse> = Self::parse_reponse(response).await?;
         Ok(res.data)
     }
@@ -139,7 +147,7 @@ impl BeaconApiClient {
             self.api_base_url,
             id.to_string()
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res = Self::parse_reponse::<ResponseWrapper<BeaconBlockRoot>>(response).await?;
         Ok(res.data.root)
     }
@@ -149,7 +157,7 @@ impl BeaconApiClient {
             "{}/eth/v1/beacon/light_client/bootstrap/{}",
             self.api_base_url, header_root,
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<Snapshot> = Self::parse_reponse(response).await?;
         Ok(res.data)
     }
@@ -176,7 +184,7 @@ impl BeaconApiClient {
             self.api_base_url,
             id.to_string(),
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<BeaconBlockWrapper> = Self::parse_reponse(response).await?;
         Ok(res.data.message)
     }
@@ -188,7 +196,7 @@ impl BeaconApiClient {
             self.api_base_url,
             id.to_string(),
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<Finality> = Self::parse_reponse(response).await?;
         Ok(res.data)
     }
@@ -228,10 +236,15 @@ impl BeaconApiClient {
             .get(url.clone())
             .header("content-type", "application/octet-stream")
             .send()
-            .await?;
+            .await
+            .map_err(|e| {
+                support_metrics::metrics::beacon_api_error("request");
+                BeaconApiError::from(e)
+            })?;
         let content_type = response.headers()[CONTENT_TYPE].as_bytes().to_vec();
         let content_type = String::from_utf8(content_type)?;
         if !response.status().is_success() || content_type.contains("application/json") {
+            support_metrics::metrics::beacon_api_error("response");
             tracing::error!("Failed to get state proof. Api: {:?}", url);
             return Err(BeaconApiError::Custom("Failed to get state proof".into()));
         }
@@ -246,7 +259,7 @@ impl BeaconApiClient {
             self.api_base_url,
             id.to_string(),
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<ForkVersion> = Self::parse_reponse(response).await?;
         Ok(res.data)
     }
@@ -256,7 +269,7 @@ impl BeaconApiClient {
             "{}/eth/v1/beacon/light_client/finality_update",
             self.api_base_url,
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let res: ResponseWrapper<FinalityUpdate> = Self::parse_reponse(response).await?;
         Ok(res.data)
     }
@@ -272,7 +285,7 @@ impl BeaconApiClient {
             start_period.to_string(),
             count.to_string(),
         );
-        let response = self.get(&url).send().await?;
+        let response = self.send(&url).await?;
         let result = match self.api_supplier {
             ApiSupplier::Nimbus => {
                 Self::parse_reponse::<Vec<ResponseWrapper<SyncCommitteePeriodUpdate>>>(response)
@@ -5,9 +5,10 @@ use client_crab::error::ClientResult;
 
 pub async fn client() -> ClientResult<CrabClient> {
     let config = ClientConfig {
-        endpoint: "wss://crab-rpc.darwinia.network".to_string(),
+        endpoint: "wss://crab-rpc.darwinia.network".into(),
         relayer_private_key: "//Alice".to_string(),
         relayer_real_account: None,
+        transaction: Default::default(),
     };
     CrabClientComponent::component(config).await
 }
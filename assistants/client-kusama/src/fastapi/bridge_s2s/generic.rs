@@ -92,6 +92,20 @@ impl S2SClientGeneric for KusamaClient {
             .await?)
     }
 
+    async fn subscribe_finalized_heads(
+        &self,
+    ) -> S2SClientResult<Subscription<<Self::Chain as Chain>::Header>> {
+        Ok(self
+            .subxt()
+            .rpc()
+            .subscribe(
+                "chain_subscribeFinalizedHeads",
+                subxt::rpc::rpc_params![],
+                "chain_unsubscribeFinalizedHeads",
+            )
+            .await?)
+    }
+
     async fn header(
         &self,
         hash: Option<<Self::Chain as Chain>::Hash>,
@@ -126,6 +140,54 @@ impl S2SClientGeneric for KusamaClient {
         }
     }
 
+    async fn finalized_head(&self) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        Ok(self.subxt().rpc().finalized_head().await?)
+    }
+
+    async fn block_hash(
+        &self,
+        number: <Self::Chain as Chain>::BlockNumber,
+    ) -> S2SClientResult<Option<<Self::Chain as Chain>::Hash>> {
+        Ok(self.subxt().rpc().block_hash(Some(number.into())).await?)
+    }
+
+    async fn find_justification(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<Option<sp_core::Bytes>> {
+        let block = match self.subxt().rpc().block(Some(hash)).await? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        if let Some(justification) = block
+            .justifications
+            .and_then(|v| v.into_justification(GRANDPA_ENGINE_ID))
+        {
+            return Ok(Some(sp_core::Bytes(justification)));
+        }
+        // the node only keeps justifications of some blocks, the others should be proved
+        let proof: Option<sp_core::Bytes> = self
+            .subxt()
+            .rpc()
+            .request(
+                "grandpa_proveFinality",
+                subxt::rpc::rpc_params![block.block.header.number],
+            )
+            .await?;
+        match proof {
+            Some(proof) => {
+                // the encoded finality proof starts with (block, justification)
+                let (proved_hash, justification): (<Self::Chain as Chain>::Hash, Vec<u8>) =
+                    codec::Decode::decode(&mut proof.as_ref()).map_err(|err| {
+                        S2SClientError::Custom(format!("Wrong finality proof: {:?}", err))
+                    })?;
+                // the justification may finalize a descendant of this block
+                Ok((proved_hash == hash).then_some(sp_core::Bytes(justification)))
+            }
+            None => Ok(None),
+        }
+    }
+
     async fn read_proof(
         &self,
         storage_keys: Vec<Vec<u8>>,
@@ -0,0 +1,243 @@
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
+use bridge_s2s_traits::types::bp_header_chain::justification::GrandpaJustification;
+use bridge_s2s_traits::types::bp_messages::{LaneId, MessageData, MessageNonce};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::HeadData;
+use codec::Encode;
+use feemarket_s2s_traits::types::{Order, Relayer};
+use sp_core::{ed25519, Pair};
+use sp_finality_grandpa::{
+    localized_payload, AuthorityList, Commit, ConsensusLog, Message, Precommit, ScheduledChange,
+    SignedPrecommit, GRANDPA_ENGINE_ID,
+};
+use sp_runtime::traits::Header;
+use sp_runtime::{Digest, DigestItem};
+use tokio::sync::broadcast;
+
+use crate::state::{MockChainState, MockStorage};
+use crate::types::{MockCall, MockChainInfo, MockExtrinsic};
+
+/// Set id of the mock grandpa authority set, the set is never changed
+pub const MOCK_AUTHORITY_SET_ID: u64 = 0;
+
+/// The only grandpa authority of mock chains, all justifications are signed by it
+pub fn mock_authority() -> ed25519::Pair {
+    ed25519::Pair::from_seed(&[1u8; 32])
+}
+
+/// Authority list of mock chains
+pub fn mock_authority_list() -> AuthorityList {
+    vec![(mock_authority().public().into(), 1)]
+}
+
+/// In-memory client, all clones share the same chain state.
+pub struct MockClient<M: MockChainInfo> {
+    state: Arc<Mutex<MockChainState<M::Chain>>>,
+    justifications: Arc<Mutex<broadcast::Sender<sp_core::Bytes>>>,
+    finalized_heads: broadcast::Sender<<M::Chain as Chain>::Header>,
+    _marker: PhantomData<M>,
+}
+
+impl<M: MockChainInfo> Clone for MockClient<M> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            justifications: self.justifications.clone(),
+            finalized_heads: self.finalized_heads.clone(),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<M: MockChainInfo> Default for MockClient<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M: MockChainInfo> MockClient<M> {
+    pub fn new() -> Self {
+        let (justifications, _) = broadcast::channel(64);
+        let (finalized_heads, _) = broadcast::channel(64);
+        Self {
+            state: Arc::new(Mutex::new(MockChainState::new())),
+            justifications: Arc::new(Mutex::new(justifications)),
+            finalized_heads,
+            _marker: Default::default(),
+        }
+    }
+
+    /// Lock chain state
+    pub fn state(&self) -> MutexGuard<MockChainState<M::Chain>> {
+        self.state.lock().unwrap()
+    }
+
+    /// Storage at block, it's cloned from state
+    pub(crate) fn storage_at(
+        &self,
+        hash: Option<<M::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<MockStorage<M::Chain>> {
+        self.state()
+            .storage_at(hash.as_ref())
+            .cloned()
+            .ok_or_else(|| S2SClientError::Custom(format!("[{}] unknown block", M::CHAIN)))
+    }
+
+    pub(crate) fn subscribe_justifications(&self) -> broadcast::Receiver<sp_core::Bytes> {
+        self.justifications.lock().unwrap().subscribe()
+    }
+
+    pub(crate) fn subscribe_heads(&self) -> broadcast::Receiver<<M::Chain as Chain>::Header> {
+        self.finalized_heads.subscribe()
+    }
+
+    /// Send the finalized header and its justification to subscribers
+    fn send_finality(&self, header: &<M::Chain as Chain>::Header) {
+        let justification = self.justification(header);
+        let _ = self.justifications.lock().unwrap().send(justification);
+        let _ = self.finalized_heads.send(header.clone());
+    }
+
+    /// Include a call by a new block, the block is finalized and its header and justification
+    /// are sent to subscribers like a produced block.
+    pub(crate) fn include_extrinsic(
+        &self,
+        mut state: MutexGuard<MockChainState<M::Chain>>,
+        call: MockCall,
+    ) -> <M::Chain as Chain>::Hash {
+        let hash = state.include_extrinsic(call);
+        let header = state.best_header().clone();
+        drop(state);
+        self.send_finality(&header);
+        hash
+    }
+
+    /// Terminate all justification subscriptions, the later subscriptions still work
+    pub fn close_justification_subscriptions(&self) {
+        let (justifications, _) = broadcast::channel(64);
+        *self.justifications.lock().unwrap() = justifications;
+    }
+}
+
+impl<M: MockChainInfo> MockClient<M> {
+    /// Produce and finalize a new block, the header and justification of this block are sent to
+    /// subscribers.
+    pub fn produce_block(&self) -> <M::Chain as Chain>::Header {
+        let header = self.state().produce_block().clone();
+        self.send_finality(&header);
+        header
+    }
+
+    /// Produce and finalize a new block which schedules GRANDPA authorities change, it's a
+    /// mandatory header of bridge.
+    pub fn schedule_authorities_change(&self) -> <M::Chain as Chain>::Header {
+        let log = ConsensusLog::<u32>::ScheduledChange(ScheduledChange {
+            next_authorities: vec![],
+            delay: 0,
+        });
+        let digest = Digest {
+            logs: vec![DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())],
+        };
+        let header = self.state().produce_block_with_digest(digest).clone();
+        self.send_finality(&header);
+        header
+    }
+
+    /// Grandpa justification of the header, it's signed by the mock authority which has
+    /// all weight of the authority set.
+    pub fn justification(&self, header: &<M::Chain as Chain>::Header) -> sp_core::Bytes {
+        let round = 1u64;
+        let precommit = Precommit::<<M::Chain as Chain>::Header> {
+            target_hash: header.hash(),
+            target_number: *header.number(),
+        };
+        let message = Message::<<M::Chain as Chain>::Header>::Precommit(precommit.clone());
+        let payload = localized_payload(round, MOCK_AUTHORITY_SET_ID, &message);
+        let authority = mock_authority();
+        let justification = GrandpaJustification::<<M::Chain as Chain>::Header> {
+            round,
+            commit: Commit::<<M::Chain as Chain>::Header> {
+                target_hash: header.hash(),
+                target_number: *header.number(),
+                precommits: vec![SignedPrecommit::<<M::Chain as Chain>::Header> {
+                    precommit,
+                    signature: authority.sign(&payload).into(),
+                    id: authority.public().into(),
+                }],
+            },
+            votes_ancestries: vec![],
+        };
+        sp_core::Bytes(justification.encode())
+    }
+
+    /// Send a message to lane, returns the message nonce and the header of block included it.
+    pub fn send_message(
+        &self,
+        lane: LaneId,
+        payload: Vec<u8>,
+        fee: u128,
+    ) -> (MessageNonce, <M::Chain as Chain>::Header) {
+        let mut state = self.state();
+        let outbound_lane_data = state.storage.outbound_lanes.entry(lane).or_default();
+        outbound_lane_data.latest_generated_nonce += 1;
+        let nonce = outbound_lane_data.latest_generated_nonce;
+        state
+            .storage
+            .outbound_messages
+            .insert((lane, nonce), MessageData { payload, fee });
+        let header = state.produce_block().clone();
+        drop(state);
+        self.send_finality(&header);
+        (nonce, header)
+    }
+
+    /// Set fee market order of message
+    pub fn set_order(
+        &self,
+        lane: LaneId,
+        nonce: MessageNonce,
+        order: Order<
+            <M::Chain as Chain>::AccountId,
+            <M::Chain as Chain>::BlockNumber,
+            <M::Chain as Chain>::Balance,
+        >,
+    ) {
+        self.state().orders.insert((lane, nonce), order);
+    }
+
+    /// Set the account which signs extrinsics
+    pub fn set_account(&self, account: <M::Chain as Chain>::AccountId) {
+        self.state().account = Some(account);
+    }
+
+    /// Enroll fee market relayer, the relayer is replaced if already enrolled
+    pub fn enroll_relayer(
+        &self,
+        relayer: Relayer<<M::Chain as Chain>::AccountId, <M::Chain as Chain>::Balance>,
+    ) {
+        let mut state = self.state();
+        state.relayers.retain(|item| item.id != relayer.id);
+        state.relayers.push(relayer);
+    }
+
+    /// Set para head stored in relay chain, a new relay block will be produced.
+    pub fn set_para_head(
+        &self,
+        para_id: u32,
+        para_header: &impl Encode,
+    ) -> <M::Chain as Chain>::Header {
+        self.state()
+            .storage
+            .para_heads
+            .insert(para_id, HeadData(para_header.encode()));
+        self.produce_block()
+    }
+
+    /// Extrinsics submitted to this chain
+    pub fn extrinsics(&self) -> Vec<MockExtrinsic> {
+        self.state().extrinsics.clone()
+    }
+}
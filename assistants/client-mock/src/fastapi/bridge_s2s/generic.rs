@@ -0,0 +1,130 @@
+use bridge_s2s_traits::client::{S2SClientBase, S2SClientGeneric};
+use bridge_s2s_traits::error::S2SClientResult;
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use codec::Encode;
+use futures::StreamExt;
+use sp_runtime::generic::{Block, SignedBlock};
+use sp_runtime::traits::Header;
+use subxt::rpc::{RpcSubscription, Subscription};
+use support_toolkit::convert::SmartCodecMapper;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::client::{mock_authority_list, MockClient, MOCK_AUTHORITY_SET_ID};
+use crate::types::{MockChainInfo, MockInitializationData};
+
+impl<M: MockChainInfo> S2SClientBase for MockClient<M> {
+    type Extrinsic = sp_runtime::OpaqueExtrinsic;
+}
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> S2SClientGeneric for MockClient<M> {
+    type InitializationData = MockInitializationData;
+
+    async fn subscribe_grandpa_justifications(
+        &self,
+    ) -> S2SClientResult<Subscription<sp_core::Bytes>> {
+        let receiver = self.subscribe_justifications();
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(justification) => return Some((justification, receiver)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .map(|justification| {
+            Ok(serde_json::value::to_raw_value(&justification)
+                .expect("Bytes always can be serialized"))
+        });
+        let subscription: RpcSubscription = Box::pin(stream);
+        Ok(Subscription::new(subscription))
+    }
+
+    async fn subscribe_finalized_heads(
+        &self,
+    ) -> S2SClientResult<Subscription<<Self::Chain as Chain>::Header>> {
+        let receiver = self.subscribe_heads();
+        let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(header) => return Some((header, receiver)),
+                    Err(RecvError::Lagged(_)) => continue,
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
+        .map(|header| {
+            Ok(serde_json::value::to_raw_value(&header).expect("Header always can be serialized"))
+        });
+        let subscription: RpcSubscription = Box::pin(stream);
+        Ok(Subscription::new(subscription))
+    }
+
+    async fn header(
+        &self,
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<<Self::Chain as Chain>::Header>> {
+        let state = self.state();
+        match hash {
+            Some(v) => Ok(state.header_by_hash(&v).cloned()),
+            None => Ok(Some(state.best_header().clone())),
+        }
+    }
+
+    async fn block(
+        &self,
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<SignedBlock<Block<<Self::Chain as Chain>::Header, Self::Extrinsic>>>>
+    {
+        let header = self.header(hash).await?;
+        Ok(header.map(|header| SignedBlock {
+            block: Block {
+                header,
+                extrinsics: vec![],
+            },
+            justifications: None,
+        }))
+    }
+
+    async fn finalized_head(&self) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        Ok(self.state().finalized_header().hash())
+    }
+
+    async fn block_hash(
+        &self,
+        number: <Self::Chain as Chain>::BlockNumber,
+    ) -> S2SClientResult<Option<<Self::Chain as Chain>::Hash>> {
+        let number: u32 = SmartCodecMapper::map_to(&number)?;
+        Ok(self
+            .state()
+            .headers
+            .get(number as usize)
+            .map(|header| header.hash()))
+    }
+
+    async fn find_justification(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<Option<sp_core::Bytes>> {
+        let header = self.state().header_by_hash(&hash).cloned();
+        Ok(header.map(|header| self.justification(&header)))
+    }
+
+    async fn read_proof(
+        &self,
+        storage_keys: Vec<Vec<u8>>,
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Vec<Vec<u8>>> {
+        Ok(self.storage_at(hash)?.read_proof(storage_keys))
+    }
+
+    async fn prepare_initialization_data(&self) -> S2SClientResult<Self::InitializationData> {
+        let header = self.state().finalized_header().clone();
+        Ok(MockInitializationData {
+            header: header.encode(),
+            authority_list: mock_authority_list(),
+            set_id: MOCK_AUTHORITY_SET_ID,
+        })
+    }
+}
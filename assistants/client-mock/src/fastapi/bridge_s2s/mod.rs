@@ -0,0 +1,3 @@
+mod generic;
+mod parachain;
+mod relay;
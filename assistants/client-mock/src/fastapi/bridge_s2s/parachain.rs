@@ -0,0 +1,108 @@
+use bridge_s2s_traits::client::{S2SParaBridgeClientRelaychain, S2SParaBridgeClientSolochain};
+use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::{BestParaHeadHash, HeadData, ParaId, ParaInfo};
+use codec::Decode;
+use sp_runtime::traits::Header;
+use support_toolkit::convert::SmartCodecMapper;
+
+use crate::client::MockClient;
+use crate::state::MockStorage;
+use crate::types::{MockCall, MockChainInfo};
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> S2SParaBridgeClientRelaychain for MockClient<M> {
+    fn gen_parachain_head_storage_key(&self, para_id: u32) -> Vec<u8> {
+        MockStorage::<M::Chain>::para_heads_key(para_id)
+    }
+
+    async fn para_head_data(
+        &self,
+        para_id: ParaId,
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<HeadData>> {
+        Ok(self.storage_at(hash)?.para_heads.get(&para_id.0).cloned())
+    }
+}
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> S2SParaBridgeClientSolochain for MockClient<M> {
+    async fn best_para_heads(
+        &self,
+        para_id: ParaId,
+        _hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<ParaInfo>> {
+        Ok(self.state().best_para_heads.get(&para_id.0).cloned())
+    }
+
+    async fn submit_parachain_heads(
+        &self,
+        relay_block: (
+            <Self::Chain as Chain>::BlockNumber,
+            <Self::Chain as Chain>::Hash,
+        ),
+        parachains: Vec<(ParaId, <Self::Chain as Chain>::Hash)>,
+        parachain_heads_proof: Vec<Vec<u8>>,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let (relay_block_number, relay_block_hash) = relay_block;
+        let relay_block_number: u32 = SmartCodecMapper::map_to(&relay_block_number)?;
+        let mut state = self.state();
+        if !state.is_imported(&relay_block_hash) {
+            return Err(S2SClientError::Custom(format!(
+                "[{}] the relay block {} is not imported",
+                M::CHAIN,
+                relay_block_number,
+            )));
+        }
+        let mut storage_proof = Vec::with_capacity(parachain_heads_proof.len());
+        for item in &parachain_heads_proof {
+            let (key, value): (Vec<u8>, Vec<u8>) = Decode::decode(&mut item.as_slice())?;
+            storage_proof.push((key, value));
+        }
+
+        let mut submitted = Vec::with_capacity(parachains.len());
+        for (para_id, head_hash) in parachains {
+            let key = MockStorage::<M::Chain>::para_heads_key(para_id.0);
+            let head_data: HeadData = match storage_proof.iter().find(|(k, _)| k == &key) {
+                Some((_, value)) => Decode::decode(&mut value.as_slice())?,
+                None => {
+                    return Err(S2SClientError::Custom(format!(
+                        "[{}] the head of parachain {} is missing in proof",
+                        M::CHAIN,
+                        para_id.0,
+                    )));
+                }
+            };
+            let para_header: <Self::Chain as Chain>::Header =
+                Decode::decode(&mut head_data.0.as_slice())?;
+            if para_header.hash() != head_hash {
+                return Err(S2SClientError::Custom(format!(
+                    "[{}] the head hash of parachain {} is mismatched",
+                    M::CHAIN,
+                    para_id.0,
+                )));
+            }
+            state.best_para_heads.insert(
+                para_id.0,
+                ParaInfo {
+                    best_head_hash: BestParaHeadHash {
+                        at_relay_block_number: relay_block_number,
+                        head_hash: SmartCodecMapper::map_to(&head_hash)?,
+                    },
+                    next_imported_hash_position: 0,
+                },
+            );
+            state
+                .imported_headers
+                .push((*para_header.number(), head_hash));
+            submitted.push((para_id.0, array_bytes::bytes2hex("0x", head_hash.as_ref())));
+        }
+        Ok(self.include_extrinsic(
+            state,
+            MockCall::SubmitParachainHeads {
+                relay_block_number,
+                parachains: submitted,
+            },
+        ))
+    }
+}
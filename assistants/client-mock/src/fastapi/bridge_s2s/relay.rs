@@ -0,0 +1,488 @@
+use std::ops::RangeInclusive;
+
+use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay};
+use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
+use bridge_s2s_traits::types::bp_messages::{
+    InboundLaneData, MessageData, MessageKey, OutboundLaneData, UnrewardedRelayer,
+    UnrewardedRelayersState, Weight,
+};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::{
+    bp_header_chain, bridge_runtime_common, ExtrinsicLocation, MessagesDeliveryLimits,
+};
+use codec::{Decode, Encode};
+use sp_runtime::traits::Header;
+
+use support_toolkit::convert::SmartCodecMapper;
+
+use crate::client::MockClient;
+use crate::state::{MockChainState, MockStorage};
+use crate::types::{MockCall, MockChainInfo};
+
+/// decode mock storage proof to (key, value) list
+fn decode_proof(storage_proof: &[Vec<u8>]) -> S2SClientResult<Vec<(Vec<u8>, Vec<u8>)>> {
+    storage_proof
+        .iter()
+        .map(|item| Ok(Decode::decode(&mut item.as_slice())?))
+        .collect()
+}
+
+fn find_in_proof<T: Decode>(
+    proof: &[(Vec<u8>, Vec<u8>)],
+    key: &[u8],
+) -> S2SClientResult<Option<T>> {
+    match proof.iter().find(|(k, _)| k == key) {
+        Some((_, value)) => Ok(Some(Decode::decode(&mut value.as_slice())?)),
+        None => Ok(None),
+    }
+}
+
+/// Dispatch the calls like `utility.batch_all`, the state is reverted if any one fails
+fn batch_all<M: MockChainInfo>(
+    state: &mut MockChainState<M::Chain>,
+    dispatch: impl FnOnce(&mut MockChainState<M::Chain>) -> S2SClientResult<Vec<MockCall>>,
+) -> S2SClientResult<Vec<MockCall>> {
+    let storage = state.storage.clone();
+    let imported_headers = state.imported_headers.clone();
+    let best_target_finalized = state.best_target_finalized;
+    let result = dispatch(state);
+    if result.is_err() {
+        state.storage = storage;
+        state.imported_headers = imported_headers;
+        state.best_target_finalized = best_target_finalized;
+    }
+    result
+}
+
+fn import_finality_proof<M: MockChainInfo>(
+    state: &mut MockChainState<M::Chain>,
+    finality_target: <M::Chain as Chain>::Header,
+    justification: bp_header_chain::justification::GrandpaJustification<
+        <M::Chain as Chain>::Header,
+    >,
+) -> S2SClientResult<MockCall> {
+    let number = *finality_target.number();
+    let hash = finality_target.hash();
+    if justification.commit.target_hash != hash {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] the justification is not for header {}",
+            M::CHAIN,
+            array_bytes::bytes2hex("0x", hash.as_ref()),
+        )));
+    }
+    match state.best_target_finalized {
+        Some((best_number, _)) if best_number < number => {}
+        _ => {
+            return Err(S2SClientError::Custom(format!(
+                "[{}] the bridge not initialized or the header {} is too old",
+                M::CHAIN,
+                number,
+            )));
+        }
+    }
+    state.imported_headers.push((number, hash));
+    state.best_target_finalized = Some((number, hash));
+    Ok(MockCall::SubmitFinalityProof {
+        number: SmartCodecMapper::map_to(&number)?,
+        hash: array_bytes::bytes2hex("0x", hash.as_ref()),
+    })
+}
+
+fn receive_messages_proof<M: MockChainInfo>(
+    state: &mut MockChainState<M::Chain>,
+    relayer_id_at_bridged_chain: <M::Chain as Chain>::AccountId,
+    proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+        <M::Chain as Chain>::Hash,
+    >,
+    messages_count: u32,
+    dispatch_weight: Weight,
+    messages_fee: u128,
+) -> S2SClientResult<MockCall> {
+    let lane = proof.lane;
+    if !state.is_imported(&proof.bridged_header_hash) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] the bridged header {} is not imported",
+            M::CHAIN,
+            array_bytes::bytes2hex("0x", proof.bridged_header_hash.as_ref()),
+        )));
+    }
+    let limits = state.delivery_limits.clone();
+    if matches!(limits.max_extrinsic_weight, Some(max) if dispatch_weight.ref_time() > max) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] the dispatch weight {} is over limit",
+            M::CHAIN,
+            dispatch_weight.ref_time(),
+        )));
+    }
+    if matches!(limits.max_extrinsic_size, Some(max) if proof.encoded_size() > max as usize) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] the proof size {} is over limit",
+            M::CHAIN,
+            proof.encoded_size(),
+        )));
+    }
+    let storage_proof = decode_proof(&proof.storage_proof)?;
+    for nonce in proof.nonces_start..=proof.nonces_end {
+        let message_key = MockStorage::<M::Chain>::outbound_messages_key(lane, nonce);
+        if find_in_proof::<MessageData<u128>>(&storage_proof, &message_key)?.is_none() {
+            return Err(S2SClientError::Custom(format!(
+                "[{}] message {} is missing in proof",
+                M::CHAIN,
+                nonce,
+            )));
+        }
+    }
+    let outbound_lane_data: Option<OutboundLaneData> = find_in_proof(
+        &storage_proof,
+        &MockStorage::<M::Chain>::outbound_lanes_key(lane),
+    )?;
+
+    let inbound_lane_data = state.storage.inbound_lanes.entry(lane).or_default();
+    if let Some(outbound_lane_data) = outbound_lane_data {
+        let last_confirmed_nonce = outbound_lane_data.latest_received_nonce;
+        if last_confirmed_nonce > inbound_lane_data.last_confirmed_nonce {
+            inbound_lane_data.last_confirmed_nonce = last_confirmed_nonce;
+            inbound_lane_data
+                .relayers
+                .retain(|entry| entry.messages.end > last_confirmed_nonce);
+        }
+    }
+    if proof.nonces_start != inbound_lane_data.last_delivered_nonce() + 1 {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] invalid nonces start {}, the last delivered nonce is {}",
+            M::CHAIN,
+            proof.nonces_start,
+            inbound_lane_data.last_delivered_nonce(),
+        )));
+    }
+    let relayer_entries = inbound_lane_data.relayers.len() as u64;
+    if matches!(limits.max_unrewarded_relayer_entries, Some(max) if relayer_entries >= max) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] too many unrewarded relayer entries",
+            M::CHAIN,
+        )));
+    }
+    let unconfirmed_messages = proof.nonces_end - inbound_lane_data.last_confirmed_nonce;
+    if matches!(limits.max_unconfirmed_messages, Some(max) if unconfirmed_messages > max) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] too many unconfirmed messages",
+            M::CHAIN,
+        )));
+    }
+    match inbound_lane_data.relayers.back_mut() {
+        Some(entry) if entry.relayer == relayer_id_at_bridged_chain => {
+            entry.messages.end = proof.nonces_end;
+        }
+        _ => {
+            // the delivered messages is decoded from (begin, end, empty dispatch results)
+            let entry: UnrewardedRelayer<<M::Chain as Chain>::AccountId> =
+                SmartCodecMapper::map_to(&(
+                    relayer_id_at_bridged_chain,
+                    proof.nonces_start,
+                    proof.nonces_end,
+                    codec::Compact(0u32),
+                ))?;
+            inbound_lane_data.relayers.push_back(entry);
+        }
+    }
+    Ok(MockCall::ReceiveMessagesProof {
+        lane,
+        nonces: proof.nonces_start..=proof.nonces_end,
+        messages_count,
+        dispatch_weight: dispatch_weight.ref_time(),
+        messages_fee,
+    })
+}
+
+fn receive_messages_delivery_proof<M: MockChainInfo>(
+    state: &mut MockChainState<M::Chain>,
+    proof: bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof<
+        <M::Chain as Chain>::Hash,
+    >,
+    relayers_state: UnrewardedRelayersState,
+) -> S2SClientResult<MockCall> {
+    let lane = proof.lane;
+    if !state.is_imported(&proof.bridged_header_hash) {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] the bridged header {} is not imported",
+            M::CHAIN,
+            array_bytes::bytes2hex("0x", proof.bridged_header_hash.as_ref()),
+        )));
+    }
+    let storage_proof = decode_proof(&proof.storage_proof)?;
+    let inbound_lane_data: InboundLaneData<<M::Chain as Chain>::AccountId> = find_in_proof(
+        &storage_proof,
+        &MockStorage::<M::Chain>::inbound_lanes_key(lane),
+    )?
+    .ok_or_else(|| {
+        S2SClientError::Custom(format!("[{}] inbound lane is missing in proof", M::CHAIN))
+    })?;
+    let outbound_lane_data = state.storage.outbound_lanes.entry(lane).or_default();
+    let last_delivered_nonce = inbound_lane_data.last_delivered_nonce();
+    if last_delivered_nonce <= outbound_lane_data.latest_received_nonce {
+        return Err(S2SClientError::Custom(format!(
+            "[{}] no new confirmations, the latest received nonce is {}",
+            M::CHAIN,
+            outbound_lane_data.latest_received_nonce,
+        )));
+    }
+    outbound_lane_data.latest_received_nonce = last_delivered_nonce;
+    Ok(MockCall::ReceiveMessagesDeliveryProof {
+        lane,
+        relayers_state,
+    })
+}
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> S2SClientRelay for MockClient<M> {
+    fn gen_outbound_messages_storage_key(&self, lane: [u8; 4], message_nonce: u64) -> Vec<u8> {
+        MockStorage::<M::Chain>::outbound_messages_key(lane, message_nonce)
+    }
+
+    fn gen_outbound_lanes_storage_key(&self, lane: [u8; 4]) -> Vec<u8> {
+        MockStorage::<M::Chain>::outbound_lanes_key(lane)
+    }
+
+    fn gen_inbound_lanes_storage_key(&self, lane: [u8; 4]) -> Vec<u8> {
+        MockStorage::<M::Chain>::inbound_lanes_key(lane)
+    }
+
+    async fn calculate_dispatch_weight(
+        &self,
+        lane: [u8; 4],
+        nonces: RangeInclusive<u64>,
+    ) -> S2SClientResult<u64> {
+        let storage = self.storage_at(None)?;
+        let mut total_weight = 0u64;
+        for nonce in nonces {
+            let message = storage
+                .outbound_messages
+                .get(&(lane, nonce))
+                .ok_or_else(|| {
+                    S2SClientError::Custom(format!(
+                        "[{}] message {} not found in lane {}",
+                        M::CHAIN,
+                        nonce,
+                        array_bytes::bytes2hex("0x", lane),
+                    ))
+                })?;
+            total_weight += message.payload.len() as u64;
+        }
+        Ok(total_weight)
+    }
+
+    async fn messages_delivery_limits(&self) -> S2SClientResult<MessagesDeliveryLimits> {
+        Ok(self.state().delivery_limits.clone())
+    }
+
+    async fn best_target_finalized(
+        &self,
+        _at_block: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<
+        Option<(
+            <Self::Chain as Chain>::BlockNumber,
+            <Self::Chain as Chain>::Hash,
+        )>,
+    > {
+        Ok(self.state().best_target_finalized)
+    }
+
+    async fn current_authority_set(
+        &self,
+        _at_block: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<bp_header_chain::AuthoritySet>> {
+        Ok(self.state().bridged_authority_set.clone())
+    }
+
+    async fn initialize(
+        &self,
+        initialization_data: <Self as S2SClientGeneric>::InitializationData,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let header: <Self::Chain as Chain>::Header =
+            Decode::decode(&mut initialization_data.header.as_slice())?;
+        let mut state = self.state();
+        let imported = (*header.number(), header.hash());
+        state.imported_headers.push(imported);
+        state.best_target_finalized = Some(imported);
+        state.bridged_authority_set = Some(bp_header_chain::AuthoritySet::new(
+            initialization_data.authority_list,
+            initialization_data.set_id,
+        ));
+        Ok(self.include_extrinsic(
+            state,
+            MockCall::Initialize {
+                number: SmartCodecMapper::map_to(header.number())?,
+                hash: array_bytes::bytes2hex("0x", header.hash().as_ref()),
+            },
+        ))
+    }
+
+    async fn submit_finality_proof(
+        &self,
+        finality_target: <Self::Chain as Chain>::Header,
+        justification: bp_header_chain::justification::GrandpaJustification<
+            <Self::Chain as Chain>::Header,
+        >,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let mut state = self.state();
+        let call = import_finality_proof::<M>(&mut state, finality_target, justification)?;
+        Ok(self.include_extrinsic(state, call))
+    }
+
+    async fn outbound_lanes(
+        &self,
+        lane: [u8; 4],
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<OutboundLaneData> {
+        Ok(self
+            .storage_at(hash)?
+            .outbound_lanes
+            .get(&lane)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn inbound_lanes(
+        &self,
+        lane: [u8; 4],
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<InboundLaneData<<Self::Chain as Chain>::AccountId>> {
+        Ok(self
+            .storage_at(hash)?
+            .inbound_lanes
+            .get(&lane)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn outbound_messages(
+        &self,
+        message_key: MessageKey,
+        hash: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<MessageData<u128>>> {
+        Ok(self
+            .storage_at(hash)?
+            .outbound_messages
+            .get(&(message_key.lane_id, message_key.nonce))
+            .cloned())
+    }
+
+    async fn receive_messages_proof(
+        &self,
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+        messages_fee: u128,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let mut state = self.state();
+        let call = receive_messages_proof::<M>(
+            &mut state,
+            relayer_id_at_bridged_chain,
+            proof,
+            messages_count,
+            dispatch_weight,
+            messages_fee,
+        )?;
+        Ok(self.include_extrinsic(state, call))
+    }
+
+    async fn find_extrinsic(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<ExtrinsicLocation<<Self::Chain as Chain>::Hash>> {
+        let state = self.state();
+        if state.pending_extrinsics.contains(&hash) {
+            return Ok(ExtrinsicLocation::Pool);
+        }
+        let hash = array_bytes::bytes2hex("0x", hash.as_ref());
+        let location = state
+            .extrinsics
+            .iter()
+            .find(|extrinsic| extrinsic.hash == hash)
+            .and_then(|extrinsic| state.headers.get(extrinsic.block_number as usize))
+            .map(|header| ExtrinsicLocation::Block(header.hash()))
+            .unwrap_or(ExtrinsicLocation::NotFound);
+        Ok(location)
+    }
+
+    async fn estimate_receive_messages_proof_fee(
+        &self,
+        _relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        _proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        _messages_count: u32,
+        _dispatch_weight: Weight,
+    ) -> S2SClientResult<Option<u128>> {
+        Ok(self.state().estimated_fee)
+    }
+
+    async fn receive_messages_delivery_proof(
+        &self,
+        proof: bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        relayers_state: UnrewardedRelayersState,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let mut state = self.state();
+        let call = receive_messages_delivery_proof::<M>(&mut state, proof, relayers_state)?;
+        Ok(self.include_extrinsic(state, call))
+    }
+
+    async fn receive_messages_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+        messages_fee: u128,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let mut state = self.state();
+        let (finality_target, justification) = finality;
+        let calls = batch_all::<M>(&mut state, |state| {
+            Ok(vec![
+                import_finality_proof::<M>(state, finality_target, justification)?,
+                receive_messages_proof::<M>(
+                    state,
+                    relayer_id_at_bridged_chain,
+                    proof,
+                    messages_count,
+                    dispatch_weight,
+                    messages_fee,
+                )?,
+            ])
+        })?;
+        Ok(self.include_extrinsic(state, MockCall::BatchAll { calls }))
+    }
+
+    async fn receive_messages_delivery_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        proof: bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        relayers_state: UnrewardedRelayersState,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let mut state = self.state();
+        let (finality_target, justification) = finality;
+        let calls = batch_all::<M>(&mut state, |state| {
+            Ok(vec![
+                import_finality_proof::<M>(state, finality_target, justification)?,
+                receive_messages_delivery_proof::<M>(state, proof, relayers_state)?,
+            ])
+        })?;
+        Ok(self.include_extrinsic(state, MockCall::BatchAll { calls }))
+    }
+}
@@ -0,0 +1,10 @@
+use client_common_traits::ClientCommon;
+
+use crate::client::MockClient;
+use crate::types::MockChainInfo;
+
+impl<M: MockChainInfo> ClientCommon for MockClient<M> {
+    const CHAIN: &'static str = M::CHAIN;
+
+    type Chain = M::Chain;
+}
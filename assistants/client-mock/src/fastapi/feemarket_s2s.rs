@@ -0,0 +1,116 @@
+use feemarket_s2s_traits::api::{FeemarketApiBase, FeemarketApiQuote, FeemarketApiRelay};
+use feemarket_s2s_traits::error::{AbstractFeemarketError, AbstractFeemarketResult};
+use feemarket_s2s_traits::types::{Chain, LaneId, MessageNonce, Order, Relayer};
+use sp_runtime::traits::Header;
+use support_toolkit::convert::SmartCodecMapper;
+
+use crate::client::MockClient;
+use crate::types::{MockCall, MockChainInfo};
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> FeemarketApiBase for MockClient<M> {
+    async fn finalized_header_number(
+        &self,
+    ) -> AbstractFeemarketResult<<Self::Chain as Chain>::BlockNumber> {
+        Ok(*self.state().finalized_header().number())
+    }
+}
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> FeemarketApiRelay for MockClient<M> {
+    async fn order(
+        &self,
+        lane_id: LaneId,
+        message_nonce: MessageNonce,
+    ) -> AbstractFeemarketResult<
+        Option<
+            Order<
+                <Self::Chain as Chain>::AccountId,
+                <Self::Chain as Chain>::BlockNumber,
+                <Self::Chain as Chain>::Balance,
+            >,
+        >,
+    > {
+        Ok(self.state().orders.get(&(lane_id, message_nonce)).cloned())
+    }
+}
+
+#[async_trait::async_trait]
+impl<M: MockChainInfo> FeemarketApiQuote for MockClient<M> {
+    async fn assigned_relayers(
+        &self,
+    ) -> AbstractFeemarketResult<
+        Vec<Relayer<<Self::Chain as Chain>::AccountId, <Self::Chain as Chain>::Balance>>,
+    > {
+        let state = self.state();
+        let mut relayers = state.relayers.clone();
+        relayers.sort_by(|a, b| a.fee.cmp(&b.fee));
+        relayers.truncate(state.assigned_relayers_number);
+        Ok(relayers)
+    }
+
+    async fn is_relayer(&self) -> AbstractFeemarketResult<bool> {
+        let state = self.state();
+        Ok(state
+            .relayers
+            .iter()
+            .any(|item| Some(&item.id) == state.account.as_ref()))
+    }
+
+    async fn relayers(&self) -> AbstractFeemarketResult<Vec<<Self::Chain as Chain>::AccountId>> {
+        Ok(self
+            .state()
+            .relayers
+            .iter()
+            .map(|item| item.id.clone())
+            .collect())
+    }
+
+    async fn relayer(
+        &self,
+        account: <Self::Chain as Chain>::AccountId,
+    ) -> AbstractFeemarketResult<
+        Option<Relayer<<Self::Chain as Chain>::AccountId, <Self::Chain as Chain>::Balance>>,
+    > {
+        Ok(self
+            .state()
+            .relayers
+            .iter()
+            .find(|item| item.id == account)
+            .cloned())
+    }
+
+    async fn update_relay_fee(
+        &self,
+        amount: <Self::Chain as Chain>::Balance,
+    ) -> AbstractFeemarketResult<()> {
+        let fee = SmartCodecMapper::map_to(&amount)?;
+        let mut state = self.state();
+        let account = state.account.clone();
+        let relayer = state
+            .relayers
+            .iter_mut()
+            .find(|item| Some(&item.id) == account.as_ref())
+            .ok_or_else(|| AbstractFeemarketError::Custom("Not a relayer".to_string()))?;
+        relayer.fee = amount;
+        self.include_extrinsic(state, MockCall::UpdateRelayFee { fee });
+        Ok(())
+    }
+
+    async fn update_locked_collateral(
+        &self,
+        amount: <Self::Chain as Chain>::Balance,
+    ) -> AbstractFeemarketResult<()> {
+        let collateral = SmartCodecMapper::map_to(&amount)?;
+        let mut state = self.state();
+        let account = state.account.clone();
+        let relayer = state
+            .relayers
+            .iter_mut()
+            .find(|item| Some(&item.id) == account.as_ref())
+            .ok_or_else(|| AbstractFeemarketError::Custom("Not a relayer".to_string()))?;
+        relayer.collateral = amount;
+        self.include_extrinsic(state, MockCall::UpdateLockedCollateral { collateral });
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+mod bridge_s2s;
+mod common;
+mod feemarket_s2s;
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod fastapi;
+pub mod state;
+pub mod subquery;
+pub mod types;
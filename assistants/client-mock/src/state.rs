@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use bridge_s2s_traits::types::bp_header_chain::AuthoritySet;
+use bridge_s2s_traits::types::bp_messages::{
+    InboundLaneData, LaneId, MessageData, MessageNonce, OutboundLaneData,
+};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::{HeadData, MessagesDeliveryLimits, ParaInfo};
+use codec::Encode;
+use feemarket_s2s_traits::types::{Order, Relayer};
+use sp_runtime::traits::{Hash, Header};
+use sp_runtime::Digest;
+
+use crate::types::{MockCall, MockExtrinsic};
+
+const PREFIX_OUTBOUND_LANES: &[u8] = b"outbound_lanes";
+const PREFIX_INBOUND_LANES: &[u8] = b"inbound_lanes";
+const PREFIX_OUTBOUND_MESSAGES: &[u8] = b"outbound_messages";
+const PREFIX_PARA_HEADS: &[u8] = b"para_heads";
+
+/// Storage which can be read with proof
+pub struct MockStorage<C: Chain> {
+    pub outbound_lanes: HashMap<LaneId, OutboundLaneData>,
+    pub inbound_lanes: HashMap<LaneId, InboundLaneData<C::AccountId>>,
+    pub outbound_messages: HashMap<(LaneId, MessageNonce), MessageData<u128>>,
+    /// para heads stored in relay chain
+    pub para_heads: HashMap<u32, HeadData>,
+}
+
+impl<C: Chain> Clone for MockStorage<C> {
+    fn clone(&self) -> Self {
+        Self {
+            outbound_lanes: self.outbound_lanes.clone(),
+            inbound_lanes: self.inbound_lanes.clone(),
+            outbound_messages: self.outbound_messages.clone(),
+            para_heads: self.para_heads.clone(),
+        }
+    }
+}
+
+impl<C: Chain> Default for MockStorage<C> {
+    fn default() -> Self {
+        Self {
+            outbound_lanes: Default::default(),
+            inbound_lanes: Default::default(),
+            outbound_messages: Default::default(),
+            para_heads: Default::default(),
+        }
+    }
+}
+
+/// In-memory chain state
+pub struct MockChainState<C: Chain> {
+    /// all produced headers, the index is block number
+    pub headers: Vec<C::Header>,
+    /// storage at each block, the index is block number
+    pub snapshots: Vec<MockStorage<C>>,
+    /// best finalized block number
+    pub finalized: u32,
+    /// storage of best block, changes are snapshotted when a new block produced
+    pub storage: MockStorage<C>,
+    /// bridged chain headers imported by this chain (grandpa pallet or parachains pallet)
+    pub imported_headers: Vec<(C::BlockNumber, C::Hash)>,
+    /// best finalized bridged chain header
+    pub best_target_finalized: Option<(C::BlockNumber, C::Hash)>,
+    /// grandpa authority set of bridged chain, it's set by bridge initialization
+    pub bridged_authority_set: Option<AuthoritySet>,
+    /// para heads imported by solo chain
+    pub best_para_heads: HashMap<u32, ParaInfo>,
+    pub orders: HashMap<(LaneId, MessageNonce), Order<C::AccountId, C::BlockNumber, C::Balance>>,
+    /// enrolled relayers of fee market
+    pub relayers: Vec<Relayer<C::AccountId, C::Balance>>,
+    /// count of assigned relayers, the cheapest relayers are assigned
+    pub assigned_relayers_number: usize,
+    /// account which signs extrinsics
+    pub account: Option<C::AccountId>,
+    /// fee of `receive_messages_proof` estimated by pre-flight check, none means disabled
+    pub estimated_fee: Option<u128>,
+    /// limits of `receive_messages_proof`, the messages proof over limits is rejected
+    pub delivery_limits: MessagesDeliveryLimits,
+    pub extrinsics: Vec<MockExtrinsic>,
+    /// hashes of extrinsics waiting in transaction pool, they are never included
+    pub pending_extrinsics: Vec<C::Hash>,
+}
+
+impl<C: Chain> MockChainState<C> {
+    pub fn new() -> Self {
+        let genesis = C::Header::new(
+            0u32.into(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+        Self {
+            headers: vec![genesis],
+            snapshots: vec![Default::default()],
+            finalized: 0,
+            storage: Default::default(),
+            imported_headers: Default::default(),
+            best_target_finalized: None,
+            bridged_authority_set: None,
+            best_para_heads: Default::default(),
+            orders: Default::default(),
+            relayers: Default::default(),
+            assigned_relayers_number: 3,
+            account: None,
+            estimated_fee: None,
+            delivery_limits: Default::default(),
+            extrinsics: Default::default(),
+            pending_extrinsics: Default::default(),
+        }
+    }
+}
+
+impl<C: Chain> Default for MockChainState<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Chain> MockChainState<C> {
+    pub fn best_header(&self) -> &C::Header {
+        self.headers.last().expect("Genesis header always exists")
+    }
+
+    pub fn finalized_header(&self) -> &C::Header {
+        &self.headers[self.finalized as usize]
+    }
+
+    pub fn header_by_hash(&self, hash: &C::Hash) -> Option<&C::Header> {
+        self.headers.iter().find(|header| &header.hash() == hash)
+    }
+
+    /// produce a new block, the block is finalized immediately
+    pub fn produce_block(&mut self) -> &C::Header {
+        self.produce_block_with_digest(Default::default())
+    }
+
+    /// produce a new block with digest, the block is finalized immediately
+    pub fn produce_block_with_digest(&mut self, digest: Digest) -> &C::Header {
+        let parent = self.best_header();
+        let number = self.headers.len() as u32;
+        let header = C::Header::new(
+            number.into(),
+            Default::default(),
+            Default::default(),
+            parent.hash(),
+            digest,
+        );
+        self.headers.push(header);
+        self.snapshots.push(self.storage.clone());
+        self.finalized = number;
+        self.best_header()
+    }
+
+    /// include a call by a new block
+    pub fn include_extrinsic(&mut self, call: MockCall) -> C::Hash {
+        let index = self.extrinsics.len() as u32;
+        let hash = C::Hasher::hash(&(index, format!("{call:?}")).encode());
+        let block_number = self.headers.len() as u32;
+        self.produce_block();
+        self.extrinsics.push(MockExtrinsic {
+            hash: array_bytes::bytes2hex("0x", hash.as_ref()),
+            block_number,
+            call,
+        });
+        hash
+    }
+
+    pub fn is_imported(&self, hash: &C::Hash) -> bool {
+        self.imported_headers.iter().any(|(_, h)| h == hash)
+    }
+
+    /// storage at block, read best block if the hash is none
+    pub fn storage_at(&self, hash: Option<&C::Hash>) -> Option<&MockStorage<C>> {
+        match hash {
+            Some(hash) => {
+                let index = self
+                    .headers
+                    .iter()
+                    .position(|header| &header.hash() == hash)?;
+                self.snapshots.get(index)
+            }
+            None => Some(&self.storage),
+        }
+    }
+}
+
+impl<C: Chain> MockStorage<C> {
+    pub fn outbound_lanes_key(lane: LaneId) -> Vec<u8> {
+        [PREFIX_OUTBOUND_LANES, &lane[..]].concat()
+    }
+
+    pub fn inbound_lanes_key(lane: LaneId) -> Vec<u8> {
+        [PREFIX_INBOUND_LANES, &lane[..]].concat()
+    }
+
+    pub fn outbound_messages_key(lane: LaneId, nonce: MessageNonce) -> Vec<u8> {
+        [
+            PREFIX_OUTBOUND_MESSAGES,
+            &lane[..],
+            &nonce.to_le_bytes()[..],
+        ]
+        .concat()
+    }
+
+    pub fn para_heads_key(para_id: u32) -> Vec<u8> {
+        [PREFIX_PARA_HEADS, &para_id.to_le_bytes()[..]].concat()
+    }
+
+    /// read encoded storage value by key
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        if let Some(lane) = key.strip_prefix(PREFIX_OUTBOUND_LANES) {
+            let lane: LaneId = lane.try_into().ok()?;
+            return Some(
+                self.outbound_lanes
+                    .get(&lane)
+                    .cloned()
+                    .unwrap_or_default()
+                    .encode(),
+            );
+        }
+        if let Some(lane) = key.strip_prefix(PREFIX_INBOUND_LANES) {
+            let lane: LaneId = lane.try_into().ok()?;
+            return Some(
+                self.inbound_lanes
+                    .get(&lane)
+                    .cloned()
+                    .unwrap_or_default()
+                    .encode(),
+            );
+        }
+        if let Some(message_key) = key.strip_prefix(PREFIX_OUTBOUND_MESSAGES) {
+            if message_key.len() != 12 {
+                return None;
+            }
+            let lane: LaneId = message_key[..4].try_into().ok()?;
+            let nonce = MessageNonce::from_le_bytes(message_key[4..].try_into().ok()?);
+            return self
+                .outbound_messages
+                .get(&(lane, nonce))
+                .map(|message| message.encode());
+        }
+        if let Some(para_id) = key.strip_prefix(PREFIX_PARA_HEADS) {
+            let para_id = u32::from_le_bytes(para_id.try_into().ok()?);
+            return self.para_heads.get(&para_id).map(|head| head.encode());
+        }
+        None
+    }
+
+    /// the storage proof of mock chain is a list of encoded (key, value)
+    pub fn read_proof(&self, storage_keys: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        storage_keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = self.get(&key)?;
+                Some((key, value).encode())
+            })
+            .collect()
+    }
+}
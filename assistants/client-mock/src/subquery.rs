@@ -0,0 +1,262 @@
+use std::sync::{Arc, Mutex};
+
+use ::subquery::types::{
+    BridgeName, CandidateIncludedEvent, JustificationMapping, NeedRelayBlock, OriginType,
+    RelayBlockType,
+};
+use ::subquery::{Subquery, SubqueryComponent, SubqueryConfig};
+use codec::Encode;
+use serde_json::{json, Value};
+use sp_runtime::traits::Header;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Indexed data of fake subquery
+#[derive(Default)]
+struct FakeSubqueryData {
+    need_relay_blocks: Vec<NeedRelayBlock>,
+    justifications: Vec<JustificationMapping>,
+    candidate_included_events: Vec<CandidateIncludedEvent>,
+}
+
+/// Local graphql server, answer the queries used by relay-s2s from indexed data.
+#[derive(Clone)]
+pub struct FakeSubquery {
+    endpoint: String,
+    data: Arc<Mutex<FakeSubqueryData>>,
+}
+
+impl FakeSubquery {
+    /// Start fake subquery server on a random local port
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let endpoint = format!("http://{}", listener.local_addr()?);
+        let data: Arc<Mutex<FakeSubqueryData>> = Default::default();
+        let server_data = data.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let data = server_data.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::serve(stream, data).await {
+                        tracing::error!(target: "client-mock", "[fake-subquery] {:?}", e);
+                    }
+                });
+            }
+        });
+        Ok(Self { endpoint, data })
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// Subquery client connect to this server
+    pub fn subquery(&self) -> Subquery {
+        SubqueryComponent::component(
+            SubqueryConfig {
+                endpoint: self.endpoint.clone(),
+            },
+            BridgeName::PangolinPangoro,
+        )
+    }
+}
+
+// index
+impl FakeSubquery {
+    /// Index a mandatory header
+    pub fn index_mandatory<H: Header<Number = u32>>(&self, header: &H) {
+        let block =
+            Self::need_relay_block(header, RelayBlockType::Mandatory, OriginType::Mandatory);
+        self.data.lock().unwrap().need_relay_blocks.push(block);
+    }
+
+    /// Index an on-demand header which contains the message sent to lane
+    pub fn index_on_demand<H: Header<Number = u32>>(
+        &self,
+        origin: OriginType,
+        header: &H,
+        lane: [u8; 4],
+        nonce: u64,
+    ) {
+        let mut block = Self::need_relay_block(header, RelayBlockType::OnDemand, origin);
+        block.lane_id = Some(array_bytes::bytes2hex("", lane));
+        block.message_nonce = Some(nonce);
+        self.data.lock().unwrap().need_relay_blocks.push(block);
+    }
+
+    /// Index the justification of header
+    pub fn index_justification<H: Header<Number = u32>>(
+        &self,
+        header: &H,
+        justification: Vec<u8>,
+        mandatory: bool,
+    ) {
+        let block_hash = array_bytes::bytes2hex("0x", header.hash().as_ref());
+        self.data
+            .lock()
+            .unwrap()
+            .justifications
+            .push(JustificationMapping {
+                id: block_hash.clone(),
+                block_number: *header.number(),
+                block_hash,
+                mandatory,
+                justification,
+            });
+    }
+
+    /// Index the candidate included event of parachain head
+    pub fn index_candidate_included<H: Header<Number = u32>, P: Header>(
+        &self,
+        relay_header: &H,
+        para_id: u32,
+        para_header: &P,
+    ) {
+        let para_head = array_bytes::bytes2hex("0x", para_header.hash().as_ref());
+        self.data
+            .lock()
+            .unwrap()
+            .candidate_included_events
+            .push(CandidateIncludedEvent {
+                id: format!("{}-{}", relay_header.number(), para_id),
+                included_relay_block: *relay_header.number(),
+                para_id,
+                signature: "0x".to_string(),
+                para_head,
+                relay_parent: array_bytes::bytes2hex("0x", relay_header.parent_hash().as_ref()),
+            });
+    }
+
+    fn need_relay_block<H: Header<Number = u32>>(
+        header: &H,
+        type_: RelayBlockType,
+        origin: OriginType,
+    ) -> NeedRelayBlock {
+        let to_bytes32 = |v: &[u8]| {
+            let mut bytes = [0u8; 32];
+            bytes.copy_from_slice(v);
+            bytes
+        };
+        let block_hash = array_bytes::bytes2hex("0x", header.hash().as_ref());
+        NeedRelayBlock {
+            id: block_hash.clone(),
+            block_number: *header.number(),
+            block_hash,
+            type_,
+            origin,
+            lane_id: None,
+            message_nonce: None,
+            parent_hash: to_bytes32(header.parent_hash().as_ref()),
+            state_root: to_bytes32(header.state_root().as_ref()),
+            extrinsics_root: to_bytes32(header.extrinsics_root().as_ref()),
+            digest: header.digest().encode(),
+        }
+    }
+}
+
+// graphql
+impl FakeSubquery {
+    async fn serve(
+        mut stream: TcpStream,
+        data: Arc<Mutex<FakeSubqueryData>>,
+    ) -> std::io::Result<()> {
+        let mut buffer = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let header_end = loop {
+            let size = stream.read(&mut chunk).await?;
+            if size == 0 {
+                return Ok(());
+            }
+            buffer.extend_from_slice(&chunk[..size]);
+            if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+                break pos + 4;
+            }
+        };
+        let content_length = String::from_utf8_lossy(&buffer[..header_end])
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .find(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+            .unwrap_or(0);
+        while buffer.len() < header_end + content_length {
+            let size = stream.read(&mut chunk).await?;
+            if size == 0 {
+                break;
+            }
+            buffer.extend_from_slice(&chunk[..size]);
+        }
+
+        let request: Value = serde_json::from_slice(&buffer[header_end..]).unwrap_or(Value::Null);
+        let response = {
+            let data = data.lock().unwrap();
+            Self::answer(&data, &request)
+        }
+        .to_string();
+        let message = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.len(),
+            response,
+        );
+        stream.write_all(message.as_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn answer(data: &FakeSubqueryData, request: &Value) -> Value {
+        let query = request["query"].as_str().unwrap_or_default();
+        let variables = &request["variables"];
+        let origin_is = |block: &NeedRelayBlock| {
+            serde_json::to_value(&block.origin).ok().as_ref() == Some(&variables["origin"])
+        };
+
+        if query.contains("candidateIncludedEvents") {
+            let para_head = variables["para_head"].as_str().unwrap_or_default();
+            let event = data
+                .candidate_included_events
+                .iter()
+                .filter(|event| event.para_head == para_head)
+                .min_by_key(|event| event.included_relay_block);
+            return Self::nodes("candidateIncludedEvents", event);
+        }
+        if query.contains("justificationMappings") {
+            let justification = if query.contains("$hash") {
+                let hash = variables["hash"].as_str().unwrap_or_default();
+                data.justifications
+                    .iter()
+                    .find(|item| item.block_hash == hash)
+            } else {
+                data.justifications
+                    .iter()
+                    .max_by_key(|item| item.block_number)
+            };
+            return Self::nodes("justificationMappings", justification);
+        }
+
+        let on_demand = data
+            .need_relay_blocks
+            .iter()
+            .filter(|block| !block.is_mandatory());
+        let block = if query.contains("queryNeedRelay") {
+            let lane = variables["lane"].as_str();
+            let nonce = variables["nonce"].as_u64();
+            on_demand
+                .filter(|block| origin_is(block))
+                .find(|block| block.lane_id.as_deref() == lane && block.message_nonce == nonce)
+        } else if query.contains("$block") {
+            let number = variables["block"].as_u64().unwrap_or_default();
+            data.need_relay_blocks
+                .iter()
+                .filter(|block| block.is_mandatory() && block.block_number as u64 > number)
+                .min_by_key(|block| block.block_number)
+        } else {
+            on_demand
+                .filter(|block| origin_is(block))
+                .max_by_key(|block| block.block_number)
+        };
+        Self::nodes("needRelayBlocks", block)
+    }
+
+    fn nodes<T: serde::Serialize>(name: &str, node: Option<&T>) -> Value {
+        let nodes: Vec<&T> = node.into_iter().collect();
+        json!({ "data": { name: { "nodes": nodes } } })
+    }
+}
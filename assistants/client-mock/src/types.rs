@@ -0,0 +1,101 @@
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+
+use bridge_s2s_traits::types::bp_messages::{LaneId, UnrewardedRelayersState};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use sp_finality_grandpa::AuthorityList;
+
+/// Mock chain info
+pub trait MockChainInfo: 'static + Send + Sync + Clone + Debug {
+    /// chain name
+    const CHAIN: &'static str;
+
+    /// chain types
+    type Chain: Chain;
+}
+
+/// Mock solo chain (or parachain) as bridge source
+#[derive(Clone, Debug)]
+pub struct MockSourceChain;
+
+impl MockChainInfo for MockSourceChain {
+    const CHAIN: &'static str = "mock-source";
+
+    type Chain = bp_darwinia_core::DarwiniaLike;
+}
+
+/// Mock solo chain (or parachain) as bridge target
+#[derive(Clone, Debug)]
+pub struct MockTargetChain;
+
+impl MockChainInfo for MockTargetChain {
+    const CHAIN: &'static str = "mock-target";
+
+    type Chain = bp_darwinia_core::DarwiniaLike;
+}
+
+/// Mock relay chain
+#[derive(Clone, Debug)]
+pub struct MockRelayChain;
+
+impl MockChainInfo for MockRelayChain {
+    const CHAIN: &'static str = "mock-relaychain";
+
+    type Chain = bp_polkadot_core::PolkadotLike;
+}
+
+/// Bridge initialization data, the header is scale encoded
+#[derive(Clone, Debug, codec::Encode, codec::Decode)]
+pub struct MockInitializationData {
+    pub header: Vec<u8>,
+    pub authority_list: AuthorityList,
+    pub set_id: u64,
+}
+
+/// Calls submitted to mock chain
+#[derive(Clone, Debug)]
+pub enum MockCall {
+    Initialize {
+        number: u32,
+        hash: String,
+    },
+    SubmitFinalityProof {
+        number: u32,
+        hash: String,
+    },
+    SubmitParachainHeads {
+        relay_block_number: u32,
+        parachains: Vec<(u32, String)>,
+    },
+    ReceiveMessagesProof {
+        lane: LaneId,
+        nonces: RangeInclusive<u64>,
+        messages_count: u32,
+        dispatch_weight: u64,
+        messages_fee: u128,
+    },
+    ReceiveMessagesDeliveryProof {
+        lane: LaneId,
+        relayers_state: UnrewardedRelayersState,
+    },
+    /// calls dispatched by `utility.batch_all`
+    BatchAll {
+        calls: Vec<MockCall>,
+    },
+    UpdateRelayFee {
+        fee: u128,
+    },
+    UpdateLockedCollateral {
+        collateral: u128,
+    },
+}
+
+/// Extrinsic included by mock chain
+#[derive(Clone, Debug)]
+pub struct MockExtrinsic {
+    /// extrinsic hash
+    pub hash: String,
+    /// the block included this extrinsic
+    pub block_number: u32,
+    pub call: MockCall,
+}
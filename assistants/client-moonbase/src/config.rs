@@ -1,9 +1,11 @@
+use client_common_traits::reconnect::Endpoints;
 use serde::{Deserialize, Serialize};
 use subxt::tx::SubstrateExtrinsicParams;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ClientConfig {
-    pub endpoint: String,
+    /// websocket endpoints, reconnect to the next one when the connection is lost
+    pub endpoint: Endpoints,
     /// relayer's private key
     pub relayer_private_key: String,
 }
@@ -1,14 +1,20 @@
+use client_common_traits::reconnect::Endpoints;
 use client_common_traits::subxt_darwinia_like::DarwiniaLikeExtrinsicParams;
+use client_common_traits::transaction::TransactionConfig;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ClientConfig {
-    pub endpoint: String,
+    /// websocket endpoints, reconnect to the next one when the connection is lost
+    pub endpoint: Endpoints,
 
     /// relayer's private key
     pub relayer_private_key: String,
     /// the real account behind the relayer
     pub relayer_real_account: Option<String>,
+    /// tip and mortality of extrinsics
+    #[serde(default)]
+    pub transaction: TransactionConfig,
 }
 
 /// Pangolin subxt config
@@ -1,6 +1,6 @@
-use feemarket_s2s_traits::api::FeemarketApiRelay;
-use feemarket_s2s_traits::error::AbstractFeemarketResult;
-use feemarket_s2s_traits::types::{Chain, Order};
+use feemarket_s2s_traits::api::{FeemarketApiQuote, FeemarketApiRelay};
+use feemarket_s2s_traits::error::{AbstractFeemarketError, AbstractFeemarketResult};
+use feemarket_s2s_traits::types::{Chain, Order, Relayer};
 use support_toolkit::convert::SmartCodecMapper;
 
 use crate::client::PangolinClient;
@@ -30,3 +30,93 @@ impl FeemarketApiRelay for PangolinClient {
         }
     }
 }
+
+#[async_trait::async_trait]
+impl FeemarketApiQuote for PangolinClient {
+    async fn assigned_relayers(
+        &self,
+    ) -> AbstractFeemarketResult<
+        Vec<Relayer<<Self::Chain as Chain>::AccountId, <Self::Chain as Chain>::Balance>>,
+    > {
+        let address = crate::subxt_runtime::api::storage()
+            .pangoro_fee_market()
+            .assigned_relayers();
+        match self.subxt().storage().fetch(&address, None).await? {
+            Some(v) => Ok(SmartCodecMapper::map_to(&v)?),
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn is_relayer(&self) -> AbstractFeemarketResult<bool> {
+        let relayers = self.relayers().await?;
+        let account_id = SmartCodecMapper::map_to(self.account().real_account())?;
+        Ok(relayers.contains(&account_id))
+    }
+
+    async fn relayers(&self) -> AbstractFeemarketResult<Vec<<Self::Chain as Chain>::AccountId>> {
+        let address = crate::subxt_runtime::api::storage()
+            .pangoro_fee_market()
+            .relayers();
+        match self.subxt().storage().fetch(&address, None).await? {
+            Some(v) => Ok(SmartCodecMapper::map_to(&v)?),
+            None => Ok(vec![]),
+        }
+    }
+
+    async fn relayer(
+        &self,
+        account: <Self::Chain as Chain>::AccountId,
+    ) -> AbstractFeemarketResult<
+        Option<Relayer<<Self::Chain as Chain>::AccountId, <Self::Chain as Chain>::Balance>>,
+    > {
+        let expected_account = SmartCodecMapper::map_to(&account)?;
+        let address = crate::subxt_runtime::api::storage()
+            .pangoro_fee_market()
+            .relayers_map(expected_account);
+        match self.subxt().storage().fetch(&address, None).await? {
+            Some(v) => Ok(Some(SmartCodecMapper::map_to(&v)?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn update_relay_fee(
+        &self,
+        amount: <Self::Chain as Chain>::Balance,
+    ) -> AbstractFeemarketResult<()> {
+        let expected_amount = SmartCodecMapper::map_to(&amount)?;
+        let call = crate::subxt_runtime::api::tx()
+            .pangoro_fee_market()
+            .update_relay_fee(expected_amount);
+        let mut submitted = self.submit(&call).await?;
+        submitted.wait_for_finalized().await.map_err(|e| {
+            AbstractFeemarketError::RPC(format!("send transaction failed: {:?}", e))
+        })?;
+        Ok(())
+    }
+
+    async fn update_locked_collateral(
+        &self,
+        amount: <Self::Chain as Chain>::Balance,
+    ) -> AbstractFeemarketResult<()> {
+        let account = SmartCodecMapper::map_to(self.account().real_account())?;
+        let relayer = self.relayer(account).await?.ok_or_else(|| {
+            AbstractFeemarketError::Custom("You are not a relayer, please enroll first".to_string())
+        })?;
+        let expected_amount = SmartCodecMapper::map_to(&amount)?;
+        let mut submitted = if amount > relayer.collateral {
+            let call = crate::subxt_runtime::api::tx()
+                .pangoro_fee_market()
+                .increase_locked_collateral(expected_amount);
+            self.submit(&call).await?
+        } else {
+            let call = crate::subxt_runtime::api::tx()
+                .pangoro_fee_market()
+                .decrease_locked_collateral(expected_amount);
+            self.submit(&call).await?
+        };
+        submitted.wait_for_finalized().await.map_err(|e| {
+            AbstractFeemarketError::RPC(format!("send transaction failed: {:?}", e))
+        })?;
+        Ok(())
+    }
+}
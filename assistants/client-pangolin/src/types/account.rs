@@ -28,18 +28,15 @@ mod darwinia {
     pub struct DarwiniaAccount {
         /// signer of the account
         signer: Signer,
-        /// proxy real
-        real: Option<Signer>,
+        /// proxy real, extrinsics are dispatched on behalf of it
+        real: Option<AccountId>,
     }
 
     impl Debug for DarwiniaAccount {
         fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
             f.write_str(&format!("account: {},", self.signer.account_id()))?;
             f.write_str(" signer: <..>,")?;
-            f.write_str(&format!(
-                " real: {:?}",
-                self.real.clone().map(|v| *v.account_id())
-            ))?;
+            f.write_str(&format!(" real: {:?}", self.real))?;
             Ok(())
         }
     }
@@ -52,16 +49,22 @@ mod darwinia {
                 Pair::from_string(&seed, None).map_err(|e| ClientError::Seed(format!("{e:?}")))?; // if not a valid seed
             let signer = PairSigner::new(pair);
 
-            let mut real_signer = None;
-            if let Some(real_seed) = real {
-                let pair = Pair::from_string(&real_seed, None)
-                    .map_err(|e| ClientError::Seed(format!("{e:?}")))?;
-                real_signer = Some(PairSigner::new(pair))
+            let real = match real {
+                Some(real) => Some(Self::parse_real(&real)?),
+                None => None,
             };
-            Ok(Self {
-                signer,
-                real: real_signer,
-            })
+            Ok(Self { signer, real })
+        }
+
+        /// the real account is an address, a seed is also accepted for compatibility
+        fn parse_real(real: &str) -> ClientResult<AccountId> {
+            if let Ok(account) = array_bytes::hex_n_into::<_, AccountId, 20>(real) {
+                return Ok(account);
+            }
+            let pair =
+                Pair::from_string(real, None).map_err(|e| ClientError::Seed(format!("{e:?}")))?;
+            let signer: Signer = PairSigner::new(pair);
+            Ok(*signer.account_id())
         }
     }
 
@@ -76,13 +79,16 @@ mod darwinia {
             &self.signer
         }
 
+        /// get proxy real account if configured
+        pub fn real(&self) -> Option<&AccountId> {
+            self.real.as_ref()
+        }
+
         /// get raw real account
         pub fn real_account(&self) -> &AccountId {
-            if let Some(real_signer) = &self.real {
-                real_signer.account_id()
-            } else {
-                self.signer.account_id()
-            }
+            self.real
+                .as_ref()
+                .unwrap_or_else(|| self.signer.account_id())
         }
     }
 }
@@ -5,9 +5,10 @@ use client_pangolin::error::ClientResult;
 
 pub async fn client() -> ClientResult<PangolinClient> {
     let config = ClientConfig {
-        endpoint: "wss://pangolin-rpc.darwinia.network".to_string(),
+        endpoint: "wss://pangolin-rpc.darwinia.network".into(),
         relayer_private_key: "//Alice".to_string(),
         relayer_real_account: None,
+        transaction: Default::default(),
     };
     PangolinClientComponent::component(config).await
 }
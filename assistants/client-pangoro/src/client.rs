@@ -1,7 +1,14 @@
+use client_common_traits::submitter::{DarwiniaLikeSubmitter, SubmittedExtrinsic};
+use client_common_traits::transaction::TransactionConfig;
+use codec::{Decode, Encode};
 use subxt::client::OnlineClient;
+use subxt::tx::{StaticTxPayload, TxPayload};
 
 use crate::config::PangoroSubxtConfig;
-use crate::types::DarwiniaAccount;
+use crate::types::{runtime_types, DarwiniaAccount, Signer};
+
+/// `proxy.proxy` call which dispatches the relay call as the real account
+type ProxyCall = StaticTxPayload<crate::subxt_runtime::api::proxy::calls::Proxy>;
 
 /// Pangoro client
 #[derive(Debug, Clone)]
@@ -10,12 +17,24 @@ pub struct PangoroClient {
     client: OnlineClient<PangoroSubxtConfig>,
     /// Pangoro Account
     account: DarwiniaAccount,
+    /// Submitter of relayer, the nonce is shared by clients of the same relayer
+    submitter: DarwiniaLikeSubmitter<PangoroSubxtConfig, Signer>,
 }
 
 impl PangoroClient {
     /// Create a new darwinia client
-    pub fn new(client: OnlineClient<PangoroSubxtConfig>, account: DarwiniaAccount) -> Self {
-        Self { client, account }
+    pub fn new(
+        client: OnlineClient<PangoroSubxtConfig>,
+        account: DarwiniaAccount,
+        transaction: TransactionConfig,
+    ) -> Self {
+        let submitter =
+            DarwiniaLikeSubmitter::new(client.clone(), account.signer().clone(), transaction);
+        Self {
+            client,
+            account,
+            submitter,
+        }
     }
 }
 
@@ -32,4 +51,74 @@ impl PangoroClient {
     pub fn subxt(&self) -> &OnlineClient<PangoroSubxtConfig> {
         &self.client
     }
+
+    /// Get submitter of relayer
+    pub fn submitter(&self) -> &DarwiniaLikeSubmitter<PangoroSubxtConfig, Signer> {
+        &self.submitter
+    }
+}
+
+impl PangoroClient {
+    /// Submit the call by relayer without waiting for finality, the call is wrapped by
+    /// `proxy.proxy` when the real account is configured
+    pub async fn submit<Call: TxPayload>(
+        &self,
+        call: &Call,
+    ) -> Result<SubmittedExtrinsic, subxt::Error> {
+        self.submit_with_fee(call, 0).await
+    }
+
+    /// Submit the call which relays messages, the tip may be scaled by `messages_fee`
+    pub async fn submit_with_fee<Call: TxPayload>(
+        &self,
+        call: &Call,
+        messages_fee: u128,
+    ) -> Result<SubmittedExtrinsic, subxt::Error> {
+        match self.proxy_call(call)? {
+            Some(proxy_call) => self.submitter.submit(&proxy_call, messages_fee).await,
+            None => self.submitter.submit(call, messages_fee).await,
+        }
+    }
+
+    /// Estimate fee of the call by `payment_queryInfo`, none if the pre-flight check
+    /// is disabled
+    pub async fn estimate_fee<Call: TxPayload>(
+        &self,
+        call: &Call,
+    ) -> Result<Option<u128>, subxt::Error> {
+        if !self.submitter.preflight_enabled() {
+            return Ok(None);
+        }
+        let fee = match self.proxy_call(call)? {
+            Some(proxy_call) => self.submitter.estimate_fee(&proxy_call).await?,
+            None => self.submitter.estimate_fee(call).await?,
+        };
+        Ok(Some(fee))
+    }
+
+    /// Decode the call as runtime call, e.g. to be nested in `utility.batch_all`
+    pub fn runtime_call<Call: TxPayload>(
+        &self,
+        call: &Call,
+    ) -> Result<runtime_types::pangoro_runtime::RuntimeCall, subxt::Error> {
+        let mut call_data = Vec::new();
+        call.encode_call_data(&self.client.metadata(), &mut call_data)?;
+        Ok(runtime_types::pangoro_runtime::RuntimeCall::decode(
+            &mut call_data.as_slice(),
+        )?)
+    }
+
+    /// Wrap the call by `proxy.proxy` when the real account is configured
+    fn proxy_call<Call: TxPayload>(&self, call: &Call) -> Result<Option<ProxyCall>, subxt::Error> {
+        let real = match self.account.real() {
+            Some(real) => real,
+            None => return Ok(None),
+        };
+        let call = self.runtime_call(call)?;
+        let real = runtime_types::account::AccountId20::decode(&mut real.encode().as_slice())?;
+        let proxy_call = crate::subxt_runtime::api::tx()
+            .proxy()
+            .proxy(real, None, call);
+        Ok(Some(proxy_call))
+    }
 }
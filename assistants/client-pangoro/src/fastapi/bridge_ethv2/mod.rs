@@ -44,20 +44,17 @@ impl EcdsaClient for PangoroClient {
                 array_bytes::bytes2hex("0x", e.as_slice())
             ))
         })?;
-        let runtime = self.runtime();
-        let track = runtime
-            .tx()
+        let call = crate::subxt_runtime::api::tx()
             .ecdsa_authority()
             .submit_authorities_change_signature(
                 runtime_types::primitive_types::H160(address),
                 runtime_types::sp_core::ecdsa::Signature(fixed_signatures),
-            )
-            .sign_and_submit_then_watch(self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+            );
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_finalized().await.map_err(|e| {
             ClientError::Custom(format!("send transaction failed pangoro: {:?}", e))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
     }
 
     async fn submit_new_message_root_signature(
@@ -71,19 +68,16 @@ impl EcdsaClient for PangoroClient {
                 array_bytes::bytes2hex("0x", e.as_slice())
             ))
         })?;
-        let runtime = self.runtime();
-        let track = runtime
-            .tx()
+        let call = crate::subxt_runtime::api::tx()
             .ecdsa_authority()
             .submit_new_message_root_signature(
                 runtime_types::primitive_types::H160(address),
                 runtime_types::sp_core::ecdsa::Signature(fixed_signatures),
-            )
-            .sign_and_submit_then_watch(self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+            );
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_finalized().await.map_err(|e| {
             ClientError::Custom(format!("send transaction failed pangoro: {:?}", e))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
     }
 }
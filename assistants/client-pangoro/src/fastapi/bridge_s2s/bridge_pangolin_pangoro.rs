@@ -4,8 +4,10 @@ use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay, S2SParaBridgeC
 use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
 use bridge_s2s_traits::types::bp_messages::Weight;
 use bridge_s2s_traits::types::{
-    bp_header_chain, bp_messages, bp_runtime::Chain, bridge_runtime_common,
+    bp_header_chain, bp_messages, bp_runtime::Chain, bridge_runtime_common, ExtrinsicLocation,
+    MessagesDeliveryLimits,
 };
+use client_common_traits::constant::optional_constant;
 use client_common_traits::ClientCommon;
 
 use support_toolkit::convert::SmartCodecMapper;
@@ -78,6 +80,38 @@ impl S2SClientRelay for PangoroClient {
         Ok(total_weight)
     }
 
+    async fn messages_delivery_limits(&self) -> S2SClientResult<MessagesDeliveryLimits> {
+        let address = crate::subxt_runtime::api::constants()
+            .system()
+            .block_length();
+        let block_length = self.subxt().constants().at(&address)?;
+        let address = crate::subxt_runtime::api::constants()
+            .system()
+            .block_weights();
+        let block_weights = self.subxt().constants().at(&address)?;
+        // the limits of inbound lane are only known if the messages pallet exposes them
+        let max_unconfirmed_messages = optional_constant(
+            self.subxt(),
+            "BridgePangolinMessages",
+            "MaxUnconfirmedMessagesAtInboundLane",
+        )?;
+        let max_unrewarded_relayer_entries = optional_constant(
+            self.subxt(),
+            "BridgePangolinMessages",
+            "MaxUnrewardedRelayerEntriesAtInboundLane",
+        )?;
+        Ok(MessagesDeliveryLimits {
+            max_unconfirmed_messages,
+            max_unrewarded_relayer_entries,
+            max_extrinsic_size: Some(block_length.max.normal),
+            max_extrinsic_weight: block_weights
+                .per_class
+                .normal
+                .max_extrinsic
+                .map(|weight| weight.ref_time),
+        })
+    }
+
     async fn best_target_finalized(
         &self,
         at_block: Option<<Self::Chain as Chain>::Hash>,
@@ -96,6 +130,19 @@ impl S2SClientRelay for PangoroClient {
         }
     }
 
+    async fn current_authority_set(
+        &self,
+        at_block: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<bp_header_chain::AuthoritySet>> {
+        let address = crate::subxt_runtime::api::storage()
+            .bridge_rococo_grandpa()
+            .current_authority_set();
+        match self.subxt().storage().fetch(&address, at_block).await? {
+            Some(v) => Ok(Some(SmartCodecMapper::map_to(&v)?)),
+            None => Ok(None),
+        }
+    }
+
     async fn initialize(
         &self,
         initialization_data: <Self as S2SClientGeneric>::InitializationData,
@@ -103,19 +150,15 @@ impl S2SClientRelay for PangoroClient {
         let call = crate::subxt_runtime::api::tx()
             .bridge_rococo_grandpa()
             .initialize(initialization_data);
-        let track = self
-            .subxt()
-            .tx()
-            .sign_and_submit_then_watch_default(&call, self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_finalized().await.map_err(|e| {
             S2SClientError::RPC(format!(
                 "send transaction failed {}: {:?}",
                 <Self as ClientCommon>::CHAIN,
                 e
             ))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
     }
 
     async fn submit_finality_proof(
@@ -131,20 +174,15 @@ impl S2SClientRelay for PangoroClient {
         let call = crate::subxt_runtime::api::tx()
             .bridge_rococo_grandpa()
             .submit_finality_proof(expected_target, expected_justification);
-        let track = self
-            .subxt()
-            .tx()
-            .sign_and_submit_then_watch_default(&call, self.account().signer())
-            .await?;
-
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
             S2SClientError::RPC(format!(
                 "send transaction failed {}: {:?}",
                 <Self as ClientCommon>::CHAIN,
                 e
             ))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
     }
 
     async fn outbound_lanes(
@@ -204,6 +242,7 @@ impl S2SClientRelay for PangoroClient {
         >,
         messages_count: u32,
         dispatch_weight: Weight,
+        messages_fee: u128,
     ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
         let relayer_id_at_bridged_chain = SmartCodecMapper::map_to(&relayer_id_at_bridged_chain)?;
         let expected_proof = SmartCodecMapper::map_to(&proof)?;
@@ -216,19 +255,45 @@ impl S2SClientRelay for PangoroClient {
                 messages_count,
                 expected_dispatch_weight,
             );
-        let track = self
-            .subxt()
-            .tx()
-            .sign_and_submit_then_watch_default(&call, self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+        let mut submitted = self.submit_with_fee(&call, messages_fee).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
             S2SClientError::RPC(format!(
                 "send transaction failed {}: {:?}",
                 <Self as ClientCommon>::CHAIN,
                 e,
             ))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
+    }
+
+    async fn find_extrinsic(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<ExtrinsicLocation<<Self::Chain as Chain>::Hash>> {
+        Ok(self.submitter().find_extrinsic(hash).await?)
+    }
+
+    async fn estimate_receive_messages_proof_fee(
+        &self,
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+    ) -> S2SClientResult<Option<u128>> {
+        let relayer_id_at_bridged_chain = SmartCodecMapper::map_to(&relayer_id_at_bridged_chain)?;
+        let expected_proof = SmartCodecMapper::map_to(&proof)?;
+        let expected_dispatch_weight = SmartCodecMapper::map_to(&dispatch_weight)?;
+        let call = crate::subxt_runtime::api::tx()
+            .bridge_pangolin_messages()
+            .receive_messages_proof(
+                relayer_id_at_bridged_chain,
+                expected_proof,
+                messages_count,
+                expected_dispatch_weight,
+            );
+        Ok(self.estimate_fee(&call).await?)
     }
 
     async fn receive_messages_delivery_proof(
@@ -243,19 +308,98 @@ impl S2SClientRelay for PangoroClient {
         let call = crate::subxt_runtime::api::tx()
             .bridge_pangolin_messages()
             .receive_messages_delivery_proof(expected_proof, expected_relayers_state);
-        let track = self
-            .subxt()
-            .tx()
-            .sign_and_submit_then_watch_default(&call, self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
+            S2SClientError::RPC(format!(
+                "send transaction failed {}: {:?}",
+                <Self as ClientCommon>::CHAIN,
+                e,
+            ))
+        })?;
+        Ok(hash)
+    }
+
+    async fn receive_messages_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+        messages_fee: u128,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let (finality_target, justification) = finality;
+        let finality_call = crate::subxt_runtime::api::tx()
+            .bridge_rococo_grandpa()
+            .submit_finality_proof(
+                SmartCodecMapper::map_to(&finality_target)?,
+                SmartCodecMapper::map_to(&justification)?,
+            );
+        let messages_call = crate::subxt_runtime::api::tx()
+            .bridge_pangolin_messages()
+            .receive_messages_proof(
+                SmartCodecMapper::map_to(&relayer_id_at_bridged_chain)?,
+                SmartCodecMapper::map_to(&proof)?,
+                messages_count,
+                SmartCodecMapper::map_to(&dispatch_weight)?,
+            );
+        let call = crate::subxt_runtime::api::tx().utility().batch_all(vec![
+            self.runtime_call(&finality_call)?,
+            self.runtime_call(&messages_call)?,
+        ]);
+        let mut submitted = self.submit_with_fee(&call, messages_fee).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
             S2SClientError::RPC(format!(
                 "send transaction failed {}: {:?}",
                 <Self as ClientCommon>::CHAIN,
                 e,
             ))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
+    }
+
+    async fn receive_messages_delivery_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        proof: bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        relayers_state: bp_messages::UnrewardedRelayersState,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash> {
+        let (finality_target, justification) = finality;
+        let finality_call = crate::subxt_runtime::api::tx()
+            .bridge_rococo_grandpa()
+            .submit_finality_proof(
+                SmartCodecMapper::map_to(&finality_target)?,
+                SmartCodecMapper::map_to(&justification)?,
+            );
+        let messages_call = crate::subxt_runtime::api::tx()
+            .bridge_pangolin_messages()
+            .receive_messages_delivery_proof(
+                SmartCodecMapper::map_to(&proof)?,
+                SmartCodecMapper::map_to(&relayers_state)?,
+            );
+        let call = crate::subxt_runtime::api::tx().utility().batch_all(vec![
+            self.runtime_call(&finality_call)?,
+            self.runtime_call(&messages_call)?,
+        ]);
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
+            S2SClientError::RPC(format!(
+                "send transaction failed {}: {:?}",
+                <Self as ClientCommon>::CHAIN,
+                e,
+            ))
+        })?;
+        Ok(hash)
     }
 }
 
@@ -300,18 +444,14 @@ impl S2SParaBridgeClientSolochain for PangoroClient {
                 parachain_heads_proof,
             ),
         );
-        let track = self
-            .subxt()
-            .tx()
-            .sign_and_submit_then_watch_default(&call, self.account().signer())
-            .await?;
-        let events = track.wait_for_finalized_success().await.map_err(|e| {
+        let mut submitted = self.submit(&call).await?;
+        let hash = submitted.wait_for_in_block().await.map_err(|e| {
             S2SClientError::RPC(format!(
                 "send transaction failed {}: {:?}",
                 <Self as ClientCommon>::CHAIN,
                 e
             ))
         })?;
-        Ok(events.extrinsic_hash())
+        Ok(hash)
     }
 }
@@ -6,9 +6,10 @@ use client_pangoro::error::ClientResult;
 
 pub async fn client() -> ClientResult<PangoroClient> {
     let config = ClientConfig {
-        endpoint: "wss://pangoro-rpc.darwinia.network".to_string(),
+        endpoint: "wss://pangoro-rpc.darwinia.network".into(),
         relayer_private_key: "//Alice".to_string(),
         relayer_real_account: None,
+        transaction: Default::default(),
     };
     PangoroClientComponent::component(config).await
 }
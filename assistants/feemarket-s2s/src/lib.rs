@@ -1,2 +1,4 @@
 pub mod error;
+pub mod price;
+pub mod quote;
 pub mod relay;
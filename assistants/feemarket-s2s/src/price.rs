@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use component_subscan::{Subscan, SubscanComponent, SubscanConfig};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FeemarketError, FeemarketResult};
+
+/// Token price, all price sources used together must be quoted in the same currency
+#[async_trait::async_trait]
+pub trait PriceSource: 'static + Send + Sync {
+    /// current price of token
+    async fn price(&self) -> FeemarketResult<f64>;
+}
+
+/// Fixed price, useful for testnet tokens which have no market price
+#[derive(Clone, Debug)]
+pub struct FixedPriceSource {
+    price: f64,
+}
+
+impl FixedPriceSource {
+    pub fn new(price: f64) -> Self {
+        Self { price }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for FixedPriceSource {
+    async fn price(&self) -> FeemarketResult<f64> {
+        Ok(self.price)
+    }
+}
+
+/// Query token price (USD) from subscan
+#[derive(Clone, Debug)]
+pub struct SubscanPriceSource {
+    subscan: Subscan,
+}
+
+impl SubscanPriceSource {
+    pub fn new(subscan: Subscan) -> Self {
+        Self { subscan }
+    }
+}
+
+#[async_trait::async_trait]
+impl PriceSource for SubscanPriceSource {
+    async fn price(&self) -> FeemarketResult<f64> {
+        let subscan = self.subscan.clone();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))?
+            .as_secs();
+        // the subscan client is blocking, don't run it in async context
+        let response =
+            tokio::task::spawn_blocking(move || futures::executor::block_on(subscan.price(now)))
+                .await
+                .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))??;
+        let open_price = response.data()?.ok_or_else(|| {
+            FeemarketError::Custom("The price data from subscan is empty".to_string())
+        })?;
+        Ok(open_price.price)
+    }
+}
+
+/// Price source config
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum PriceSourceConfig {
+    /// Fixed price
+    Fixed { price: f64 },
+    /// Query price from subscan
+    Subscan(SubscanConfig),
+}
+
+impl PriceSourceConfig {
+    pub fn price_source(&self) -> FeemarketResult<Arc<dyn PriceSource>> {
+        match self {
+            Self::Fixed { price } => Ok(Arc::new(FixedPriceSource::new(*price))),
+            Self::Subscan(config) => {
+                let subscan = SubscanComponent::component(config.clone())?;
+                Ok(Arc::new(SubscanPriceSource::new(subscan)))
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use feemarket_s2s_traits::api::FeemarketApiQuote;
+use feemarket_s2s_traits::types::Chain;
+use serde::{Deserialize, Serialize};
+use support_toolkit::{convert::SmartCodecMapper, logk};
+
+use crate::error::{FeemarketError, FeemarketResult};
+
+/// Quote policy of a fee market, all amounts are in the smallest unit of chain token
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct QuotePolicy {
+    /// Quote `step` units below the cheapest assigned relayer
+    #[serde(default)]
+    pub step: u128,
+    /// Never quote lower than this fee
+    pub floor: u128,
+    /// Never quote higher than this fee, also the fee when no other assigned relayers
+    pub ceiling: u128,
+    /// Top up collateral when the locked collateral is lower than this amount
+    #[serde(default)]
+    pub collateral_threshold: Option<u128>,
+    /// The locked collateral after top up, default is the threshold
+    #[serde(default)]
+    pub collateral_target: Option<u128>,
+}
+
+impl QuotePolicy {
+    /// the expected fee by quotes of other assigned relayers
+    pub fn expected_fee(&self, others: &[u128]) -> u128 {
+        let fee = match others.iter().min() {
+            Some(cheapest) => cheapest.saturating_sub(self.step),
+            None => self.ceiling,
+        };
+        fee.clamp(self.floor, std::cmp::max(self.floor, self.ceiling))
+    }
+
+    /// the collateral should be locked, return none if the collateral is enough
+    pub fn expected_collateral(&self, collateral: u128) -> Option<u128> {
+        let threshold = self.collateral_threshold?;
+        if collateral >= threshold {
+            return None;
+        }
+        let target = self
+            .collateral_target
+            .map(|target| std::cmp::max(target, threshold))
+            .unwrap_or(threshold);
+        Some(target)
+    }
+}
+
+/// Manage relay fee and collateral of relayer by quote policy
+pub struct QuoteRunner<A: FeemarketApiQuote> {
+    api: A,
+    account: <A::Chain as Chain>::AccountId,
+    policy: QuotePolicy,
+}
+
+impl<A: FeemarketApiQuote> QuoteRunner<A> {
+    pub fn new(api: A, account: <A::Chain as Chain>::AccountId, policy: QuotePolicy) -> Self {
+        Self {
+            api,
+            account,
+            policy,
+        }
+    }
+}
+
+impl<A: FeemarketApiQuote> QuoteRunner<A> {
+    /// check the relayer once, update relay fee and collateral if needed
+    pub async fn run(&self) -> FeemarketResult<()> {
+        let relayer = self
+            .api
+            .relayer(self.account.clone())
+            .await
+            .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))?;
+        let relayer = match relayer {
+            Some(v) => v,
+            None => {
+                tracing::warn!(
+                    target: "feemarket",
+                    "{} you are not a relayer, please enroll first",
+                    logk::prefix_with_relation("feemarket", "quote", A::CHAIN, "::"),
+                );
+                return Ok(());
+            }
+        };
+        let current_fee: u128 = SmartCodecMapper::map_to(&relayer.fee)
+            .map_err(|e| FeemarketError::WrongConvert(format!("{:?}", e)))?;
+        let current_collateral: u128 = SmartCodecMapper::map_to(&relayer.collateral)
+            .map_err(|e| FeemarketError::WrongConvert(format!("{:?}", e)))?;
+
+        // collateral first, the relayer with not enough collateral can't be assigned
+        if let Some(collateral) = self.policy.expected_collateral(current_collateral) {
+            tracing::info!(
+                target: "feemarket",
+                "{} the locked collateral {} is lower than threshold, top up to {}",
+                logk::prefix_with_relation("feemarket", "quote", A::CHAIN, "::"),
+                current_collateral,
+                collateral,
+            );
+            let amount = SmartCodecMapper::map_to(&collateral)
+                .map_err(|e| FeemarketError::WrongConvert(format!("{:?}", e)))?;
+            self.api
+                .update_locked_collateral(amount)
+                .await
+                .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))?;
+        }
+
+        let assigned_relayers = self
+            .api
+            .assigned_relayers()
+            .await
+            .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))?;
+        let mut others = Vec::with_capacity(assigned_relayers.len());
+        for item in assigned_relayers {
+            if item.id == self.account {
+                continue;
+            }
+            let fee: u128 = SmartCodecMapper::map_to(&item.fee)
+                .map_err(|e| FeemarketError::WrongConvert(format!("{:?}", e)))?;
+            others.push(fee);
+        }
+        let expected_fee = self.policy.expected_fee(&others);
+        if expected_fee == current_fee {
+            tracing::debug!(
+                target: "feemarket",
+                "{} the relay fee {} is expected, nothing to do",
+                logk::prefix_with_relation("feemarket", "quote", A::CHAIN, "::"),
+                current_fee,
+            );
+            return Ok(());
+        }
+        tracing::info!(
+            target: "feemarket",
+            "{} update relay fee from {} to {}",
+            logk::prefix_with_relation("feemarket", "quote", A::CHAIN, "::"),
+            current_fee,
+            expected_fee,
+        );
+        let amount = SmartCodecMapper::map_to(&expected_fee)
+            .map_err(|e| FeemarketError::WrongConvert(format!("{:?}", e)))?;
+        self.api
+            .update_relay_fee(amount)
+            .await
+            .map_err(|e| FeemarketError::Custom(format!("{:?}", e)))?;
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
 pub mod basic;
+pub mod profitable;
+pub mod strategy;
@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
+use bridge_s2s_traits::strategy::{RelayReference, RelayStrategy};
+use serde::{Deserialize, Serialize};
+
+use feemarket_s2s_traits::api::FeemarketApiRelay;
+use feemarket_s2s_traits::types::Chain;
+use support_toolkit::{convert::SmartCodecMapper, logk};
+
+use crate::error::FeemarketResult;
+use crate::price::{PriceSource, PriceSourceConfig};
+use crate::relay::basic::BasicRelayStrategy;
+
+/// Token economy of a chain
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChainEconomyConfig {
+    /// Decimals of chain token
+    pub decimals: u32,
+    /// Price source of chain token
+    pub price: PriceSourceConfig,
+    /// Base fee of `receive_messages_proof` extrinsic on this chain
+    #[serde(default)]
+    pub base_fee: u128,
+    /// Fee per byte of messages proof
+    #[serde(default)]
+    pub byte_fee: u128,
+    /// Fee per unit of dispatch weight
+    #[serde(default)]
+    pub weight_fee: u128,
+    /// Minimum profit for relaying a message sent from this chain, in the smallest unit of chain token
+    #[serde(default)]
+    pub margin: u128,
+}
+
+/// Token economy of a chain, with price source created
+#[derive(Clone)]
+pub struct ChainEconomy {
+    config: ChainEconomyConfig,
+    price: Arc<dyn PriceSource>,
+}
+
+impl ChainEconomy {
+    pub fn new(config: ChainEconomyConfig) -> FeemarketResult<Self> {
+        let price = config.price.price_source()?;
+        Ok(Self { config, price })
+    }
+
+    pub fn with_price_source(config: ChainEconomyConfig, price: Arc<dyn PriceSource>) -> Self {
+        Self { config, price }
+    }
+
+    /// estimate fee of `receive_messages_proof`
+    pub fn estimate_fee(&self, message_size: usize, total_weight: u64) -> u128 {
+        let config = &self.config;
+        config
+            .base_fee
+            .saturating_add(config.byte_fee.saturating_mul(message_size as u128))
+            .saturating_add(config.weight_fee.saturating_mul(total_weight as u128))
+    }
+}
+
+/// Profitable relay strategy
+/// 1. only relay the nonce which the basic relay strategy decide to relay
+/// 2. if you are assigned relayer and the order is in your slot, relay it to avoid slashing
+/// 3. otherwise, estimate the fee of `receive_messages_proof` on target chain, and convert it to source chain token,
+///    only relay when the order fee minus the fee is not less than the margin. The fee estimated by pre-flight
+///    check is used if present, otherwise it's calculated by the economy config of target chain.
+pub struct ProfitableRelayStrategy<A: FeemarketApiRelay> {
+    basic: BasicRelayStrategy<A>,
+    api: A,
+    account: <A::Chain as Chain>::AccountId,
+    source: ChainEconomy,
+    target: ChainEconomy,
+}
+
+impl<A: FeemarketApiRelay> ProfitableRelayStrategy<A> {
+    /// the source is chain which sent messages (paid order fee), the target is chain which received messages.
+    pub fn new(
+        api: A,
+        account: <A::Chain as Chain>::AccountId,
+        source: ChainEconomy,
+        target: ChainEconomy,
+    ) -> Self {
+        Self {
+            basic: BasicRelayStrategy::new(api.clone(), account.clone()),
+            api,
+            account,
+            source,
+            target,
+        }
+    }
+}
+
+impl<A: FeemarketApiRelay> Clone for ProfitableRelayStrategy<A> {
+    fn clone(&self) -> Self {
+        Self {
+            basic: self.basic.clone(),
+            api: self.api.clone(),
+            account: self.account.clone(),
+            source: self.source.clone(),
+            target: self.target.clone(),
+        }
+    }
+}
+
+impl<A: FeemarketApiRelay> ProfitableRelayStrategy<A> {
+    /// convert amount of target chain token to source chain token
+    async fn target_to_source(&self, amount: u128) -> S2SClientResult<u128> {
+        let source_price = self
+            .source
+            .price
+            .price()
+            .await
+            .map_err(|e| S2SClientError::Custom(format!("[feemarket]: {:?}", e)))?;
+        let target_price = self
+            .target
+            .price
+            .price()
+            .await
+            .map_err(|e| S2SClientError::Custom(format!("[feemarket]: {:?}", e)))?;
+        if source_price <= 0f64 {
+            return Err(S2SClientError::Custom(format!(
+                "[feemarket]: wrong price of {} token: {}",
+                A::CHAIN,
+                source_price,
+            )));
+        }
+        let value = amount as f64 / 10f64.powi(self.target.config.decimals as i32) * target_price;
+        let converted = value / source_price * 10f64.powi(self.source.config.decimals as i32);
+        Ok(converted.ceil() as u128)
+    }
+}
+
+#[async_trait::async_trait]
+impl<A: FeemarketApiRelay> RelayStrategy for ProfitableRelayStrategy<A> {
+    async fn decide(&mut self, reference: RelayReference) -> S2SClientResult<bool> {
+        let lane = reference.lane;
+        let nonce = reference.nonce;
+        let basic_reference = RelayReference {
+            lane,
+            nonce,
+            messages_count: reference.messages_count,
+            message_size: reference.message_size,
+            total_weight: reference.total_weight,
+            estimated_fee: reference.estimated_fee,
+        };
+        if !self.basic.decide(basic_reference).await? {
+            return Ok(false);
+        }
+
+        let order = self
+            .api
+            .order(lane, nonce)
+            .await
+            .map_err(|e| S2SClientError::Custom(format!("[feemarket]: {:?}", e)))?;
+        let order = match order {
+            Some(v) => v,
+            None => {
+                tracing::info!(
+                    target: "feemarket",
+                    "{} not found order by nonce: {}, so decide don't relay this nonce",
+                    logk::prefix_with_relation("feemarket", "relay", A::CHAIN, "::"),
+                    nonce,
+                );
+                return Ok(false);
+            }
+        };
+        let relayers = order.assigned_relayers;
+
+        // If you are assigned relayer, you must relay this nonce in your slot, otherwise you will be slashed.
+        if let Some(relayer) = relayers.iter().find(|item| item.id == self.account) {
+            let finalized_block_number = self
+                .api
+                .finalized_header_number()
+                .await
+                .map_err(|e| S2SClientError::Custom(format!("[feemarket]: {:?}", e)))?;
+            if relayer.valid_range.contains(&finalized_block_number) {
+                tracing::info!(
+                    target: "feemarket",
+                    "{} you are assigned relayer and this order is in your slot, you must be relay this nonce({})",
+                    logk::prefix_with_relation("feemarket", "relay", A::CHAIN, "::"),
+                    nonce,
+                );
+                return Ok(true);
+            }
+        }
+
+        // The order fee is the quote of the last assigned relayer
+        let order_fee: u128 = match relayers.last() {
+            Some(relayer) => SmartCodecMapper::map_to(&relayer.fee)?,
+            None => 0,
+        };
+        // the fee queried from target chain is preferred, the configured fee is the fallback
+        let messages_count = std::cmp::max(reference.messages_count, 1) as u128;
+        let estimated_fee = reference.estimated_fee.unwrap_or_else(|| {
+            self.target
+                .estimate_fee(reference.message_size, reference.total_weight)
+        }) / messages_count;
+        let cost = self.target_to_source(estimated_fee).await?;
+        let profit = order_fee.saturating_sub(cost);
+        let margin = self.source.config.margin;
+        if order_fee < cost || profit < margin {
+            tracing::info!(
+                target: "feemarket",
+                "{} the order fee of nonce({}) is {}, the estimated cost is {} (margin {}), it's unprofitable, don't relay this",
+                logk::prefix_with_relation("feemarket", "relay", A::CHAIN, "::"),
+                nonce,
+                order_fee,
+                cost,
+                margin,
+            );
+            return Ok(false);
+        }
+        tracing::info!(
+            target: "feemarket",
+            "{} the order fee of nonce({}) is {}, the estimated cost is {}, relay this nonce",
+            logk::prefix_with_relation("feemarket", "relay", A::CHAIN, "::"),
+            nonce,
+            order_fee,
+            cost,
+        );
+        Ok(true)
+    }
+}
@@ -0,0 +1,44 @@
+use bridge_s2s_traits::error::S2SClientResult;
+use bridge_s2s_traits::strategy::{RelayReference, RelayStrategy};
+
+use feemarket_s2s_traits::api::FeemarketApiRelay;
+
+use crate::relay::basic::BasicRelayStrategy;
+use crate::relay::profitable::ProfitableRelayStrategy;
+
+/// Feemarket relay strategy, choose by config
+pub enum FeemarketRelayStrategy<A: FeemarketApiRelay> {
+    Basic(BasicRelayStrategy<A>),
+    Profitable(ProfitableRelayStrategy<A>),
+}
+
+impl<A: FeemarketApiRelay> Clone for FeemarketRelayStrategy<A> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Basic(strategy) => Self::Basic(strategy.clone()),
+            Self::Profitable(strategy) => Self::Profitable(strategy.clone()),
+        }
+    }
+}
+
+impl<A: FeemarketApiRelay> From<BasicRelayStrategy<A>> for FeemarketRelayStrategy<A> {
+    fn from(strategy: BasicRelayStrategy<A>) -> Self {
+        Self::Basic(strategy)
+    }
+}
+
+impl<A: FeemarketApiRelay> From<ProfitableRelayStrategy<A>> for FeemarketRelayStrategy<A> {
+    fn from(strategy: ProfitableRelayStrategy<A>) -> Self {
+        Self::Profitable(strategy)
+    }
+}
+
+#[async_trait::async_trait]
+impl<A: FeemarketApiRelay> RelayStrategy for FeemarketRelayStrategy<A> {
+    async fn decide(&mut self, reference: RelayReference) -> S2SClientResult<bool> {
+        match self {
+            Self::Basic(strategy) => strategy.decide(reference).await,
+            Self::Profitable(strategy) => strategy.decide(reference).await,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use bridge_s2s_traits::strategy::{RelayReference, RelayStrategy};
+use client_mock::client::MockClient;
+use client_mock::types::{MockChainInfo, MockSourceChain};
+use feemarket_s2s_traits::types::{Chain, Order};
+use support_toolkit::convert::SmartCodecMapper;
+
+use feemarket_s2s::price::{FixedPriceSource, PriceSourceConfig};
+use feemarket_s2s::relay::profitable::{ChainEconomy, ChainEconomyConfig, ProfitableRelayStrategy};
+
+type MockChain = <MockSourceChain as MockChainInfo>::Chain;
+type AccountId = <MockChain as Chain>::AccountId;
+type MockOrder = Order<AccountId, <MockChain as Chain>::BlockNumber, <MockChain as Chain>::Balance>;
+
+const LANE: [u8; 4] = [0, 0, 0, 0];
+
+fn account(seed: u8) -> AccountId {
+    SmartCodecMapper::map_to(&[seed; 32]).unwrap()
+}
+
+fn order(
+    nonce: u64,
+    relayer: AccountId,
+    fee: u128,
+    valid_range: std::ops::Range<u32>,
+) -> MockOrder {
+    let raw = (
+        LANE,
+        nonce,
+        0u32,
+        None::<u32>,
+        0u128,
+        vec![(relayer, fee, valid_range)],
+    );
+    SmartCodecMapper::map_to(&raw).unwrap()
+}
+
+fn economy(price: f64, base_fee: u128, byte_fee: u128, margin: u128) -> ChainEconomy {
+    let config = ChainEconomyConfig {
+        decimals: 18,
+        price: PriceSourceConfig::Fixed { price },
+        base_fee,
+        byte_fee,
+        weight_fee: 0,
+        margin,
+    };
+    ChainEconomy::with_price_source(config, Arc::new(FixedPriceSource::new(price)))
+}
+
+fn reference(nonce: u64) -> RelayReference {
+    RelayReference {
+        lane: LANE,
+        nonce,
+        messages_count: 1,
+        message_size: 100,
+        total_weight: 0,
+        estimated_fee: None,
+    }
+}
+
+#[tokio::test]
+async fn test_profitable_relay_strategy() {
+    let client = MockClient::<MockSourceChain>::new();
+    for _ in 0..3 {
+        client.produce_block();
+    }
+    let me = account(1);
+    let other = account(2);
+    // timed out orders, the estimated cost is (100 + 100) * 2 = 400 in source token
+    client.set_order(LANE, 1, order(1, other.clone(), 1_000, 0..2));
+    client.set_order(LANE, 2, order(2, other.clone(), 450, 0..2));
+    // on-time order assigned to others
+    client.set_order(LANE, 3, order(3, other, 1_000, 0..10));
+    // on-time order assigned to me
+    client.set_order(LANE, 4, order(4, me.clone(), 0, 0..10));
+
+    let mut strategy =
+        ProfitableRelayStrategy::new(client, me, economy(1.0, 0, 0, 100), economy(2.0, 100, 1, 0));
+    assert!(strategy.decide(reference(1)).await.unwrap());
+    assert!(!strategy.decide(reference(2)).await.unwrap());
+    assert!(!strategy.decide(reference(3)).await.unwrap());
+    assert!(strategy.decide(reference(4)).await.unwrap());
+    assert!(!strategy.decide(reference(5)).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_profitable_relay_strategy_with_estimated_fee() {
+    let client = MockClient::<MockSourceChain>::new();
+    for _ in 0..3 {
+        client.produce_block();
+    }
+    let me = account(1);
+    let other = account(2);
+    client.set_order(LANE, 1, order(1, other, 450, 0..2));
+
+    let mut strategy =
+        ProfitableRelayStrategy::new(client, me, economy(1.0, 0, 0, 100), economy(2.0, 100, 1, 0));
+    // the configured cost is 400, it's unprofitable
+    assert!(!strategy.decide(reference(1)).await.unwrap());
+    // the estimated fee queried from target chain is preferred, the cost is 100 * 2 = 200
+    let estimated = RelayReference {
+        estimated_fee: Some(100),
+        ..reference(1)
+    };
+    assert!(strategy.decide(estimated).await.unwrap());
+}
@@ -0,0 +1,77 @@
+use client_mock::client::MockClient;
+use client_mock::types::{MockCall, MockChainInfo, MockSourceChain};
+use feemarket_s2s_traits::api::FeemarketApiQuote;
+use feemarket_s2s_traits::types::{Chain, Relayer};
+use support_toolkit::convert::SmartCodecMapper;
+
+use feemarket_s2s::quote::{QuotePolicy, QuoteRunner};
+
+type MockChain = <MockSourceChain as MockChainInfo>::Chain;
+type AccountId = <MockChain as Chain>::AccountId;
+type MockRelayer = Relayer<AccountId, <MockChain as Chain>::Balance>;
+
+fn account(seed: u8) -> AccountId {
+    SmartCodecMapper::map_to(&[seed; 32]).unwrap()
+}
+
+fn relayer(id: AccountId, collateral: u128, fee: u128) -> MockRelayer {
+    SmartCodecMapper::map_to(&(id, collateral, fee)).unwrap()
+}
+
+fn policy() -> QuotePolicy {
+    QuotePolicy {
+        step: 10,
+        floor: 100,
+        ceiling: 1_000,
+        collateral_threshold: Some(500),
+        collateral_target: Some(800),
+    }
+}
+
+#[test]
+fn test_quote_policy() {
+    let policy = policy();
+    assert_eq!(policy.expected_fee(&[]), 1_000);
+    assert_eq!(policy.expected_fee(&[300, 200]), 190);
+    assert_eq!(policy.expected_fee(&[105]), 100);
+    assert_eq!(policy.expected_fee(&[5_000]), 1_000);
+    assert_eq!(policy.expected_collateral(500), None);
+    assert_eq!(policy.expected_collateral(499), Some(800));
+}
+
+#[tokio::test]
+async fn test_quote_runner() {
+    let client = MockClient::<MockSourceChain>::new();
+    let me = account(1);
+    client.set_account(me.clone());
+    client.enroll_relayer(relayer(account(2), 1_000, 300));
+    client.enroll_relayer(relayer(account(3), 1_000, 200));
+    client.enroll_relayer(relayer(me.clone(), 100, 500));
+
+    let runner = QuoteRunner::new(client.clone(), me.clone(), policy());
+    runner.run().await.unwrap();
+
+    let me = client.relayer(me).await.unwrap().unwrap();
+    let (fee, collateral): (u128, u128) = (
+        SmartCodecMapper::map_to(&me.fee).unwrap(),
+        SmartCodecMapper::map_to(&me.collateral).unwrap(),
+    );
+    assert_eq!(fee, 190);
+    assert_eq!(collateral, 800);
+    let calls: Vec<MockCall> = client
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .collect();
+    assert!(matches!(
+        calls.as_slice(),
+        [
+            MockCall::UpdateLockedCollateral { collateral: 800 },
+            MockCall::UpdateRelayFee { fee: 190 },
+        ]
+    ));
+
+    // the fee is expected, nothing to do
+    runner.run().await.unwrap();
+    assert_eq!(client.extrinsics().len(), 2);
+}
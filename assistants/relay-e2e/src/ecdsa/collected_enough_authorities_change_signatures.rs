@@ -136,6 +136,9 @@ impl<T: EcdsaClient> CollectedEnoughAuthoritiesChangeSignaturesRunner<T> {
             3,
         )
         .await?;
+        self.source
+            .report_gas_used("authorities-change", hash)
+            .await;
 
         Ok(Some(event.block_number))
     }
@@ -109,6 +109,7 @@ impl<T: EcdsaClient> CollectedEnoughNewMessageRootSignaturesRunner<T> {
             3,
         )
         .await?;
+        self.source.report_gas_used("message-root", hash).await;
         self.last_relay_time = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .map_err(|e| RelayError::Custom(format!("{}", e)))?
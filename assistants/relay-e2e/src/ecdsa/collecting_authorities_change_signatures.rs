@@ -60,6 +60,7 @@ impl<T: EcdsaClient> CollectingAuthoritiesChangeSignaturesRunner<T> {
         let hash = client_darwinia_substrate
             .submit_authorities_change_signature(address.0, signature)
             .await?;
+        support_metrics::metrics::ecdsa_signature_submitted("authorities-change");
         tracing::info!(
             target: "relay-e2e",
             "[Darwinia][ECDSA][collectingAuthorities] submitted signature: {}",
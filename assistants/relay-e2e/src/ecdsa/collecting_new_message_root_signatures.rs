@@ -64,6 +64,7 @@ impl<T: EcdsaClient> CollectingNewMessageRootSignaturesRunner<T> {
         let hash = client_darwinia_substrate
             .submit_new_message_root_signature(address.0, signature)
             .await?;
+        support_metrics::metrics::ecdsa_signature_submitted("message-root");
 
         tracing::info!(
             target: "relay-e2e",
@@ -1,5 +1,6 @@
 use client_contracts::PosaLightClient;
 use web3::transports::Http;
+use web3::types::H256;
 use web3::Web3;
 
 use crate::types::ethereum::FastEthereumAccount;
@@ -18,3 +19,25 @@ pub struct EcdsaSource<T: EcdsaClient> {
     pub ethereum_account: FastEthereumAccount,
     pub minimal_interval: u64,
 }
+
+impl<T: EcdsaClient> EcdsaSource<T> {
+    /// Report gas used by the confirmed transaction which imports collected signatures, the
+    /// relay is done already, so a failed receipt query is only logged
+    pub async fn report_gas_used(&self, operation: &str, hash: H256) {
+        match self.client_eth_web3.eth().transaction_receipt(hash).await {
+            Ok(receipt) => {
+                if let Some(gas_used) = receipt.and_then(|receipt| receipt.gas_used) {
+                    support_metrics::metrics::ecdsa_gas_used(operation, gas_used.as_u64());
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    target: "relay-e2e",
+                    "[Darwinia][ECDSA] failed to query receipt of {:?}: {:?}",
+                    hash,
+                    e,
+                );
+            }
+        }
+    }
+}
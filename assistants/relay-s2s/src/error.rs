@@ -15,6 +15,12 @@ pub enum RelayError {
     Client(#[from] S2SClientError),
     #[error(transparent)]
     Codec(#[from] codec::Error),
+    #[error("Keepstate: {0}")]
+    Keepstate(String),
+    #[error("Indexer: {0}")]
+    Indexer(String),
+    #[error("Justification: {0}")]
+    Justification(String),
     #[error("Custom: {0}")]
     Custom(String),
 }
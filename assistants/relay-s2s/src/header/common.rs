@@ -0,0 +1,182 @@
+use std::str::FromStr;
+
+use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay};
+use bridge_s2s_traits::types::bp_header_chain::justification::{self, GrandpaJustification};
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use finality_grandpa::voter_set::VoterSet;
+use sp_runtime::traits::Header;
+use support_toolkit::{convert::SmartCodecMapper, logk};
+
+use crate::error::{RelayError, RelayResult};
+use crate::indexer::Indexer;
+use crate::keepstate::RecentlyJustification;
+use crate::types::{JustificationSource, M_HEADER};
+
+/// Header of the bridged chain and the justification which finalizes it, in types of the chain
+/// which imports the header
+pub(crate) type Finality<C> = (
+    <C as Chain>::Header,
+    GrandpaJustification<<C as Chain>::Header>,
+);
+
+/// Find justification of the mandatory block, the sources are tried in order until one of them
+/// has the justification
+pub(crate) async fn find_mandatory_justification<C: S2SClientGeneric>(
+    client: &C,
+    indexer: &Indexer,
+    sources: &[JustificationSource],
+    block_hash: &str,
+) -> RelayResult<Vec<u8>> {
+    for source in sources {
+        let justification = match source {
+            JustificationSource::Indexer => indexer
+                .find_justification(block_hash, true)
+                .await
+                .map(|v| v.map(|mapping| mapping.justification)),
+            JustificationSource::Node => find_justification_from_node(client, block_hash).await,
+        };
+        match justification {
+            Ok(Some(justification)) => return Ok(justification),
+            Ok(None) => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} not found justification of block {} from {}",
+                    logk::prefix_multi(M_HEADER, vec![C::CHAIN]),
+                    block_hash,
+                    source,
+                );
+            }
+            Err(err) => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} failed to query justification of block {} from {}: {:?}",
+                    logk::prefix_multi(M_HEADER, vec![C::CHAIN]),
+                    block_hash,
+                    source,
+                    err,
+                );
+            }
+        }
+    }
+    Err(RelayError::Custom(format!(
+        "Failed to query justification for block hash: {block_hash:?}",
+    )))
+}
+
+async fn find_justification_from_node<C: S2SClientGeneric>(
+    client: &C,
+    block_hash: &str,
+) -> RelayResult<Option<Vec<u8>>> {
+    let hash = sp_core::H256::from_str(block_hash)
+        .map_err(|e| RelayError::Custom(format!("Wrong block hash [{block_hash}] {e:?}")))?;
+    let justification = client
+        .find_justification(SmartCodecMapper::map_to(&hash)?)
+        .await?;
+    Ok(justification.map(|v| v.0))
+}
+
+/// Verify the justification against the authority set stored in bridge grandpa pallet of
+/// target chain, a justification rejected here would fail `submit_finality_proof` as well.
+/// The verification checks signatures of precommits, ancestries of votes and whether the
+/// cumulative weight of votes reaches the threshold (2/3+1) of authority set.
+pub(crate) async fn verify_justification<TC: S2SClientRelay>(
+    client_target: &TC,
+    header: &<TC::Chain as Chain>::Header,
+    grandpa_justification: &GrandpaJustification<<TC::Chain as Chain>::Header>,
+) -> RelayResult<()> {
+    let block_number = header.number();
+    let block_hash = header.hash();
+    let authority_set = client_target
+        .current_authority_set(None)
+        .await?
+        .ok_or_else(|| {
+            RelayError::Justification(format!(
+                "Not found authority set in {}, the bridge may not be initialized",
+                TC::CHAIN,
+            ))
+        })?;
+    let voter_set = VoterSet::new(authority_set.authorities.iter().cloned()).ok_or_else(|| {
+        RelayError::Justification(format!(
+            "The authority set {} stored in {} is empty or has zero weight",
+            authority_set.set_id,
+            TC::CHAIN,
+        ))
+    })?;
+    justification::verify_justification::<<TC::Chain as Chain>::Header>(
+        (block_hash, *block_number),
+        authority_set.set_id,
+        &voter_set,
+        grandpa_justification,
+    )
+    .map_err(|e| {
+        RelayError::Justification(format!(
+            "The justification of block {:?} ({}) failed the {:?} check against authority set {} of {}",
+            block_number,
+            array_bytes::bytes2hex("0x", block_hash),
+            e,
+            authority_set.set_id,
+            TC::CHAIN,
+        ))
+    })
+}
+
+/// Prepare the header finalized by the justification to be submitted with other calls, it's
+/// `None` if the justification can't be accepted by target chain, e.g. there is a mandatory
+/// header before it which should be relayed by header runner first.
+pub(crate) async fn prepare_finality<SC: S2SClientGeneric, TC: S2SClientRelay>(
+    client_source: &SC,
+    client_target: &TC,
+    justification: &RecentlyJustification,
+) -> RelayResult<Option<Finality<TC::Chain>>> {
+    let block_hash = justification.block_hash.trim_start_matches("0x");
+    let block_hash = sp_core::H256::from_str(block_hash)
+        .map_err(|e| RelayError::Custom(format!("Wrong block hash [{block_hash}] {e:?}")))?;
+    let header = client_source
+        .header(Some(SmartCodecMapper::map_to(&block_hash)?))
+        .await?
+        .ok_or_else(|| RelayError::Custom(format!("Not found header by hash: {block_hash}")))?;
+    let expected_header = SmartCodecMapper::map_to(&header)?;
+    let grandpa_justification =
+        sp_runtime::codec::Decode::decode(&mut justification.justification.as_ref())?;
+    if let Err(e) =
+        verify_justification(client_target, &expected_header, &grandpa_justification).await
+    {
+        tracing::warn!(
+            target: "relay-s2s",
+            "{} can't bundle header {}: {:?}",
+            logk::prefix_with_bridge(M_HEADER, SC::CHAIN, TC::CHAIN),
+            justification.block_number,
+            e,
+        );
+        return Ok(None);
+    }
+    Ok(Some((expected_header, grandpa_justification)))
+}
+
+/// Whether the header or a later one has been imported by target chain, it happens when other
+/// relayers relay headers of the same bridge.
+pub(crate) async fn is_header_imported<TC: S2SClientRelay>(
+    client_target: &TC,
+    header: &<TC::Chain as Chain>::Header,
+) -> RelayResult<bool> {
+    let best_target_finalized = client_target.best_target_finalized(None).await?;
+    Ok(best_target_finalized
+        .map(|(number, _)| number >= *header.number())
+        .unwrap_or(false))
+}
+
+pub(crate) fn report_header_lost_race(
+    source: &str,
+    target: &str,
+    block_number: impl std::fmt::Debug,
+    stage: &str,
+) {
+    tracing::warn!(
+        target: "relay-s2s",
+        "{} lost race ({}), the header {:?} has been relayed by other relayers",
+        logk::prefix_with_bridge(M_HEADER, source, target),
+        stage,
+        block_number,
+    );
+    support_metrics::metrics::lost_race(target, "submit_finality_proof", stage);
+}
@@ -4,6 +4,7 @@ pub use self::para_head_relay::*;
 pub use self::relaychain_head_relay::*;
 pub use self::solochain_head_relay::*;
 
+pub(crate) mod common;
 #[cfg(feature = "bridge-parachain")]
 mod para_head_relay;
 #[cfg(feature = "bridge-parachain")]
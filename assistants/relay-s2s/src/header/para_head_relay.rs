@@ -1,10 +1,13 @@
 use bridge_s2s_traits::client::{S2SParaBridgeClientRelaychain, S2SParaBridgeClientSolochain};
+use bridge_s2s_traits::types::bp_runtime::Chain;
 use bridge_s2s_traits::types::ParaId;
+use sp_core::H256;
 use sp_runtime::traits::Header;
 
 use support_toolkit::{convert::SmartCodecMapper, logk};
 
 use crate::error::{RelayError, RelayResult};
+use crate::subscribe::FinalityWakeup;
 use crate::types::{ParaHeaderInput, M_PARA_HEAD};
 
 /// para head to solo chain header relay runner
@@ -20,9 +23,10 @@ impl<SC: S2SParaBridgeClientRelaychain, TC: S2SParaBridgeClientSolochain> ParaHe
 
 impl<SC: S2SParaBridgeClientRelaychain, TC: S2SParaBridgeClientSolochain> ParaHeaderRunner<SC, TC> {
     pub async fn start(&self) -> RelayResult<()> {
+        let mut wakeup = FinalityWakeup::new(&self.input.keepstate, vec![SC::CHAIN, TC::CHAIN]);
         loop {
             self.run().await?;
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            wakeup.wait().await;
         }
     }
 
@@ -41,21 +45,6 @@ impl<SC: S2SParaBridgeClientRelaychain, TC: S2SParaBridgeClientSolochain> ParaHe
             SC::CHAIN,
             best_target_header.number(),
         );
-        let para_head_at_target = client_solochain
-            .best_para_heads(ParaId(self.input.para_id), Some(best_target_header.hash()))
-            .await?;
-        tracing::trace!(
-            target: "relay-s2s",
-            "{} the last para-head on {}: {}",
-            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
-            SC::CHAIN,
-            if let Some(phat) = &para_head_at_target {
-                format!("{}@{}", phat.best_head_hash.at_relay_block_number, phat.best_head_hash.head_hash)
-            } else {
-                "".to_string()
-            }
-        );
-
         let best_finalized_source_block = match client_solochain
             .best_target_finalized(Some(best_target_header.hash()))
             .await?
@@ -88,85 +77,138 @@ impl<SC: S2SParaBridgeClientRelaychain, TC: S2SParaBridgeClientSolochain> ParaHe
             logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
             best_finalized_source_block_at_target_number,
         );
+
+        let mut stale_para_heads = Vec::with_capacity(self.input.para_ids.len());
+        for para_id in &self.input.para_ids {
+            if let Some(para_head_hash) = self
+                .stale_para_head(
+                    *para_id,
+                    best_target_header.hash(),
+                    expected_source_block_hash,
+                    best_finalized_source_block_at_target_number,
+                )
+                .await?
+            {
+                stale_para_heads.push((*para_id, para_head_hash));
+            }
+        }
+        if stale_para_heads.is_empty() {
+            return Ok(());
+        }
+
+        let heads_proofs = client_relaychain
+            .read_proof(
+                stale_para_heads
+                    .iter()
+                    .map(|(para_id, _)| client_relaychain.gen_parachain_head_storage_key(*para_id))
+                    .collect(),
+                Some(expected_source_block_hash),
+            )
+            .await?;
+        if self.input.dry_run {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} [dry-run] would submit submit_parachain_heads of parachains {:?} at relay block {:?} to {}, proof size: {} bytes",
+                logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+                stale_para_heads.iter().map(|(para_id, _)| para_id).collect::<Vec<_>>(),
+                best_finalized_source_block,
+                TC::CHAIN,
+                heads_proofs.iter().map(Vec::len).sum::<usize>(),
+            );
+            return Ok(());
+        }
+        tracing::info!(
+            target: "relay-s2s",
+            "{} submitting parachain heads update transaction of parachains {:?} to {}",
+            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+            stale_para_heads.iter().map(|(para_id, _)| para_id).collect::<Vec<_>>(),
+            SC::CHAIN,
+        );
+        let mut parachains = Vec::with_capacity(stale_para_heads.len());
+        for (para_id, para_head_hash) in stale_para_heads {
+            parachains.push((ParaId(para_id), SmartCodecMapper::map_to(&para_head_hash)?));
+        }
+
+        let result = client_solochain
+            .submit_parachain_heads(best_finalized_source_block, parachains, heads_proofs)
+            .await;
+        support_metrics::metrics::record_extrinsic(TC::CHAIN, "submit_parachain_heads", &result);
+        let hash = result?;
+        tracing::info!(
+            target: "relay-s2s",
+            "{} the tx hash {} emitted",
+            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+            array_bytes::bytes2hex("0x", hash),
+        );
+        Ok(())
+    }
+
+    /// Head hash of the parachain at source if the head stored in target is stale
+    async fn stale_para_head(
+        &self,
+        para_id: u32,
+        best_target_header_hash: <TC::Chain as Chain>::Hash,
+        expected_source_block_hash: <SC::Chain as Chain>::Hash,
+        best_finalized_source_block_at_target_number: u32,
+    ) -> RelayResult<Option<H256>> {
+        let client_solochain = &self.input.client_solochain;
+        let client_relaychain = &self.input.client_relaychain;
+
+        let para_head_at_target = client_solochain
+            .best_para_heads(ParaId(para_id), Some(best_target_header_hash))
+            .await?;
+        tracing::trace!(
+            target: "relay-s2s",
+            "{} the last para-head of {} on {}: {}",
+            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+            para_id,
+            SC::CHAIN,
+            if let Some(phat) = &para_head_at_target {
+                format!("{}@{}", phat.best_head_hash.at_relay_block_number, phat.best_head_hash.head_hash)
+            } else {
+                "".to_string()
+            }
+        );
         let para_head_at_source = client_relaychain
-            .para_head_data(ParaId(self.input.para_id), Some(expected_source_block_hash))
+            .para_head_data(ParaId(para_id), Some(expected_source_block_hash))
             .await?;
         tracing::trace!(
             target: "relay-s2s",
-            "{} the last para-head on relaychain {:?}",
+            "{} the last para-head of {} on relaychain {:?}",
             logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+            para_id,
             best_finalized_source_block_at_target_number,
         );
 
-        let mut para_head_hash = None;
-        let need_relay = match (para_head_at_source, para_head_at_target) {
+        let para_head_hash = match (para_head_at_source, para_head_at_target) {
             (Some(head_at_source), Some(head_at_target))
                 if head_at_target.best_head_hash.at_relay_block_number
                     < best_finalized_source_block_at_target_number
                     && head_at_target.best_head_hash.head_hash != head_at_source.hash() =>
             {
-                para_head_hash = Some(head_at_source.hash());
-                true
-            }
-            (Some(head_at_source), None) => {
-                para_head_hash = Some(head_at_source.hash());
-                true
-            }
-            (None, Some(head_at_target)) => {
-                para_head_hash = Some(head_at_target.best_head_hash.head_hash);
-                true
+                Some(head_at_source.hash())
             }
+            (Some(head_at_source), None) => Some(head_at_source.hash()),
+            (None, Some(head_at_target)) => Some(head_at_target.best_head_hash.head_hash),
             (None, None) => {
                 tracing::info!(
                     target: "relay-s2s",
-                    "{} parachain is unknown to both clients",
+                    "{} parachain {} is unknown to both clients",
                     logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+                    para_id,
                 );
-                false
+                None
             }
             (Some(_), Some(_)) => {
                 tracing::info!(
                     target: "relay-s2s",
-                    "{} not need to relay",
+                    "{} parachain {} not need to relay",
                     logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
+                    para_id,
                 );
-                false
+                None
             }
         };
-
-        if !need_relay {
-            return Ok(());
-        }
-
-        let heads_proofs = client_relaychain
-            .read_proof(
-                vec![client_relaychain.gen_parachain_head_storage_key(self.input.para_id)],
-                Some(expected_source_block_hash),
-            )
-            .await?;
-        tracing::info!(
-            target: "relay-s2s",
-            "{} submitting parachain heads update transaction to {}",
-            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
-            SC::CHAIN,
-        );
-
-        let hash = client_solochain
-            .submit_parachain_heads(
-                best_finalized_source_block,
-                vec![(
-                    ParaId(self.input.para_id),
-                    SmartCodecMapper::map_to(&para_head_hash.expect("Unreachable"))?,
-                )],
-                heads_proofs,
-            )
-            .await?;
-        tracing::info!(
-            target: "relay-s2s",
-            "{} the tx hash {} emitted",
-            logk::prefix_with_bridge(M_PARA_HEAD, SC::CHAIN, TC::CHAIN),
-            array_bytes::bytes2hex("0x", hash),
-        );
-        Ok(())
+        Ok(para_head_hash)
     }
 }
@@ -1,14 +1,16 @@
 use std::str::FromStr;
 
 use bridge_s2s_traits::client::S2SClientRelay;
-use bridge_s2s_traits::types::bp_header_chain;
-use bridge_s2s_traits::types::bp_runtime::Chain;
 use sp_runtime::codec;
 use sp_runtime::traits::Header;
 use subquery::types::NeedRelayBlock;
 use support_toolkit::{convert::SmartCodecMapper, logk};
 
 use crate::error::{RelayError, RelayResult};
+use crate::header::common::{
+    find_mandatory_justification, is_header_imported, report_header_lost_race, verify_justification,
+};
+use crate::subscribe::FinalityWakeup;
 use crate::types::{SolochainHeaderInput, M_HEADER};
 
 /// solo chain to solo chain header relay runner
@@ -25,9 +27,10 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
 impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
     /// start header relay
     pub async fn start(&self) -> RelayResult<()> {
+        let mut wakeup = FinalityWakeup::new(&self.input.keepstate, vec![SC::CHAIN, TC::CHAIN]);
         loop {
             self.run().await?;
-            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            wakeup.wait().await;
         }
     }
 
@@ -62,6 +65,7 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
 
         let block_number = last_relayed_source_block_in_target.block.header.number();
         let block_number: u32 = SmartCodecMapper::map_to(block_number)?;
+        support_metrics::metrics::header_relayed(SC::CHAIN, TC::CHAIN, block_number);
         tracing::trace!(
             target: "relay-s2s",
             "{} the last relayed {} block is: {:?}",
@@ -112,9 +116,41 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
         let grandpa_justification =
             sp_runtime::codec::Decode::decode(&mut justification.as_slice())?;
         let expected_header = SmartCodecMapper::map_to(&header)?;
-        let hash = client_target
-            .submit_finality_proof(expected_header, grandpa_justification)
-            .await?;
+        verify_justification(client_target, &expected_header, &grandpa_justification).await?;
+        if self.input.dry_run {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} [dry-run] would submit submit_finality_proof for header {:?}, encoded size: {} bytes",
+                logk::prefix_with_bridge(M_HEADER, SC::CHAIN, TC::CHAIN),
+                block_hash,
+                codec::Encode::encoded_size(&header) + justification.len(),
+            );
+            return Ok(());
+        }
+        // other relayers may relay the same header while the justification was prepared
+        if is_header_imported(client_target, &expected_header).await? {
+            report_header_lost_race(SC::CHAIN, TC::CHAIN, header.number(), "before-submit");
+            return Ok(());
+        }
+        let result = client_target
+            .submit_finality_proof(expected_header.clone(), grandpa_justification)
+            .await;
+        support_metrics::metrics::record_extrinsic(TC::CHAIN, "submit_finality_proof", &result);
+        let hash = match result {
+            Ok(v) => v,
+            Err(e) => {
+                if is_header_imported(client_target, &expected_header).await? {
+                    report_header_lost_race(
+                        SC::CHAIN,
+                        TC::CHAIN,
+                        header.number(),
+                        "after-inclusion",
+                    );
+                    return Ok(());
+                }
+                return Err(e.into());
+            }
+        };
         tracing::info!(
             target: "relay-s2s",
             "{} header relayed: {:?}",
@@ -131,17 +167,14 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
             logk::prefix_with_bridge(M_HEADER, SC::CHAIN, TC::CHAIN),
             &block_to_relay.block_number
         );
-        let subquery_source = &self.input.subquery_source;
-        let justification = subquery_source
-            .find_justification(block_to_relay.block_hash.clone(), true)
-            .await?
-            .ok_or_else(|| {
-                RelayError::Custom(format!(
-                    "Failed to query justification for block hash: {:?}",
-                    &block_to_relay.block_hash
-                ))
-            })?;
-        self.submit_finality(block_to_relay.block_hash, justification.justification)
+        let justification = find_mandatory_justification(
+            &self.input.client_source,
+            &self.input.subquery_source,
+            &self.input.justification_sources,
+            &block_to_relay.block_hash,
+        )
+        .await?;
+        self.submit_finality(block_to_relay.block_hash, justification)
             .await?;
 
         Ok(())
@@ -182,29 +215,24 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay> SolochainHeaderRunner<SC, TC> {
             next_header.block_number,
         );
 
-        match crate::keepstate::get_recently_justification(SC::CHAIN) {
+        // any justification since the on-demand block finalizes it
+        match self
+            .input
+            .keepstate
+            .find_justification_since(SC::CHAIN, next_header.block_number)?
+        {
             Some(justification) => {
                 tracing::trace!(
                     target: "relay-s2s",
-                    "{} found on-demand block {}, and found new justification, ready to relay header",
+                    "{} found on-demand block {}, and found justification at block {}, ready to relay header",
                     logk::prefix_with_bridge(M_HEADER, SC::CHAIN, TC::CHAIN),
                     next_header.block_number,
+                    justification.block_number,
                 );
-                let grandpa_justification: bp_header_chain::justification::GrandpaJustification<
-                    <SC::Chain as Chain>::Header,
-                > = codec::Decode::decode(&mut justification.as_ref()).map_err(|err| {
-                    RelayError::Custom(format!(
-                        "Failed to decode justification of {}: {:?}",
-                        SC::CHAIN,
-                        err,
-                    ))
-                })?;
-                let target_number: u32 =
-                    SmartCodecMapper::map_to(&grandpa_justification.commit.target_number)?;
-                if target_number > last_block_number {
+                if justification.block_number > last_block_number {
                     self.submit_finality(
-                        array_bytes::bytes2hex("", grandpa_justification.commit.target_hash),
-                        justification.to_vec(),
+                        justification.block_hash.trim_start_matches("0x"),
+                        justification.justification.to_vec(),
                     )
                     .await?;
                 }
@@ -0,0 +1,431 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::time::Duration;
+
+use bridge_s2s_traits::client::S2SClientRelay;
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use microkv::namespace::NamespaceMicroKV;
+use serde::{Deserialize, Serialize};
+use sp_finality_grandpa::{ConsensusLog, GRANDPA_ENGINE_ID};
+use sp_runtime::codec;
+use sp_runtime::traits::Header;
+use subquery::types::{JustificationMapping, NeedRelayBlock, OriginType, RelayBlockType};
+use support_toolkit::{convert::SmartCodecMapper, logk};
+use support_tracker::Tracker;
+
+use crate::error::{RelayError, RelayResult};
+use crate::types::{LaneId, M_INDEXER};
+
+/// Wait time before checking the finalized head again when all finalized blocks are scanned
+const SCAN_INTERVAL: Duration = Duration::from_secs(6);
+
+/// Indexer works with chain rpc only, it walks finalized headers and records the blocks need to
+/// relay to microkv.
+///
+/// - the header which schedules GRANDPA authorities change is mandatory, its justification is
+///   recorded as well
+/// - the header which accepts messages or receives messages of the lanes is on-demand
+#[derive(Clone)]
+pub struct ChainScanIndexer {
+    chain: String,
+    /// origin of recorded on-demand blocks
+    origin: OriginType,
+    microkv: NamespaceMicroKV,
+    tracker: Tracker,
+}
+
+impl Debug for ChainScanIndexer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChainScanIndexer")
+            .field("chain", &self.chain)
+            .field("origin", &self.origin)
+            .field("tracker", &self.tracker)
+            .finish()
+    }
+}
+
+/// Messages until the nonce are accepted at or before the block where the first scan started
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct ScanBaseline {
+    nonce: u64,
+    block: NeedRelayBlock,
+}
+
+/// Nonces of lane at a block, compare with its parent block to find message changes
+#[derive(Clone, Copy, Debug, Default)]
+struct LaneNonces {
+    latest_generated_nonce: u64,
+    last_delivered_nonce: u64,
+}
+
+impl ChainScanIndexer {
+    /// The microkv should be namespaced by bridge, the keys are prefixed by chain
+    pub fn new(microkv: NamespaceMicroKV, chain: impl AsRef<str>, origin: OriginType) -> Self {
+        let chain = chain.as_ref().to_string();
+        let tracker = Tracker::new(microkv.clone(), format!("chain-scan.{chain}.scanned"));
+        Self {
+            chain,
+            origin,
+            microkv,
+            tracker,
+        }
+    }
+}
+
+impl ChainScanIndexer {
+    /// Next mandatory header after the block
+    pub fn next_mandatory_header(&self, block_number: u32) -> RelayResult<Option<NeedRelayBlock>> {
+        Ok(self
+            .read_mandatory_blocks()?
+            .into_iter()
+            .find(|block| block.block_number > block_number))
+    }
+
+    /// The latest on-demand block
+    pub fn next_needed_header(&self) -> RelayResult<Option<NeedRelayBlock>> {
+        self.read(&self.key_on_demand())
+    }
+
+    /// Justification of mandatory header
+    pub fn find_justification(
+        &self,
+        block_hash: impl AsRef<str>,
+    ) -> RelayResult<Option<JustificationMapping>> {
+        self.read(&self.key_justification(block_hash.as_ref()))
+    }
+
+    /// The block which accepted the message, the messages before scan are treated as accepted
+    /// by the block where the first scan started
+    pub fn query_need_relay(
+        &self,
+        lane: LaneId,
+        nonce: u64,
+    ) -> RelayResult<Option<NeedRelayBlock>> {
+        if let Some(block) = self.read(&self.key_message(lane, nonce))? {
+            return Ok(Some(block));
+        }
+        let baseline: Option<ScanBaseline> = self.read(&self.key_baseline(lane))?;
+        Ok(baseline
+            .filter(|baseline| nonce <= baseline.nonce)
+            .map(|baseline| baseline.block))
+    }
+}
+
+impl ChainScanIndexer {
+    /// Scan finalized blocks forever. The first scan starts after the last block imported by
+    /// bridged chain, then it continues from the progress kept by tracker.
+    pub async fn start<C: S2SClientRelay, BC: S2SClientRelay>(
+        &self,
+        client: C,
+        client_bridged: BC,
+        lanes: Vec<LaneId>,
+    ) -> RelayResult<()> {
+        self.tracker.start_running().map_err(tracker_error)?;
+        if self.tracker.current().await.map_err(tracker_error)? == 0 {
+            let (from, _) = client_bridged
+                .best_target_finalized(None)
+                .await?
+                .ok_or_else(|| {
+                    RelayError::Indexer(format!(
+                        "The bridge of {} at {} not initialized",
+                        C::CHAIN,
+                        BC::CHAIN,
+                    ))
+                })?;
+            let from: u32 = SmartCodecMapper::map_to(&from)?;
+            tracing::info!(
+                target: "relay-s2s",
+                "{} start the first scan after block {}",
+                logk::prefix_multi(M_INDEXER, vec![C::CHAIN]),
+                from,
+            );
+            self.record_baseline(&client, &lanes, from).await?;
+            self.tracker.planned(from as usize).map_err(tracker_error)?;
+        }
+
+        let mut previous: Option<(u32, HashMap<LaneId, LaneNonces>)> = None;
+        loop {
+            let next = self.tracker.next().await.map_err(tracker_error)? as u32;
+            let finalized_hash = client.finalized_head().await?;
+            let finalized_header = client.header(Some(finalized_hash)).await?.ok_or_else(|| {
+                RelayError::Indexer(format!("Not found finalized header of {}", C::CHAIN))
+            })?;
+            let finalized_number: u32 = SmartCodecMapper::map_to(finalized_header.number())?;
+            if next > finalized_number {
+                tokio::time::sleep(SCAN_INTERVAL).await;
+                continue;
+            }
+            tracing::debug!(
+                target: "relay-s2s",
+                "{} scan blocks {}..={}",
+                logk::prefix_multi(M_INDEXER, vec![C::CHAIN]),
+                next,
+                finalized_number,
+            );
+            for number in next..=finalized_number {
+                let nonces = self
+                    .scan_block(&client, &lanes, number, previous.take())
+                    .await?;
+                previous = Some((number, nonces));
+                self.tracker
+                    .finish(number as usize)
+                    .map_err(tracker_error)?;
+            }
+        }
+    }
+
+    async fn scan_block<C: S2SClientRelay>(
+        &self,
+        client: &C,
+        lanes: &[LaneId],
+        number: u32,
+        previous: Option<(u32, HashMap<LaneId, LaneNonces>)>,
+    ) -> RelayResult<HashMap<LaneId, LaneNonces>> {
+        let header = self.header_by_number(client, number).await?;
+        let hash = header.hash();
+
+        if let Some(change) = find_authorities_change(&header) {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} found mandatory block {} which {}",
+                logk::prefix_multi(M_INDEXER, vec![C::CHAIN]),
+                number,
+                change,
+            );
+            self.record_mandatory(client, &header).await?;
+        }
+
+        let parent_nonces = match previous {
+            Some((previous_number, nonces)) if previous_number + 1 == number => nonces,
+            _ => lane_nonces(client, lanes, *header.parent_hash()).await?,
+        };
+        let nonces = lane_nonces(client, lanes, hash).await?;
+        let mut on_demand = None;
+        for lane in lanes {
+            let parent = parent_nonces.get(lane).copied().unwrap_or_default();
+            let current = nonces.get(lane).copied().unwrap_or_default();
+            if current.latest_generated_nonce > parent.latest_generated_nonce {
+                for nonce in (parent.latest_generated_nonce + 1)..=current.latest_generated_nonce {
+                    let block = self.on_demand_block(&header, Some((*lane, nonce)))?;
+                    self.write(&self.key_message(*lane, nonce), &block)?;
+                    on_demand = Some(block);
+                }
+            }
+            // the header is needed by bridged chain to confirm the delivered messages
+            if on_demand.is_none() && current.last_delivered_nonce > parent.last_delivered_nonce {
+                on_demand = Some(self.on_demand_block(&header, None)?);
+            }
+        }
+        if let Some(block) = on_demand {
+            tracing::debug!(
+                target: "relay-s2s",
+                "{} found on-demand block {}",
+                logk::prefix_multi(M_INDEXER, vec![C::CHAIN]),
+                number,
+            );
+            self.write(&self.key_on_demand(), &block)?;
+        }
+        Ok(nonces)
+    }
+
+    async fn header_by_number<C: S2SClientRelay>(
+        &self,
+        client: &C,
+        number: u32,
+    ) -> RelayResult<<C::Chain as Chain>::Header> {
+        let hash = client
+            .block_hash(SmartCodecMapper::map_to(&number)?)
+            .await?
+            .ok_or_else(|| {
+                RelayError::Indexer(format!("Not found block {} of {}", number, C::CHAIN))
+            })?;
+        client.header(Some(hash)).await?.ok_or_else(|| {
+            RelayError::Indexer(format!("Not found header {} of {}", number, C::CHAIN))
+        })
+    }
+
+    async fn record_baseline<C: S2SClientRelay>(
+        &self,
+        client: &C,
+        lanes: &[LaneId],
+        from: u32,
+    ) -> RelayResult<()> {
+        let header = self.header_by_number(client, from).await?;
+        let nonces = lane_nonces(client, lanes, header.hash()).await?;
+        for (lane, nonces) in nonces {
+            let baseline = ScanBaseline {
+                nonce: nonces.latest_generated_nonce,
+                block: self.on_demand_block(&header, None)?,
+            };
+            self.write(&self.key_baseline(lane), &baseline)?;
+        }
+        Ok(())
+    }
+
+    async fn record_mandatory<C: S2SClientRelay>(
+        &self,
+        client: &C,
+        header: &<C::Chain as Chain>::Header,
+    ) -> RelayResult<()> {
+        let block = need_relay_block(
+            header,
+            RelayBlockType::Mandatory,
+            OriginType::Mandatory,
+            None,
+        )?;
+        match client.find_justification(header.hash()).await? {
+            Some(justification) => {
+                let mapping = JustificationMapping {
+                    id: block.block_hash.clone(),
+                    block_number: block.block_number,
+                    block_hash: block.block_hash.clone(),
+                    mandatory: true,
+                    justification: justification.0,
+                };
+                self.write(&self.key_justification(&block.block_hash), &mapping)?;
+            }
+            None => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} not found justification of mandatory block {}",
+                    logk::prefix_multi(M_INDEXER, vec![C::CHAIN]),
+                    block.block_number,
+                );
+            }
+        }
+        let mut blocks = self.read_mandatory_blocks()?;
+        if !blocks
+            .iter()
+            .any(|item| item.block_hash == block.block_hash)
+        {
+            blocks.push(block);
+            blocks.sort_by_key(|item| item.block_number);
+        }
+        self.write(&self.key_mandatory(), &blocks)
+    }
+
+    fn on_demand_block<H: Header>(
+        &self,
+        header: &H,
+        message: Option<(LaneId, u64)>,
+    ) -> RelayResult<NeedRelayBlock> {
+        need_relay_block(
+            header,
+            RelayBlockType::OnDemand,
+            self.origin.clone(),
+            message,
+        )
+    }
+}
+
+impl ChainScanIndexer {
+    fn key_mandatory(&self) -> String {
+        format!("chain-scan.{}.mandatory", self.chain)
+    }
+
+    fn key_on_demand(&self) -> String {
+        format!("chain-scan.{}.on-demand", self.chain)
+    }
+
+    fn key_justification(&self, block_hash: &str) -> String {
+        format!("chain-scan.{}.justification.{}", self.chain, block_hash)
+    }
+
+    fn key_message(&self, lane: LaneId, nonce: u64) -> String {
+        format!(
+            "chain-scan.{}.message.{}.{}",
+            self.chain,
+            array_bytes::bytes2hex("0x", lane),
+            nonce,
+        )
+    }
+
+    fn key_baseline(&self, lane: LaneId) -> String {
+        format!(
+            "chain-scan.{}.baseline.{}",
+            self.chain,
+            array_bytes::bytes2hex("0x", lane),
+        )
+    }
+
+    fn read_mandatory_blocks(&self) -> RelayResult<Vec<NeedRelayBlock>> {
+        Ok(self.read(&self.key_mandatory())?.unwrap_or_default())
+    }
+
+    fn read<T: serde::de::DeserializeOwned>(&self, key: &str) -> RelayResult<Option<T>> {
+        let value: Option<serde_json::Value> = self
+            .microkv
+            .get(key)
+            .map_err(|e| RelayError::Indexer(format!("Failed to read {key}: {e:?}")))?;
+        match value {
+            Some(v) => Ok(Some(serde_json::from_value(v).map_err(|e| {
+                RelayError::Indexer(format!("Wrong value stored in {key}: {e:?}"))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    fn write<T: Serialize>(&self, key: &str, value: &T) -> RelayResult<()> {
+        self.microkv
+            .put(key, value)
+            .map_err(|e| RelayError::Indexer(format!("Failed to write {key}: {e:?}")))
+    }
+}
+
+fn tracker_error(err: impl Debug) -> RelayError {
+    RelayError::Indexer(format!("Tracker: {err:?}"))
+}
+
+async fn lane_nonces<C: S2SClientRelay>(
+    client: &C,
+    lanes: &[LaneId],
+    hash: <C::Chain as Chain>::Hash,
+) -> RelayResult<HashMap<LaneId, LaneNonces>> {
+    let mut nonces = HashMap::with_capacity(lanes.len());
+    for lane in lanes {
+        let outbound = client.outbound_lanes(*lane, Some(hash)).await?;
+        let inbound = client.inbound_lanes(*lane, Some(hash)).await?;
+        nonces.insert(
+            *lane,
+            LaneNonces {
+                latest_generated_nonce: outbound.latest_generated_nonce,
+                last_delivered_nonce: inbound.last_delivered_nonce(),
+            },
+        );
+    }
+    Ok(nonces)
+}
+
+/// Find the GRANDPA consensus digest which changes authorities, the bridged chain has to
+/// import this header to follow the authorities set
+fn find_authorities_change<H: Header>(header: &H) -> Option<&'static str> {
+    header.digest().logs().iter().find_map(|item| {
+        match item.consensus_try_to::<ConsensusLog<H::Number>>(&GRANDPA_ENGINE_ID) {
+            Some(ConsensusLog::ScheduledChange(_)) => Some("schedules authorities change"),
+            Some(ConsensusLog::ForcedChange(..)) => Some("forces authorities change"),
+            _ => None,
+        }
+    })
+}
+
+fn need_relay_block<H: Header>(
+    header: &H,
+    type_: RelayBlockType,
+    origin: OriginType,
+    message: Option<(LaneId, u64)>,
+) -> RelayResult<NeedRelayBlock> {
+    let block_hash = array_bytes::bytes2hex("0x", header.hash());
+    Ok(NeedRelayBlock {
+        id: block_hash.clone(),
+        block_number: SmartCodecMapper::map_to(header.number())?,
+        block_hash,
+        type_,
+        origin,
+        lane_id: message.map(|(lane, _)| array_bytes::bytes2hex("", lane)),
+        message_nonce: message.map(|(_, nonce)| nonce),
+        parent_hash: SmartCodecMapper::map_to(header.parent_hash())?,
+        state_root: SmartCodecMapper::map_to(header.state_root())?,
+        extrinsics_root: SmartCodecMapper::map_to(header.extrinsics_root())?,
+        digest: codec::Encode::encode(header.digest()),
+    })
+}
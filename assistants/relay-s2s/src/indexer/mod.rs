@@ -0,0 +1,5 @@
+pub use self::chain_scan::*;
+pub use self::traits::*;
+
+mod chain_scan;
+mod traits;
@@ -0,0 +1,103 @@
+#[cfg(feature = "bridge-parachain")]
+use subquery::types::CandidateIncludedEvent;
+use subquery::types::{JustificationMapping, NeedRelayBlock, OriginType};
+use subquery::Subquery;
+
+#[cfg(feature = "bridge-parachain")]
+use crate::error::RelayError;
+use crate::error::RelayResult;
+use crate::indexer::ChainScanIndexer;
+use crate::types::LaneId;
+
+/// Indexer queried by runners to find the blocks need to relay
+#[derive(Clone, Debug)]
+pub enum Indexer {
+    /// Query subquery service
+    Subquery(Subquery),
+    /// Query blocks recorded by scanning chain rpc, no indexer service required
+    ChainScan(ChainScanIndexer),
+}
+
+impl From<Subquery> for Indexer {
+    fn from(subquery: Subquery) -> Self {
+        Self::Subquery(subquery)
+    }
+}
+
+impl From<ChainScanIndexer> for Indexer {
+    fn from(indexer: ChainScanIndexer) -> Self {
+        Self::ChainScan(indexer)
+    }
+}
+
+impl Indexer {
+    /// Query next mandatory header after the block
+    pub async fn next_mandatory_header(
+        &self,
+        block_number: u32,
+    ) -> RelayResult<Option<NeedRelayBlock>> {
+        match self {
+            Self::Subquery(subquery) => Ok(subquery.next_mandatory_header(block_number).await?),
+            Self::ChainScan(indexer) => indexer.next_mandatory_header(block_number),
+        }
+    }
+
+    /// Query next needed header (on-demand), the chain scan indexer only records the blocks
+    /// of its own origin
+    pub async fn next_needed_header(
+        &self,
+        origin: OriginType,
+    ) -> RelayResult<Option<NeedRelayBlock>> {
+        match self {
+            Self::Subquery(subquery) => Ok(subquery.next_needed_header(origin).await?),
+            Self::ChainScan(indexer) => indexer.next_needed_header(),
+        }
+    }
+
+    /// Find justification, the chain scan indexer only records justifications of
+    /// mandatory headers
+    pub async fn find_justification(
+        &self,
+        block_hash: impl AsRef<str>,
+        is_mandatory: bool,
+    ) -> RelayResult<Option<JustificationMapping>> {
+        match self {
+            Self::Subquery(subquery) => Ok(subquery
+                .find_justification(block_hash, is_mandatory)
+                .await?),
+            Self::ChainScan(indexer) if is_mandatory => indexer.find_justification(block_hash),
+            Self::ChainScan(_) => Ok(None),
+        }
+    }
+
+    /// Query the block which accepted the message
+    pub async fn query_need_relay(
+        &self,
+        origin: OriginType,
+        lane: LaneId,
+        nonce: u64,
+    ) -> RelayResult<Option<NeedRelayBlock>> {
+        match self {
+            Self::Subquery(subquery) => Ok(subquery.query_need_relay(origin, lane, nonce).await?),
+            Self::ChainScan(indexer) => indexer.query_need_relay(lane, nonce),
+        }
+    }
+}
+
+#[cfg(feature = "bridge-parachain")]
+impl Indexer {
+    /// Query the relay chain block which includes the para head
+    pub async fn get_block_with_para_head(
+        &self,
+        para_head_hash: impl AsRef<str>,
+    ) -> RelayResult<Option<CandidateIncludedEvent>> {
+        match self {
+            Self::Subquery(subquery) => {
+                Ok(subquery.get_block_with_para_head(para_head_hash).await?)
+            }
+            Self::ChainScan(_) => Err(RelayError::Indexer(
+                "The chain scan indexer doesn't record included para heads".to_string(),
+            )),
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::error::RelayResult;
+use crate::keepstate::{
+    insert_justification, KeepstateBackend, RecentlyJustification, RelayedNonce,
+};
+
+/// Max justifications kept for each chain
+const MAX_JUSTIFICATIONS: usize = 10;
+
+/// In-memory keepstate, the data is shared by clones of this instance only
+#[derive(Clone, Debug, Default)]
+pub struct MemoryKeepstate {
+    last_relayed_nonce: Arc<Mutex<HashMap<String, RelayedNonce>>>,
+    recently_justifications: Arc<Mutex<HashMap<String, Vec<RecentlyJustification>>>>,
+}
+
+impl KeepstateBackend for MemoryKeepstate {
+    fn get_relayed_nonce(&self, key: &str) -> RelayResult<Option<RelayedNonce>> {
+        let data = self.last_relayed_nonce.lock().unwrap();
+        Ok(data.get(key).cloned())
+    }
+
+    fn set_relayed_nonce(&self, key: &str, relayed: RelayedNonce) -> RelayResult<()> {
+        let mut data = self.last_relayed_nonce.lock().unwrap();
+        data.insert(key.to_string(), relayed);
+        Ok(())
+    }
+
+    fn remove_relayed_nonce(&self, key: &str) -> RelayResult<()> {
+        let mut data = self.last_relayed_nonce.lock().unwrap();
+        data.remove(key);
+        Ok(())
+    }
+
+    fn push_justification(
+        &self,
+        chain: &str,
+        justification: RecentlyJustification,
+    ) -> RelayResult<()> {
+        let mut data = self.recently_justifications.lock().unwrap();
+        let justifications = data.entry(chain.to_string()).or_default();
+        insert_justification(justifications, justification, MAX_JUSTIFICATIONS);
+        Ok(())
+    }
+
+    fn recently_justifications(&self, chain: &str) -> RelayResult<Vec<RecentlyJustification>> {
+        let data = self.recently_justifications.lock().unwrap();
+        Ok(data.get(chain).cloned().unwrap_or_default())
+    }
+}
@@ -0,0 +1,9 @@
+pub use self::memory::*;
+pub use self::persist::*;
+pub use self::traits::*;
+pub use self::types::*;
+
+mod memory;
+mod persist;
+mod traits;
+mod types;
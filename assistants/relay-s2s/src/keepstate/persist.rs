@@ -0,0 +1,89 @@
+use microkv::namespace::NamespaceMicroKV;
+
+use crate::error::{RelayError, RelayResult};
+use crate::keepstate::{
+    insert_justification, KeepstateBackend, RecentlyJustification, RelayedNonce,
+};
+
+/// Keepstate persisted to microkv, survives restarts.
+/// The microkv should be namespaced by bridge, so that different bridges never share keys.
+#[derive(Clone)]
+pub struct MicrokvKeepstate {
+    microkv: NamespaceMicroKV,
+    max_justifications: usize,
+}
+
+impl MicrokvKeepstate {
+    pub fn new(microkv: NamespaceMicroKV) -> Self {
+        Self {
+            microkv,
+            max_justifications: 10,
+        }
+    }
+}
+
+impl MicrokvKeepstate {
+    fn key_relayed_nonce(&self, key: &str) -> String {
+        format!("keepstate.relayed.{key}")
+    }
+
+    fn key_justifications(&self, chain: &str) -> String {
+        format!("keepstate.recently-justifications.{chain}")
+    }
+
+    fn read<T: serde::de::DeserializeOwned>(&self, key: &str) -> RelayResult<Option<T>> {
+        let value: Option<serde_json::Value> = self
+            .microkv
+            .get(key)
+            .map_err(|e| RelayError::Keepstate(format!("Failed to read {key}: {e:?}")))?;
+        match value {
+            Some(v) => Ok(Some(serde_json::from_value(v).map_err(|e| {
+                RelayError::Keepstate(format!("Wrong value stored in {key}: {e:?}"))
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    fn write<T: serde::Serialize>(&self, key: &str, value: &T) -> RelayResult<()> {
+        self.microkv
+            .put(key, value)
+            .map_err(|e| RelayError::Keepstate(format!("Failed to write {key}: {e:?}")))
+    }
+
+    fn read_justifications(&self, chain: &str) -> RelayResult<Vec<RecentlyJustification>> {
+        Ok(self
+            .read::<Vec<RecentlyJustification>>(&self.key_justifications(chain))?
+            .unwrap_or_default())
+    }
+}
+
+impl KeepstateBackend for MicrokvKeepstate {
+    fn get_relayed_nonce(&self, key: &str) -> RelayResult<Option<RelayedNonce>> {
+        self.read(&self.key_relayed_nonce(key))
+    }
+
+    fn set_relayed_nonce(&self, key: &str, relayed: RelayedNonce) -> RelayResult<()> {
+        self.write(&self.key_relayed_nonce(key), &relayed)
+    }
+
+    fn remove_relayed_nonce(&self, key: &str) -> RelayResult<()> {
+        let key = self.key_relayed_nonce(key);
+        self.microkv
+            .delete(&key)
+            .map_err(|e| RelayError::Keepstate(format!("Failed to delete {key}: {e:?}")))
+    }
+
+    fn push_justification(
+        &self,
+        chain: &str,
+        justification: RecentlyJustification,
+    ) -> RelayResult<()> {
+        let mut justifications = self.read_justifications(chain)?;
+        insert_justification(&mut justifications, justification, self.max_justifications);
+        self.write(&self.key_justifications(chain), &justifications)
+    }
+
+    fn recently_justifications(&self, chain: &str) -> RelayResult<Vec<RecentlyJustification>> {
+        self.read_justifications(chain)
+    }
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+use microkv::namespace::NamespaceMicroKV;
+use tokio::sync::watch;
+
+use crate::error::RelayResult;
+use crate::keepstate::{MemoryKeepstate, MicrokvKeepstate, RecentlyJustification, RelayedNonce};
+use crate::types::LaneId;
+
+/// Keepstate storage backend
+pub trait KeepstateBackend: Send + Sync {
+    /// read relayed nonce by key
+    fn get_relayed_nonce(&self, key: &str) -> RelayResult<Option<RelayedNonce>>;
+
+    /// store relayed nonce by key
+    fn set_relayed_nonce(&self, key: &str, relayed: RelayedNonce) -> RelayResult<()>;
+
+    /// remove relayed nonce by key
+    fn remove_relayed_nonce(&self, key: &str) -> RelayResult<()>;
+
+    /// push a new justification of chain
+    fn push_justification(
+        &self,
+        chain: &str,
+        justification: RecentlyJustification,
+    ) -> RelayResult<()>;
+
+    /// read recently justifications of chain, ordered by block number
+    fn recently_justifications(&self, chain: &str) -> RelayResult<Vec<RecentlyJustification>>;
+}
+
+/// Keepstate, shared by runners of one bridge
+#[derive(Clone)]
+pub struct Keepstate {
+    backend: Arc<dyn KeepstateBackend>,
+    /// finality counter of each chain, increased when new finality of the chain is notified
+    finality: Arc<watch::Sender<HashMap<String, u64>>>,
+}
+
+impl Keepstate {
+    /// Create keepstate with custom backend
+    pub fn new(backend: impl KeepstateBackend + 'static) -> Self {
+        let (finality, _) = watch::channel(HashMap::new());
+        Self {
+            backend: Arc::new(backend),
+            finality: Arc::new(finality),
+        }
+    }
+
+    /// In-memory keepstate, lost after restart. The clones of returned keepstate share data,
+    /// but each call creates a new one
+    pub fn memory() -> Self {
+        Self::new(MemoryKeepstate::default())
+    }
+
+    /// Keepstate persisted to microkv
+    pub fn microkv(microkv: NamespaceMicroKV) -> Self {
+        Self::new(MicrokvKeepstate::new(microkv))
+    }
+}
+
+impl Default for Keepstate {
+    fn default() -> Self {
+        Self::memory()
+    }
+}
+
+impl Debug for Keepstate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Keepstate { backend: <...> }")
+    }
+}
+
+impl Keepstate {
+    fn relayed_nonce_key(chain: &str, lane: LaneId, kind: &str) -> String {
+        format!("{}-{}-{}", chain, array_bytes::bytes2hex("", lane), kind)
+    }
+
+    pub fn get_last_delivery_relayed_nonce(
+        &self,
+        chain: &str,
+        lane: LaneId,
+    ) -> RelayResult<Option<RelayedNonce>> {
+        self.backend
+            .get_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "delivery"))
+    }
+
+    pub fn set_last_delivery_relayed_nonce(
+        &self,
+        chain: &str,
+        lane: LaneId,
+        relayed: RelayedNonce,
+    ) -> RelayResult<()> {
+        self.backend
+            .set_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "delivery"), relayed)
+    }
+
+    pub fn remove_last_delivery_relayed_nonce(&self, chain: &str, lane: LaneId) -> RelayResult<()> {
+        self.backend
+            .remove_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "delivery"))
+    }
+
+    pub fn get_last_receiving_relayed_nonce(
+        &self,
+        chain: &str,
+        lane: LaneId,
+    ) -> RelayResult<Option<RelayedNonce>> {
+        self.backend
+            .get_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "receiving"))
+    }
+
+    pub fn set_last_receiving_relayed_nonce(
+        &self,
+        chain: &str,
+        lane: LaneId,
+        relayed: RelayedNonce,
+    ) -> RelayResult<()> {
+        self.backend
+            .set_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "receiving"), relayed)
+    }
+
+    pub fn remove_last_receiving_relayed_nonce(
+        &self,
+        chain: &str,
+        lane: LaneId,
+    ) -> RelayResult<()> {
+        self.backend
+            .remove_relayed_nonce(&Self::relayed_nonce_key(chain, lane, "receiving"))
+    }
+
+    pub fn set_recently_justification(
+        &self,
+        chain: &str,
+        justification: RecentlyJustification,
+    ) -> RelayResult<()> {
+        self.backend.push_justification(chain, justification)?;
+        self.notify_finality(chain);
+        Ok(())
+    }
+
+    /// Notify new finality of chain, e.g. a finalized head is subscribed or a justification
+    /// is kept
+    pub fn notify_finality(&self, chain: &str) {
+        self.finality.send_modify(|finality| {
+            *finality.entry(chain.to_string()).or_default() += 1;
+        });
+    }
+
+    /// Finality counters of the chains of bridge, runners wait for the counters of the chains
+    /// they depend on instead of polling on a fixed interval
+    pub fn subscribe_finality(&self) -> watch::Receiver<HashMap<String, u64>> {
+        self.finality.subscribe()
+    }
+
+    /// The latest justification of chain
+    pub fn get_recently_justification(
+        &self,
+        chain: &str,
+    ) -> RelayResult<Option<RecentlyJustification>> {
+        Ok(self.backend.recently_justifications(chain)?.pop())
+    }
+
+    /// The justification which finalizes the block of this number
+    pub fn get_justification_by_number(
+        &self,
+        chain: &str,
+        block_number: u32,
+    ) -> RelayResult<Option<RecentlyJustification>> {
+        Ok(self
+            .backend
+            .recently_justifications(chain)?
+            .into_iter()
+            .find(|v| v.block_number == block_number))
+    }
+
+    /// The justification which finalizes the block of this hash
+    pub fn get_justification_by_hash(
+        &self,
+        chain: &str,
+        block_hash: &str,
+    ) -> RelayResult<Option<RecentlyJustification>> {
+        Ok(self
+            .backend
+            .recently_justifications(chain)?
+            .into_iter()
+            .find(|v| v.is_for_hash(block_hash)))
+    }
+
+    /// The earliest justification which finalizes the block of this number or a descendant of
+    /// it, relaying its header also finalizes the block
+    pub fn find_justification_since(
+        &self,
+        chain: &str,
+        block_number: u32,
+    ) -> RelayResult<Option<RecentlyJustification>> {
+        Ok(self
+            .backend
+            .recently_justifications(chain)?
+            .into_iter()
+            .find(|v| v.block_number >= block_number))
+    }
+}
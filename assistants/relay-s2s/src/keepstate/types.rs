@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// The last relayed nonce which is waiting for the next step
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RelayedNonce {
+    /// relayed nonce
+    pub nonce: u64,
+    /// hash of the extrinsic which submitted this nonce
+    pub extrinsic_hash: String,
+}
+
+/// Justification subscribed from chain, with the block it finalizes
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RecentlyJustification {
+    /// number of the finalized block
+    pub block_number: u32,
+    /// hex hash of the finalized block, with `0x` prefix
+    pub block_hash: String,
+    /// encoded grandpa justification
+    pub justification: sp_core::Bytes,
+}
+
+impl RecentlyJustification {
+    /// Whether the justification finalizes the block of this hash, the `0x` prefix is optional
+    pub fn is_for_hash(&self, block_hash: &str) -> bool {
+        let expected = block_hash.trim_start_matches("0x");
+        self.block_hash
+            .trim_start_matches("0x")
+            .eq_ignore_ascii_case(expected)
+    }
+}
+
+/// Insert the justification ordered by block number, the duplicated one is ignored and
+/// the oldest ones are dropped when there are more than `max` justifications
+pub(crate) fn insert_justification(
+    justifications: &mut Vec<RecentlyJustification>,
+    justification: RecentlyJustification,
+    max: usize,
+) {
+    if justifications
+        .iter()
+        .any(|v| v.block_hash == justification.block_hash)
+    {
+        return;
+    }
+    let index = justifications.partition_point(|v| v.block_number <= justification.block_number);
+    justifications.insert(index, justification);
+    if justifications.len() > max {
+        let overflow = justifications.len() - max;
+        justifications.drain(..overflow);
+    }
+}
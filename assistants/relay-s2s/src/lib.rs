@@ -1,9 +1,10 @@
 pub mod error;
 pub mod header;
+pub mod indexer;
+pub mod keepstate;
 pub mod message;
 pub mod subscribe;
 pub mod types;
 
-mod keepstate;
 mod special;
 mod strategy;
@@ -1,16 +1,16 @@
 use bridge_s2s_traits::client::S2SClientRelay;
-use bridge_s2s_traits::strategy::RelayStrategy;
+use bridge_s2s_traits::strategy::BatchRelayStrategy;
 
 use crate::error::RelayResult;
 use crate::message::common::CommonDeliveryRunner;
 use crate::special::SolochainSpecialClientApi;
-use crate::types::MessageDeliveryInput;
+use crate::types::{DeliveryStatus, LaneId, MessageDeliveryInput};
 
 pub struct BridgeSolochainDeliveryRunner<SC, TC, Strategy>
 where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     common: CommonDeliveryRunner<SC, TC, SolochainSpecialClientApi<TC>, Strategy>,
 }
@@ -19,7 +19,7 @@ impl<SC, TC, Strategy> BridgeSolochainDeliveryRunner<SC, TC, Strategy>
 where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     pub fn new(input: MessageDeliveryInput<SC, TC, Strategy>) -> Self {
         let different = SolochainSpecialClientApi {
@@ -32,4 +32,9 @@ where
     pub async fn start(&self) -> RelayResult<()> {
         self.common.start().await
     }
+
+    /// Delivery status of a lane, nothing will be submitted
+    pub async fn status(&self, lane: LaneId) -> RelayResult<DeliveryStatus> {
+        self.common.status(lane).await
+    }
 }
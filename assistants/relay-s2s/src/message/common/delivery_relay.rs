@@ -1,28 +1,43 @@
+use std::collections::HashMap;
 use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::Mutex;
 
 use bridge_s2s_traits::client::S2SClientRelay;
-use bridge_s2s_traits::strategy::RelayStrategy;
-use bridge_s2s_traits::types::bp_messages::{OutboundLaneData, Weight};
+use bridge_s2s_traits::strategy::BatchRelayStrategy;
+use bridge_s2s_traits::types::bp_messages::{
+    InboundLaneData, MessageKey, OutboundLaneData, Weight,
+};
+use bridge_s2s_traits::types::bp_runtime::Chain;
 use bridge_s2s_traits::types::bridge_runtime_common::messages::target::FromBridgedChainMessagesProof;
+use bridge_s2s_traits::types::{ExtrinsicLocation, MessagesDeliveryLimits};
+use sp_runtime::codec;
 use sp_runtime::traits::Header;
 
 use support_toolkit::{convert::SmartCodecMapper, logk};
 
 use crate::error::{RelayError, RelayResult};
-use crate::keepstate;
+use crate::header::common::{prepare_finality, Finality};
+use crate::keepstate::RelayedNonce;
 use crate::special::DifferentClientApi;
 use crate::strategy::{EnforcementDecideReference, EnforcementRelayStrategy};
-use crate::types::{LaneId, MessageDeliveryInput, M_DELIVERY};
+use crate::subscribe::FinalityWakeup;
+use crate::types::{
+    DeliveryDecision, DeliveryStatus, LaneId, MessageDeliveryInput, UnrewardedRelayerStatus,
+    M_DELIVERY,
+};
 
 pub struct CommonDeliveryRunner<SC, TC, DC, Strategy>
 where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
     DC: DifferentClientApi<TC>,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     different: DC,
     input: MessageDeliveryInput<SC, TC, Strategy>,
+    /// the nonce over the limits of target chain which is already reported for each lane
+    over_limits_reported: Mutex<HashMap<LaneId, u64>>,
 }
 
 impl<SC, TC, DC, Strategy> CommonDeliveryRunner<SC, TC, DC, Strategy>
@@ -30,10 +45,14 @@ where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
     DC: DifferentClientApi<TC>,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     pub fn new(input: MessageDeliveryInput<SC, TC, Strategy>, different: DC) -> Self {
-        Self { input, different }
+        Self {
+            input,
+            different,
+            over_limits_reported: Default::default(),
+        }
     }
 }
 
@@ -43,14 +62,67 @@ where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
     DC: DifferentClientApi<TC>,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     async fn source_outbound_lane_data(&self, lane: LaneId) -> RelayResult<OutboundLaneData> {
         let outbound_lane_data = self.input.client_source.outbound_lanes(lane, None).await?;
         Ok(outbound_lane_data)
     }
 
-    async fn assemble_nonces(
+    fn report_lane_nonces(
+        &self,
+        lane: LaneId,
+        outbound_lane_data: &OutboundLaneData,
+        observation: &DeliveryObservation,
+    ) {
+        let lane = array_bytes::bytes2hex("0x", lane);
+        let report = |kind, nonce| {
+            support_metrics::metrics::lane_nonce(SC::CHAIN, TC::CHAIN, &lane, kind, nonce)
+        };
+        report("generated", outbound_lane_data.latest_generated_nonce);
+        report("confirmed", outbound_lane_data.latest_received_nonce);
+        // the target inbound lane isn't queried when all messages are confirmed
+        let delivered = observation.last_delivered_nonce.or_else(|| {
+            (outbound_lane_data.latest_received_nonce == outbound_lane_data.latest_generated_nonce)
+                .then_some(outbound_lane_data.latest_generated_nonce)
+        });
+        if let Some(nonce) = delivered {
+            report("delivered", nonce);
+        }
+        report(
+            "over-limits",
+            observation.over_limits_nonce.unwrap_or_default(),
+        );
+    }
+
+    /// The lane is stuck at the message over the limits of target chain, report it loudly once
+    fn report_over_limits(&self, lane: LaneId, over_limits_nonce: Option<u64>) {
+        let mut reported = self.over_limits_reported.lock().unwrap();
+        let nonce = match over_limits_nonce {
+            Some(v) => v,
+            None => {
+                reported.remove(&lane);
+                return;
+            }
+        };
+        if reported.insert(lane, nonce) == Some(nonce) {
+            return;
+        }
+        tracing::error!(
+            target: "relay-s2s",
+            "{} the message {} is over the limits of {} even if delivered alone, the lane is stuck until the limits of target runtime or the `delivery_limits` config are raised",
+            logk::prefix_with_bridge_and_others(
+                M_DELIVERY,
+                SC::CHAIN,
+                TC::CHAIN,
+                vec![array_bytes::bytes2hex("0x", lane),],
+            ),
+            nonce,
+            TC::CHAIN,
+        );
+    }
+
+    pub(super) async fn assemble_nonces(
         &self,
         lane: LaneId,
         limit: u64,
@@ -78,7 +150,12 @@ where
 
         // assemble nonce range
         let start: u64 = latest_confirmed_nonce + 1;
-        if let Some(last_relayed_nonce) = keepstate::get_last_delivery_relayed_nonce(SC::CHAIN) {
+        if let Some(last_relayed) = self
+            .input
+            .keepstate
+            .get_last_delivery_relayed_nonce(SC::CHAIN, lane)?
+        {
+            let last_relayed_nonce = last_relayed.nonce;
             if last_relayed_nonce >= start {
                 tracing::warn!(
                     target: "relay-s2s",
@@ -117,6 +194,312 @@ where
         let nonces = start..=end;
         Ok(Some(nonces))
     }
+
+    /// Read messages proof of nonces at the source block, returns the proof, the bytes of proof
+    /// and the total dispatch weight of messages.
+    async fn messages_proof(
+        &self,
+        lane: LaneId,
+        nonces: RangeInclusive<u64>,
+        outbound_state_proof_required: bool,
+        at: <SC::Chain as Chain>::Hash,
+    ) -> RelayResult<(
+        FromBridgedChainMessagesProof<<SC::Chain as Chain>::Hash>,
+        usize,
+        u64,
+    )> {
+        let client_source = &self.input.client_source;
+
+        // read proof
+        let mut storage_keys = Vec::with_capacity((nonces.end() - nonces.start()) as usize + 1);
+        let mut message_nonce = *nonces.start();
+        while message_nonce <= *nonces.end() {
+            let message_key = client_source.gen_outbound_messages_storage_key(lane, message_nonce);
+            storage_keys.push(message_key);
+            message_nonce += 1;
+        }
+        if outbound_state_proof_required {
+            storage_keys.push(client_source.gen_outbound_lanes_storage_key(lane));
+        }
+
+        // fill delivery data
+        let total_weight = client_source
+            .calculate_dispatch_weight(lane, nonces.clone())
+            .await?;
+
+        let proof = client_source.read_proof(storage_keys, Some(at)).await?;
+        let message_size = proof.iter().map(|item| item.len()).sum();
+        let proof = FromBridgedChainMessagesProof {
+            bridged_header_hash: at,
+            storage_proof: proof,
+            lane,
+            nonces_start: *nonces.start(),
+            nonces_end: *nonces.end(),
+        };
+        Ok((proof, message_size, total_weight))
+    }
+
+    /// Read messages proof like `messages_proof`, but the nonces are shrunk until the proof fits
+    /// the max extrinsic size and weight of target chain, the fitted nonces are returned. It
+    /// returns none if the first message is over the limits alone.
+    async fn limited_messages_proof(
+        &self,
+        lane: LaneId,
+        nonces: RangeInclusive<u64>,
+        outbound_state_proof_required: bool,
+        at: <SC::Chain as Chain>::Hash,
+        limits: &MessagesDeliveryLimits,
+    ) -> RelayResult<
+        Option<(
+            RangeInclusive<u64>,
+            FromBridgedChainMessagesProof<<SC::Chain as Chain>::Hash>,
+            usize,
+            u64,
+        )>,
+    > {
+        let mut nonces = nonces;
+        loop {
+            let (proof, message_size, total_weight) = self
+                .messages_proof(lane, nonces.clone(), outbound_state_proof_required, at)
+                .await?;
+            let proof_size = codec::Encode::encoded_size(&proof);
+            let over_weight =
+                matches!(limits.max_extrinsic_weight, Some(max) if total_weight > max);
+            let over_size =
+                matches!(limits.max_extrinsic_size, Some(max) if proof_size > max as usize);
+            if !over_weight && !over_size {
+                return Ok(Some((nonces, proof, message_size, total_weight)));
+            }
+            if nonces.start() == nonces.end() {
+                tracing::debug!(
+                    target: "relay-s2s",
+                    "{} the message {} is over the limits of {} even if delivered alone, skip it. encoded size: {} bytes, dispatch weight: {}",
+                    logk::prefix_with_bridge_and_others(
+                        M_DELIVERY,
+                        SC::CHAIN,
+                        TC::CHAIN,
+                        vec![array_bytes::bytes2hex("0x", lane),],
+                    ),
+                    nonces.start(),
+                    TC::CHAIN,
+                    proof_size,
+                    total_weight,
+                );
+                return Ok(None);
+            }
+            let fitted = match limits.max_extrinsic_weight {
+                Some(max) if over_weight => self.weight_fitted_nonces(lane, &nonces, max).await?,
+                // the size of proof isn't linear to messages, halve the nonces and read again
+                _ => *nonces.start()..=nonces.start() + (nonces.end() - nonces.start()) / 2,
+            };
+            tracing::info!(
+                target: "relay-s2s",
+                "{} the nonces {:?} are over the limits of {}, shrink to {:?}, encoded size: {} bytes, dispatch weight: {}",
+                logk::prefix_with_bridge_and_others(
+                    M_DELIVERY,
+                    SC::CHAIN,
+                    TC::CHAIN,
+                    vec![array_bytes::bytes2hex("0x", lane),],
+                ),
+                nonces,
+                TC::CHAIN,
+                fitted,
+                proof_size,
+                total_weight,
+            );
+            nonces = fitted;
+        }
+    }
+
+    /// The longest nonces from the start whose total dispatch weight isn't over the max weight,
+    /// the first nonce is always kept.
+    async fn weight_fitted_nonces(
+        &self,
+        lane: LaneId,
+        nonces: &RangeInclusive<u64>,
+        max_weight: u64,
+    ) -> RelayResult<RangeInclusive<u64>> {
+        let mut total_weight = 0u64;
+        let mut end = *nonces.start();
+        for nonce in nonces.clone() {
+            let weight = self
+                .input
+                .client_source
+                .calculate_dispatch_weight(lane, nonce..=nonce)
+                .await?;
+            total_weight = total_weight.saturating_add(weight);
+            if nonce != *nonces.start() && total_weight > max_weight {
+                break;
+            }
+            end = nonce;
+        }
+        Ok(*nonces.start()..=end)
+    }
+
+    /// Estimate fee of delivering the nonces by pre-flight check of target chain
+    async fn estimate_delivery_fee(
+        &self,
+        nonces: &RangeInclusive<u64>,
+        proof: &FromBridgedChainMessagesProof<<SC::Chain as Chain>::Hash>,
+        total_weight: u64,
+    ) -> RelayResult<Option<u128>> {
+        let expected_proof = SmartCodecMapper::map_to(proof)?;
+        let expected_relayer_id = SmartCodecMapper::map_to(&self.input.relayer_account)?;
+        let estimated_fee = self
+            .input
+            .client_target
+            .estimate_receive_messages_proof_fee(
+                expected_relayer_id,
+                expected_proof,
+                (nonces.end() - nonces.start() + 1) as _,
+                Weight::from_ref_time(total_weight),
+            )
+            .await?;
+        Ok(estimated_fee)
+    }
+
+    /// Total fee of messages at the source block
+    async fn messages_fee(
+        &self,
+        lane: LaneId,
+        nonces: RangeInclusive<u64>,
+        at: <SC::Chain as Chain>::Hash,
+    ) -> RelayResult<u128> {
+        let mut messages_fee: u128 = 0;
+        for nonce in nonces {
+            let message_key = MessageKey {
+                lane_id: lane,
+                nonce,
+            };
+            if let Some(message) = self
+                .input
+                .client_source
+                .outbound_messages(message_key, Some(at))
+                .await?
+            {
+                messages_fee = messages_fee.saturating_add(message.fee);
+            }
+        }
+        Ok(messages_fee)
+    }
+
+    /// Whether the first nonce has been delivered to target chain
+    async fn is_delivered(&self, lane: LaneId, nonces: &RangeInclusive<u64>) -> RelayResult<bool> {
+        let target_inbound_lane_data = self.input.client_target.inbound_lanes(lane, None).await?;
+        Ok(target_inbound_lane_data.last_delivered_nonce() >= *nonces.start())
+    }
+
+    /// The header which finalizes the block of nonce, it's bundled with the messages proof
+    /// when header relay is behind.
+    async fn bundled_finality(
+        &self,
+        block_number: u32,
+    ) -> RelayResult<Option<Finality<TC::Chain>>> {
+        if !self.input.bundle_header || !self.different.supports_header_bundle() {
+            return Ok(None);
+        }
+        let justification = match self
+            .input
+            .keepstate
+            .find_justification_since(SC::CHAIN, block_number)?
+        {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        prepare_finality(
+            &self.input.client_source,
+            &self.input.client_target,
+            &justification,
+        )
+        .await
+    }
+
+    fn report_lost_race(&self, lane: LaneId, nonces: &RangeInclusive<u64>, stage: &str) {
+        tracing::warn!(
+            target: "relay-s2s",
+            "{} lost race ({}), the nonces {:?} have been delivered by other relayers",
+            logk::prefix_with_bridge_and_others(
+                M_DELIVERY,
+                SC::CHAIN,
+                TC::CHAIN,
+                vec![array_bytes::bytes2hex("0x", lane),],
+            ),
+            stage,
+            nonces,
+        );
+        support_metrics::metrics::lost_race(TC::CHAIN, "receive_messages_proof", stage);
+    }
+
+    /// The relayed nonce stored before restart may be delivered, pending or dropped by target
+    /// chain. It's cleared only if the extrinsic is neither delivered nor pending in transaction
+    /// pool, so that these nonces can be relayed again.
+    pub(super) async fn check_pending_relayed_nonce(&self, lane: LaneId) -> RelayResult<()> {
+        let keepstate = &self.input.keepstate;
+        let pending = match keepstate.get_last_delivery_relayed_nonce(SC::CHAIN, lane)? {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        let extrinsic_hash = pending.extrinsic_hash.trim_start_matches("0x");
+        let extrinsic_hash = sp_core::H256::from_str(extrinsic_hash).map_err(|e| {
+            RelayError::Custom(format!("Wrong extrinsic hash [{extrinsic_hash}] {e:?}"))
+        })?;
+        // look up the extrinsic before reading the lane, so that the lane reflects the
+        // extrinsic if it's included meanwhile
+        let location = self
+            .input
+            .client_target
+            .find_extrinsic(SmartCodecMapper::map_to(&extrinsic_hash)?)
+            .await?;
+        let target_inbound_lane_data = self.input.client_target.inbound_lanes(lane, None).await?;
+        let logk_prefix = logk::prefix_with_bridge_and_others(
+            M_DELIVERY,
+            SC::CHAIN,
+            TC::CHAIN,
+            vec![array_bytes::bytes2hex("0x", lane)],
+        );
+        if target_inbound_lane_data.last_delivered_nonce() >= pending.nonce {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} the pending nonce({}) sent by {} has been finalized",
+                logk_prefix,
+                pending.nonce,
+                pending.extrinsic_hash,
+            );
+            return Ok(());
+        }
+        match location {
+            ExtrinsicLocation::Pool => {
+                tracing::info!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} is still in transaction pool, keep it.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                );
+                return Ok(());
+            }
+            ExtrinsicLocation::Block(block_hash) => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} is included by block {:?} but not delivered, it was failed.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                    block_hash,
+                );
+            }
+            ExtrinsicLocation::NotFound => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} not found in target chain, it was dropped.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                );
+            }
+        }
+        keepstate.remove_last_delivery_relayed_nonce(SC::CHAIN, lane)
+    }
 }
 
 impl<SC, TC, DC, Strategy> CommonDeliveryRunner<SC, TC, DC, Strategy>
@@ -124,7 +507,7 @@ where
     SC: S2SClientRelay,
     TC: S2SClientRelay,
     DC: DifferentClientApi<TC>,
-    Strategy: RelayStrategy,
+    Strategy: BatchRelayStrategy,
 {
     pub async fn start(&self) -> RelayResult<()> {
         tracing::info!(
@@ -132,30 +515,194 @@ where
             "{} SERVICE RESTARTING...",
             logk::prefix_with_bridge(M_DELIVERY, SC::CHAIN, TC::CHAIN),
         );
+        for lane in &self.input.lanes {
+            self.check_pending_relayed_nonce(*lane).await?;
+        }
+        let mut wakeup = FinalityWakeup::new(&self.input.keepstate, vec![SC::CHAIN, TC::CHAIN]);
         loop {
             for lane in &self.input.lanes {
-                let last_relayed_nonce = self.run(*lane, self.input.nonces_limit).await?;
-                if last_relayed_nonce.is_some() {
-                    keepstate::set_last_delivery_relayed_nonce(
+                let last_relayed = self.run(*lane, self.input.nonces_limit).await?;
+                if let Some(relayed) = last_relayed {
+                    self.input.keepstate.set_last_delivery_relayed_nonce(
                         SC::CHAIN,
-                        last_relayed_nonce.expect("Unreachable"),
-                    );
+                        *lane,
+                        relayed,
+                    )?;
                 }
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
+            wakeup.wait().await;
+        }
+    }
+
+    async fn run(&self, lane: LaneId, limit: u64) -> RelayResult<Option<RelayedNonce>> {
+        let source_outbound_lane_data = self.source_outbound_lane_data(lane).await?;
+        let mut observation = DeliveryObservation::default();
+        let plan = self
+            .plan(lane, limit, &source_outbound_lane_data, &mut observation)
+            .await?;
+        self.report_lane_nonces(lane, &source_outbound_lane_data, &observation);
+        self.report_over_limits(lane, observation.over_limits_nonce);
+        let (nonces, proof, total_weight, finality) = match plan {
+            DeliveryPlan::Relay {
+                nonces,
+                proof,
+                total_weight,
+                finality,
+            } => (nonces, proof, total_weight, finality),
+            DeliveryPlan::Skip(_) => return Ok(None),
+        };
+
+        if self.input.dry_run {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} [dry-run] would submit receive_messages_proof for nonces {:?}{}, encoded size: {} bytes, dispatch weight: {}",
+                logk::prefix_with_bridge_and_others(
+                    M_DELIVERY,
+                    SC::CHAIN,
+                    TC::CHAIN,
+                    vec![array_bytes::bytes2hex("0x", lane),],
+                ),
+                nonces,
+                finality
+                    .as_ref()
+                    .map(|(header, _)| format!(" bundled with header {:?}", header.number()))
+                    .unwrap_or_default(),
+                codec::Encode::encoded_size(&proof),
+                total_weight,
+            );
+            return Ok(None);
         }
+
+        // submit messages proof to target chain
+        let messages_fee = self
+            .messages_fee(lane, nonces.clone(), proof.bridged_header_hash)
+            .await?;
+        let expected_proof = SmartCodecMapper::map_to(&proof)?;
+        let relayer_account_source_chain = self.input.relayer_account.clone();
+        let expected_relayer_id = SmartCodecMapper::map_to(&relayer_account_source_chain)?;
+
+        // other relayers may deliver the same nonces while the proof was prepared
+        if self.is_delivered(lane, &nonces).await? {
+            self.report_lost_race(lane, &nonces, "before-submit");
+            return Ok(None);
+        }
+        let client_target = &self.input.client_target;
+        let messages_count = (nonces.end() - nonces.start() + 1) as _;
+        let dispatch_weight = Weight::from_ref_time(total_weight);
+        let (call, result) = match finality {
+            Some(finality) => (
+                "receive_messages_proof_with_finality",
+                client_target
+                    .receive_messages_proof_with_finality(
+                        finality,
+                        expected_relayer_id,
+                        expected_proof,
+                        messages_count,
+                        dispatch_weight,
+                        messages_fee,
+                    )
+                    .await,
+            ),
+            None => (
+                "receive_messages_proof",
+                client_target
+                    .receive_messages_proof(
+                        expected_relayer_id,
+                        expected_proof,
+                        messages_count,
+                        dispatch_weight,
+                        messages_fee,
+                    )
+                    .await,
+            ),
+        };
+        support_metrics::metrics::record_extrinsic(TC::CHAIN, call, &result);
+        let hash = match result {
+            Ok(v) => v,
+            // the extrinsic is failed if the nonces were delivered by others in a prior block
+            Err(e) => {
+                if self.is_delivered(lane, &nonces).await? {
+                    self.report_lost_race(lane, &nonces, "after-inclusion");
+                    return Ok(None);
+                }
+                return Err(e.into());
+            }
+        };
+
+        tracing::info!(
+            target: "relay-s2s",
+            "{} the nonces {:?} in delivered to target chain -> {}",
+            logk::prefix_with_bridge_and_others(
+                M_DELIVERY,
+                SC::CHAIN,
+                TC::CHAIN,
+                vec![array_bytes::bytes2hex("0x", lane),],
+            ),
+            nonces,
+            array_bytes::bytes2hex("0x", hash),
+        );
+        Ok(Some(RelayedNonce {
+            nonce: *nonces.end(),
+            extrinsic_hash: array_bytes::bytes2hex("0x", hash),
+        }))
     }
 
-    async fn run(&self, lane: LaneId, limit: u64) -> RelayResult<Option<u64>> {
+    /// Snapshot of a lane, the decision is made by the same logic as the delivery relay,
+    /// but nothing will be submitted.
+    pub async fn status(&self, lane: LaneId) -> RelayResult<DeliveryStatus> {
         let source_outbound_lane_data = self.source_outbound_lane_data(lane).await?;
+        let target_inbound_lane_data = self.input.client_target.inbound_lanes(lane, None).await?;
+        let mut observation = DeliveryObservation::default();
+        let decision = match self
+            .plan(
+                lane,
+                self.input.nonces_limit,
+                &source_outbound_lane_data,
+                &mut observation,
+            )
+            .await?
+        {
+            DeliveryPlan::Relay { nonces, .. } => DeliveryDecision::Relay { nonces },
+            DeliveryPlan::Skip(decision) => decision,
+        };
+        let unrewarded_relayers = target_inbound_lane_data
+            .relayers
+            .iter()
+            .map(|item| UnrewardedRelayerStatus {
+                relayer: format!("{:?}", item.relayer),
+                begin: item.messages.begin,
+                end: item.messages.end,
+            })
+            .collect();
+        Ok(DeliveryStatus {
+            lane,
+            latest_generated_nonce: source_outbound_lane_data.latest_generated_nonce,
+            latest_received_nonce: source_outbound_lane_data.latest_received_nonce,
+            last_delivered_nonce: target_inbound_lane_data.last_delivered_nonce(),
+            last_confirmed_nonce: target_inbound_lane_data.last_confirmed_nonce,
+            unrewarded_relayers,
+            last_relayed_block: observation.last_relayed_block,
+            nonce_block: observation.nonce_block,
+            over_limits_nonce: observation.over_limits_nonce,
+            decision,
+        })
+    }
 
+    /// Decide what to deliver for a lane, the messages proof is prepared if there are nonces to relay.
+    async fn plan(
+        &self,
+        lane: LaneId,
+        limit: u64,
+        source_outbound_lane_data: &OutboundLaneData,
+        observation: &mut DeliveryObservation,
+    ) -> RelayResult<DeliveryPlan<<SC::Chain as Chain>::Hash, Finality<TC::Chain>>> {
         // alias
         let client_source = &self.input.client_source;
         let client_target = &self.input.client_target;
         let subquery_source = &self.input.subquery_source;
 
         let nonces = match self
-            .assemble_nonces(lane, limit, &source_outbound_lane_data)
+            .assemble_nonces(lane, limit, source_outbound_lane_data)
             .await?
         {
             Some(v) => v,
@@ -170,7 +717,22 @@ where
                         vec![array_bytes::bytes2hex("0x", lane),],
                     ),
                 );
-                return Ok(None);
+                let last_relayed = self
+                    .input
+                    .keepstate
+                    .get_last_delivery_relayed_nonce(SC::CHAIN, lane)?;
+                let decision = match last_relayed {
+                    Some(relayed)
+                        if source_outbound_lane_data.latest_received_nonce
+                            != source_outbound_lane_data.latest_generated_nonce =>
+                    {
+                        DeliveryDecision::WaitReceiving {
+                            last_relayed_nonce: relayed.nonce,
+                        }
+                    }
+                    _ => DeliveryDecision::AllDelivered,
+                };
+                return Ok(DeliveryPlan::Skip(decision));
             }
         };
         tracing::debug!(
@@ -204,9 +766,12 @@ where
                     nonces.end(),
                     SC::CHAIN,
                 );
-                return Ok(None);
+                return Ok(DeliveryPlan::Skip(DeliveryDecision::IndexerGap {
+                    nonce: *nonces.end(),
+                }));
             }
         };
+        observation.nonce_block = Some(last_relay.block_number);
 
         // query last relayed header
         let last_relayed_source_block_in_target =
@@ -218,10 +783,10 @@ where
                         "{} the bridge not initialized, please init first.",
                         logk::prefix_with_bridge(M_DELIVERY, SC::CHAIN, TC::CHAIN),
                     );
-                    return Ok(None);
+                    return Ok(DeliveryPlan::Skip(DeliveryDecision::NotInitialized));
                 }
             };
-        let expected_source_hash =
+        let mut expected_source_hash =
             SmartCodecMapper::map_to(&last_relayed_source_block_in_target.1)?;
         let last_relayed_source_block_in_target = client_source
             .block(Some(expected_source_hash))
@@ -237,7 +802,29 @@ where
         // compare last nonce block with last relayed header
         let relayed_block_number = last_relayed_source_block_in_target.block.header.number();
         let relayed_block_number: u32 = SmartCodecMapper::map_to(relayed_block_number)?;
-        if relayed_block_number < last_relay.block_number {
+        observation.last_relayed_block = Some(relayed_block_number);
+        let finality = if relayed_block_number < last_relay.block_number {
+            self.bundled_finality(last_relay.block_number).await?
+        } else {
+            None
+        };
+        if let Some((header, _)) = &finality {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} the last nonce({}) at block {} is large than last relayed header {}, bundle header {:?} with messages proof.",
+                logk::prefix_with_bridge_and_others(
+                    M_DELIVERY,
+                    SC::CHAIN,
+                    TC::CHAIN,
+                    vec![array_bytes::bytes2hex("0x", lane),],
+                ),
+                nonces.end(),
+                last_relay.block_number,
+                relayed_block_number,
+                header.number(),
+            );
+            expected_source_hash = SmartCodecMapper::map_to(&header.hash())?;
+        } else if relayed_block_number < last_relay.block_number {
             tracing::warn!(
                 target: "relay-s2s",
                 "{} the last nonce({}) at block {} is large than last relayed header {}, please wait header relay.",
@@ -251,42 +838,93 @@ where
                 last_relay.block_number,
                 relayed_block_number,
             );
-            return Ok(None);
-        }
-
-        // read proof
-        let mut storage_keys = Vec::with_capacity((nonces.end() - nonces.start()) as usize + 1);
-        let mut message_nonce = *nonces.start();
-        while message_nonce <= *nonces.end() {
-            let message_key = client_source.gen_outbound_messages_storage_key(lane, message_nonce);
-            storage_keys.push(message_key);
-            message_nonce += 1;
+            return Ok(DeliveryPlan::Skip(DeliveryDecision::WaitHeader {
+                nonce: *nonces.end(),
+            }));
         }
 
         //- query inbound land data
         let target_inbound_lane_data = client_target.inbound_lanes(lane, None).await?;
+        observation.last_delivered_nonce = Some(target_inbound_lane_data.last_delivered_nonce());
         let outbound_state_proof_required = target_inbound_lane_data.last_confirmed_nonce
             < source_outbound_lane_data.latest_received_nonce;
-        if outbound_state_proof_required {
-            storage_keys.push(client_source.gen_outbound_lanes_storage_key(lane));
-        }
 
-        // fill delivery data
-        let total_weight = client_source
-            .calculate_dispatch_weight(lane, nonces.clone())
-            .await?;
+        // the messages over the limits of target runtime are rejected, and the lane is stalled
+        let limits = self
+            .input
+            .delivery_limits
+            .clone()
+            .or(&client_target.messages_delivery_limits().await?);
+        let nonces = match lane_accepted_nonces(
+            &nonces,
+            source_outbound_lane_data.latest_received_nonce,
+            &target_inbound_lane_data,
+            &limits,
+        ) {
+            Some(v) => v,
+            None => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the inbound lane of {} is full, please wait receiving.",
+                    logk::prefix_with_bridge_and_others(
+                        M_DELIVERY,
+                        SC::CHAIN,
+                        TC::CHAIN,
+                        vec![array_bytes::bytes2hex("0x", lane),],
+                    ),
+                    TC::CHAIN,
+                );
+                return Ok(DeliveryPlan::Skip(DeliveryDecision::InboundLaneFull {
+                    last_delivered_nonce: target_inbound_lane_data.last_delivered_nonce(),
+                }));
+            }
+        };
+        let first_nonce = *nonces.start();
+        let (nonces, proof, message_size, total_weight) = match self
+            .limited_messages_proof(
+                lane,
+                nonces,
+                outbound_state_proof_required,
+                expected_source_hash,
+                &limits,
+            )
+            .await?
+        {
+            Some(v) => v,
+            None => {
+                observation.over_limits_nonce = Some(first_nonce);
+                return Ok(DeliveryPlan::Skip(DeliveryDecision::OverLimits {
+                    nonce: first_nonce,
+                }));
+            }
+        };
 
-        // query last relayed  header
-        let proof = client_source
-            .read_proof(storage_keys, Some(expected_source_hash))
-            .await?;
-        let message_size = proof.len();
-        let proof = FromBridgedChainMessagesProof {
-            bridged_header_hash: expected_source_hash,
-            storage_proof: proof,
-            lane,
-            nonces_start: *nonces.start(),
-            nonces_end: *nonces.end(),
+        // the strategy falls back to its own estimation if the fee can't be queried, the proof
+        // at a bundled header can't be checked before the header is imported
+        let estimated_fee = if finality.is_some() {
+            None
+        } else {
+            match self
+                .estimate_delivery_fee(&nonces, &proof, total_weight)
+                .await
+            {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::warn!(
+                        target: "relay-s2s",
+                        "{} failed to estimate delivery fee of nonces {:?}: {:?}",
+                        logk::prefix_with_bridge_and_others(
+                            M_DELIVERY,
+                            SC::CHAIN,
+                            TC::CHAIN,
+                            vec![array_bytes::bytes2hex("0x", lane),],
+                        ),
+                        nonces,
+                        e,
+                    );
+                    None
+                }
+            }
         };
 
         // relay strategy
@@ -295,48 +933,113 @@ where
             nonces: nonces.clone(),
             message_size,
             total_weight,
+            estimated_fee,
         };
         let mut relay_strategy = EnforcementRelayStrategy::new(self.input.relay_strategy.clone());
-        if !relay_strategy.decide(reference).await? {
-            tracing::warn!(
-                target: "relay-s2s",
-                "{} the relay strategy decide not relay these nonces({:?})",
-                logk::prefix_with_bridge_and_others(
-                    M_DELIVERY,
-                    SC::CHAIN,
-                    TC::CHAIN,
-                    vec![array_bytes::bytes2hex("0x", lane),],
-                ),
+        let accepted_nonces = match relay_strategy.decide(reference).await? {
+            Some(v) => v,
+            None => {
+                support_metrics::metrics::strategy_decision(SC::CHAIN, TC::CHAIN, "refused");
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the relay strategy decide not relay these nonces({:?})",
+                    logk::prefix_with_bridge_and_others(
+                        M_DELIVERY,
+                        SC::CHAIN,
+                        TC::CHAIN,
+                        vec![array_bytes::bytes2hex("0x", lane),],
+                    ),
+                    nonces,
+                );
+                return Ok(DeliveryPlan::Skip(DeliveryDecision::StrategyRefused {
+                    nonces,
+                }));
+            }
+        };
+
+        // only relay the accepted nonces, the proof should be regenerated
+        if accepted_nonces == nonces {
+            support_metrics::metrics::strategy_decision(SC::CHAIN, TC::CHAIN, "accepted");
+            return Ok(DeliveryPlan::Relay {
                 nonces,
-            );
-            return Ok(None);
+                proof,
+                total_weight,
+                finality,
+            });
         }
-
-        // submit messages proof to target chain
-        let expected_proof = SmartCodecMapper::map_to(&proof)?;
-        let relayer_account_source_chain = self.input.relayer_account.clone();
-        let expected_relayer_id = SmartCodecMapper::map_to(&relayer_account_source_chain)?;
-        let hash = client_target
-            .receive_messages_proof(
-                expected_relayer_id,
-                expected_proof,
-                (nonces.end() - nonces.start() + 1) as _,
-                Weight::from_ref_time(total_weight),
-            )
-            .await?;
-
         tracing::info!(
             target: "relay-s2s",
-            "{} the nonces {:?} in delivered to target chain -> {}",
+            "{} the relay strategy decide only relay nonces {:?} of {:?}",
             logk::prefix_with_bridge_and_others(
                 M_DELIVERY,
                 SC::CHAIN,
                 TC::CHAIN,
                 vec![array_bytes::bytes2hex("0x", lane),],
             ),
+            accepted_nonces,
             nonces,
-            array_bytes::bytes2hex("0x", hash),
         );
-        Ok(Some(*nonces.end()))
+        support_metrics::metrics::strategy_decision(SC::CHAIN, TC::CHAIN, "partially-accepted");
+        let (proof, _, total_weight) = self
+            .messages_proof(
+                lane,
+                accepted_nonces.clone(),
+                outbound_state_proof_required,
+                expected_source_hash,
+            )
+            .await?;
+        Ok(DeliveryPlan::Relay {
+            nonces: accepted_nonces,
+            proof,
+            total_weight,
+            finality,
+        })
+    }
+}
+
+/// Nonces which can be received by the inbound lane of target chain, the unconfirmed messages
+/// and unrewarded relayer entries are counted after the confirmations carried by the proof.
+/// It returns none if no more messages can be received.
+pub(super) fn lane_accepted_nonces<AccountId>(
+    nonces: &RangeInclusive<u64>,
+    source_latest_received_nonce: u64,
+    inbound_lane_data: &InboundLaneData<AccountId>,
+    limits: &MessagesDeliveryLimits,
+) -> Option<RangeInclusive<u64>> {
+    let last_confirmed_nonce = inbound_lane_data
+        .last_confirmed_nonce
+        .max(source_latest_received_nonce);
+    let relayer_entries = inbound_lane_data
+        .relayers
+        .iter()
+        .filter(|entry| entry.messages.end > last_confirmed_nonce)
+        .count() as u64;
+    if matches!(limits.max_unrewarded_relayer_entries, Some(max) if relayer_entries >= max) {
+        return None;
     }
+    let end = match limits.max_unconfirmed_messages {
+        Some(max) => (*nonces.end()).min(last_confirmed_nonce.saturating_add(max)),
+        None => *nonces.end(),
+    };
+    (end >= *nonces.start()).then_some(*nonces.start()..=end)
+}
+
+/// Header and nonce blocks seen while planning delivery
+#[derive(Default)]
+struct DeliveryObservation {
+    last_relayed_block: Option<u32>,
+    nonce_block: Option<u32>,
+    last_delivered_nonce: Option<u64>,
+    over_limits_nonce: Option<u64>,
+}
+
+enum DeliveryPlan<Hash, F> {
+    Skip(DeliveryDecision),
+    Relay {
+        nonces: RangeInclusive<u64>,
+        proof: FromBridgedChainMessagesProof<Hash>,
+        total_weight: u64,
+        /// header submitted with the proof, the proof is read at this header
+        finality: Option<F>,
+    },
 }
@@ -3,3 +3,6 @@ pub use receiving_relay::*;
 
 mod delivery_relay;
 mod receiving_relay;
+
+#[cfg(test)]
+mod tests;
@@ -1,19 +1,32 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use bridge_s2s_traits::client::S2SClientRelay;
 use bridge_s2s_traits::types::bp_messages::{OutboundLaneData, UnrewardedRelayersState};
 use bridge_s2s_traits::types::bp_runtime::Chain;
 use bridge_s2s_traits::types::bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof;
+use bridge_s2s_traits::types::ExtrinsicLocation;
+use sp_runtime::codec;
+use sp_runtime::traits::Header;
 
 use support_toolkit::{convert::SmartCodecMapper, logk};
 
-use crate::error::RelayResult;
-use crate::keepstate;
+use crate::error::{RelayError, RelayResult};
+use crate::header::common::{prepare_finality, Finality};
+use crate::keepstate::RelayedNonce;
 use crate::special::DifferentClientApi;
+use crate::subscribe::FinalityWakeup;
 use crate::types::{LaneId, MessageReceivingInput, M_RECEIVING};
 
 pub struct CommonReceivingRunner<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
 {
     different: DC,
     input: MessageReceivingInput<SC, TC>,
+    /// latest received nonce of lane and the instant the runner first saw unconfirmed
+    /// messages after it, it's reset when the received nonce changes
+    unconfirmed_since: Mutex<HashMap<LaneId, (u64, Instant)>>,
 }
 
 impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
@@ -23,6 +36,7 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
         Self {
             different,
             input: message_relay,
+            unconfirmed_since: Mutex::new(HashMap::new()),
         }
     }
 }
@@ -40,7 +54,7 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
         lane: LaneId,
         at_block: <TC::Chain as Chain>::Hash,
         source_outbound_lane_data: &OutboundLaneData,
-    ) -> RelayResult<Option<(u64, UnrewardedRelayersState)>> {
+    ) -> RelayResult<Option<(u64, UnrewardedRelayersState, bool)>> {
         let block_hex = array_bytes::bytes2hex("0x", at_block);
         let inbound_lane_data = self
             .input
@@ -65,8 +79,12 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
             );
             return Ok(None);
         }
-        if let Some(last_relayed_nonce) = keepstate::get_last_receiving_relayed_nonce(TC::CHAIN) {
-            if last_relayed_nonce >= max_confirm_end_at_target {
+        if let Some(last_relayed) = self
+            .input
+            .keepstate
+            .get_last_receiving_relayed_nonce(TC::CHAIN, lane)?
+        {
+            if last_relayed.nonce >= max_confirm_end_at_target {
                 tracing::warn!(
                     target: "relay-s2s",
                     "{} the nonce({}) is being processed. please waiting for the processing to finish.",
@@ -77,6 +95,9 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
             }
         }
         let relayers = inbound_lane_data.relayers;
+        let relayer_account: <TC::Chain as Chain>::AccountId =
+            SmartCodecMapper::map_to(&self.input.relayer_account)?;
+        let own_rewards_pending = relayers.iter().any(|item| item.relayer == relayer_account);
         let total_unrewarded_messages = match (relayers.front(), relayers.back()) {
             (Some(front), Some(back)) => {
                 if back.messages.end < front.messages.begin {
@@ -107,8 +128,164 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
                 total_messages: total_unrewarded_messages.expect("Unreachable"),
                 last_delivered_nonce,
             },
+            own_rewards_pending,
         )))
     }
+
+    /// Why to confirm now by the confirmation policy, it's `None` if the confirmation should
+    /// be deferred so that more messages are confirmed by one extrinsic.
+    pub(super) fn confirm_reason(
+        &self,
+        lane: LaneId,
+        latest_received_nonce: u64,
+        unconfirmed_messages: u64,
+        relayers_state: &UnrewardedRelayersState,
+        own_rewards_pending: bool,
+    ) -> Option<&'static str> {
+        let policy = &self.input.confirmation_policy;
+        if policy.is_eager() {
+            return Some("eager");
+        }
+        if matches!(policy.max_unconfirmed_messages, Some(max) if unconfirmed_messages >= max) {
+            return Some("unconfirmed-messages");
+        }
+        if matches!(
+            policy.max_unrewarded_relayer_entries,
+            Some(max) if relayers_state.unrewarded_relayer_entries >= max
+        ) {
+            return Some("unrewarded-relayer-entries");
+        }
+        if policy.own_rewards && own_rewards_pending {
+            return Some("own-rewards");
+        }
+        if let Some(max_delay_secs) = policy.max_delay_secs {
+            let mut unconfirmed_since = self.unconfirmed_since.lock().unwrap();
+            let since = unconfirmed_since
+                .entry(lane)
+                .or_insert((latest_received_nonce, Instant::now()));
+            if since.0 != latest_received_nonce {
+                *since = (latest_received_nonce, Instant::now());
+            }
+            if since.1.elapsed() >= Duration::from_secs(max_delay_secs) {
+                return Some("delay");
+            }
+        }
+        None
+    }
+
+    /// Whether the confirmation of nonce has been received by source chain
+    async fn is_received(&self, lane: LaneId, nonce: u64) -> RelayResult<bool> {
+        let source_outbound_lane_data = self.source_outbound_lane_data(lane).await?;
+        Ok(source_outbound_lane_data.latest_received_nonce >= nonce)
+    }
+
+    /// The latest target header with justification, it's bundled with the delivery proof when
+    /// the last relayed target header doesn't include new deliveries.
+    async fn bundled_finality(
+        &self,
+        relayed_block_number: u32,
+    ) -> RelayResult<Option<Finality<SC::Chain>>> {
+        if !self.input.bundle_header || !self.different.supports_header_bundle() {
+            return Ok(None);
+        }
+        let justification = match self.input.keepstate.get_recently_justification(TC::CHAIN)? {
+            Some(v) if v.block_number > relayed_block_number => v,
+            _ => return Ok(None),
+        };
+        prepare_finality(
+            &self.input.client_target,
+            &self.input.client_source,
+            &justification,
+        )
+        .await
+    }
+
+    fn report_lost_race(&self, lane: LaneId, nonce: u64, stage: &str) {
+        tracing::warn!(
+            target: "relay-s2s",
+            "{} lost race ({}), the nonce({}) has been confirmed by other relayers",
+            logk::prefix_with_bridge_and_others(
+                M_RECEIVING,
+                SC::CHAIN,
+                TC::CHAIN,
+                vec![array_bytes::bytes2hex("0x", lane),],
+            ),
+            stage,
+            nonce,
+        );
+        support_metrics::metrics::lost_race(SC::CHAIN, "receive_messages_delivery_proof", stage);
+    }
+
+    /// The relayed nonce stored before restart may be confirmed, pending or dropped by source
+    /// chain. It's cleared only if the extrinsic is neither confirmed nor pending in transaction
+    /// pool, so that this confirmation can be relayed again.
+    pub(super) async fn check_pending_relayed_nonce(&self, lane: LaneId) -> RelayResult<()> {
+        let keepstate = &self.input.keepstate;
+        let pending = match keepstate.get_last_receiving_relayed_nonce(TC::CHAIN, lane)? {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        let extrinsic_hash = pending.extrinsic_hash.trim_start_matches("0x");
+        let extrinsic_hash = sp_core::H256::from_str(extrinsic_hash).map_err(|e| {
+            RelayError::Custom(format!("Wrong extrinsic hash [{extrinsic_hash}] {e:?}"))
+        })?;
+        // look up the extrinsic before reading the lane, so that the lane reflects the
+        // extrinsic if it's included meanwhile
+        let location = self
+            .input
+            .client_source
+            .find_extrinsic(SmartCodecMapper::map_to(&extrinsic_hash)?)
+            .await?;
+        let source_outbound_lane_data = self.source_outbound_lane_data(lane).await?;
+        let logk_prefix = logk::prefix_with_bridge_and_others(
+            M_RECEIVING,
+            SC::CHAIN,
+            TC::CHAIN,
+            vec![array_bytes::bytes2hex("0x", lane)],
+        );
+        if source_outbound_lane_data.latest_received_nonce >= pending.nonce {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} the pending nonce({}) sent by {} has been finalized",
+                logk_prefix,
+                pending.nonce,
+                pending.extrinsic_hash,
+            );
+            return Ok(());
+        }
+        match location {
+            ExtrinsicLocation::Pool => {
+                tracing::info!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} is still in transaction pool, keep it.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                );
+                return Ok(());
+            }
+            ExtrinsicLocation::Block(block_hash) => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} is included by block {:?} but not confirmed, it was failed.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                    block_hash,
+                );
+            }
+            ExtrinsicLocation::NotFound => {
+                tracing::warn!(
+                    target: "relay-s2s",
+                    "{} the pending nonce({}) sent by {} not found in source chain, it was dropped.",
+                    logk_prefix,
+                    pending.nonce,
+                    pending.extrinsic_hash,
+                );
+            }
+        }
+        keepstate.remove_last_receiving_relayed_nonce(TC::CHAIN, lane)
+    }
 }
 
 impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
@@ -120,21 +297,26 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
             "{} SERVICE RESTARTING...",
             logk::prefix_with_bridge(M_RECEIVING, SC::CHAIN, TC::CHAIN),
         );
+        for lane in &self.input.lanes {
+            self.check_pending_relayed_nonce(*lane).await?;
+        }
+        let mut wakeup = FinalityWakeup::new(&self.input.keepstate, vec![SC::CHAIN, TC::CHAIN]);
         loop {
             for lane in &self.input.lanes {
-                let last_relayed_nonce = self.run(*lane).await?;
-                if last_relayed_nonce.is_some() {
-                    keepstate::set_last_receiving_relayed_nonce(
+                let last_relayed = self.run(*lane).await?;
+                if let Some(relayed) = last_relayed {
+                    self.input.keepstate.set_last_receiving_relayed_nonce(
                         TC::CHAIN,
-                        last_relayed_nonce.expect("Unreachable"),
-                    );
+                        *lane,
+                        relayed,
+                    )?;
                 }
-                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
             }
+            wakeup.wait().await;
         }
     }
 
-    async fn run(&self, lane: LaneId) -> RelayResult<Option<u64>> {
+    async fn run(&self, lane: LaneId) -> RelayResult<Option<RelayedNonce>> {
         // alias
         let client_source = &self.input.client_source;
         let client_target = &self.input.client_target;
@@ -173,34 +355,108 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
                     return Ok(None);
                 }
             };
-        let expected_target_hash =
-            SmartCodecMapper::map_to(&last_relayed_target_block_in_source.1)?;
+        let relayed_block_number: u32 =
+            SmartCodecMapper::map_to(&last_relayed_target_block_in_source.0)?;
+        let mut bridged_header_hash = last_relayed_target_block_in_source.1;
 
         // assemble unrewarded relayers state
-        let (max_confirmed_nonce_at_target, relayers_state) = match self
+        let mut unrewarded_relayers_state = self
             .target_unrewarded_relayers_state(
                 lane,
-                expected_target_hash,
+                SmartCodecMapper::map_to(&bridged_header_hash)?,
                 &source_outbound_lane_data,
             )
-            .await?
-        {
-            Some(v) => v,
+            .await?;
+        let mut finality = None;
+        if unrewarded_relayers_state.is_none() {
+            if let Some(bundled) = self.bundled_finality(relayed_block_number).await? {
+                let bundled_header_hash = SmartCodecMapper::map_to(&bundled.0.hash())?;
+                unrewarded_relayers_state = self
+                    .target_unrewarded_relayers_state(
+                        lane,
+                        SmartCodecMapper::map_to(&bundled_header_hash)?,
+                        &source_outbound_lane_data,
+                    )
+                    .await?;
+                if unrewarded_relayers_state.is_some() {
+                    tracing::info!(
+                        target: "relay-s2s",
+                        "{} bundle header {:?} with delivery proof, the last relayed header is {}",
+                        logk::prefix_with_bridge_and_others(
+                            M_RECEIVING,
+                            SC::CHAIN,
+                            TC::CHAIN,
+                            vec![array_bytes::bytes2hex("0x", lane),],
+                        ),
+                        bundled.0.number(),
+                        relayed_block_number,
+                    );
+                    bridged_header_hash = bundled_header_hash;
+                    finality = Some(bundled);
+                }
+            }
+        }
+        let expected_target_hash = SmartCodecMapper::map_to(&bridged_header_hash)?;
+        let (max_confirmed_nonce_at_target, relayers_state, own_rewards_pending) =
+            match unrewarded_relayers_state {
+                Some(v) => v,
+                None => {
+                    tracing::warn!(
+                        target: "relay-s2s",
+                        "{} no unrewarded relayers state found by {}",
+                        logk::prefix_with_bridge_and_others(
+                            M_RECEIVING,
+                            SC::CHAIN,
+                            TC::CHAIN,
+                            vec![array_bytes::bytes2hex("0x", lane),],
+                        ),
+                        TC::CHAIN,
+                    );
+                    return Ok(None);
+                }
+            };
+
+        // defer by confirmation policy, the later confirmation covers these messages as well
+        let latest_received_nonce = source_outbound_lane_data.latest_received_nonce;
+        let unconfirmed_messages =
+            max_confirmed_nonce_at_target.saturating_sub(latest_received_nonce);
+        match self.confirm_reason(
+            lane,
+            latest_received_nonce,
+            unconfirmed_messages,
+            &relayers_state,
+            own_rewards_pending,
+        ) {
+            Some(reason) => {
+                tracing::debug!(
+                    target: "relay-s2s",
+                    "{} confirm {} unconfirmed messages, reason: {}",
+                    logk::prefix_with_bridge_and_others(
+                        M_RECEIVING,
+                        SC::CHAIN,
+                        TC::CHAIN,
+                        vec![array_bytes::bytes2hex("0x", lane),],
+                    ),
+                    unconfirmed_messages,
+                    reason,
+                );
+            }
             None => {
-                tracing::warn!(
+                tracing::info!(
                     target: "relay-s2s",
-                    "{} no unrewarded relayers state found by {}",
+                    "{} defer confirmation of {} unconfirmed messages ({} unrewarded relayer entries) by confirmation policy",
                     logk::prefix_with_bridge_and_others(
                         M_RECEIVING,
                         SC::CHAIN,
                         TC::CHAIN,
                         vec![array_bytes::bytes2hex("0x", lane),],
                     ),
-                    TC::CHAIN,
+                    unconfirmed_messages,
+                    relayers_state.unrewarded_relayer_entries,
                 );
                 return Ok(None);
             }
-        };
+        }
 
         // read proof
         let inbound_data_key = client_target.gen_inbound_lanes_storage_key(lane);
@@ -208,15 +464,69 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
             .read_proof(vec![inbound_data_key], Some(expected_target_hash))
             .await?;
         let proof = FromBridgedChainMessagesDeliveryProof {
-            bridged_header_hash: last_relayed_target_block_in_source.1,
+            bridged_header_hash,
             storage_proof: proof,
             lane,
         };
 
+        if self.input.dry_run {
+            tracing::info!(
+                target: "relay-s2s",
+                "{} [dry-run] would submit receive_messages_delivery_proof confirming {} messages{}, encoded size: {} bytes",
+                logk::prefix_with_bridge_and_others(
+                    M_RECEIVING,
+                    SC::CHAIN,
+                    TC::CHAIN,
+                    vec![array_bytes::bytes2hex("0x", lane),],
+                ),
+                relayers_state.total_messages,
+                finality
+                    .as_ref()
+                    .map(|(header, _)| format!(" bundled with header {:?}", header.number()))
+                    .unwrap_or_default(),
+                codec::Encode::encoded_size(&proof) + codec::Encode::encoded_size(&relayers_state),
+            );
+            return Ok(None);
+        }
+
+        // other relayers may confirm the same nonces while the proof was prepared
+        if self
+            .is_received(lane, max_confirmed_nonce_at_target)
+            .await?
+        {
+            self.report_lost_race(lane, max_confirmed_nonce_at_target, "before-submit");
+            return Ok(None);
+        }
+
         // send proof
-        let hash = client_source
-            .receive_messages_delivery_proof(proof, relayers_state)
-            .await?;
+        let (call, result) = match finality {
+            Some(finality) => (
+                "receive_messages_delivery_proof_with_finality",
+                client_source
+                    .receive_messages_delivery_proof_with_finality(finality, proof, relayers_state)
+                    .await,
+            ),
+            None => (
+                "receive_messages_delivery_proof",
+                client_source
+                    .receive_messages_delivery_proof(proof, relayers_state)
+                    .await,
+            ),
+        };
+        support_metrics::metrics::record_extrinsic(SC::CHAIN, call, &result);
+        let hash = match result {
+            Ok(v) => v,
+            Err(e) => {
+                if self
+                    .is_received(lane, max_confirmed_nonce_at_target)
+                    .await?
+                {
+                    self.report_lost_race(lane, max_confirmed_nonce_at_target, "after-inclusion");
+                    return Ok(None);
+                }
+                return Err(e.into());
+            }
+        };
 
         tracing::info!(
             target: "relay-s2s",
@@ -229,6 +539,9 @@ impl<SC: S2SClientRelay, TC: S2SClientRelay, DC: DifferentClientApi<SC>>
             ),
             array_bytes::bytes2hex("0x", hash),
         );
-        Ok(Some(max_confirmed_nonce_at_target))
+        Ok(Some(RelayedNonce {
+            nonce: max_confirmed_nonce_at_target,
+            extrinsic_hash: array_bytes::bytes2hex("0x", hash),
+        }))
     }
 }
@@ -0,0 +1,422 @@
+use bridge_s2s_traits::client::S2SClientRelay;
+use bridge_s2s_traits::strategy::AlwaysRelayStrategy;
+use bridge_s2s_traits::types::bp_messages::{
+    InboundLaneData, OutboundLaneData, UnrewardedRelayersState,
+};
+use bridge_s2s_traits::types::MessagesDeliveryLimits;
+use client_common_traits::ClientCommon;
+use client_mock::client::MockClient;
+use client_mock::types::{MockCall, MockChainInfo, MockSourceChain, MockTargetChain};
+use sp_runtime::codec::Compact;
+use subquery::types::{BridgeName, OriginType};
+use subquery::{Subquery, SubqueryComponent, SubqueryConfig};
+
+use support_toolkit::convert::SmartCodecMapper;
+
+use crate::keepstate::{Keepstate, RelayedNonce};
+use crate::message::common::delivery_relay::lane_accepted_nonces;
+use crate::message::common::{CommonDeliveryRunner, CommonReceivingRunner};
+use crate::special::SolochainSpecialClientApi;
+use crate::types::{ConfirmationPolicy, LaneId, MessageDeliveryInput, MessageReceivingInput};
+
+type SourceClient = MockClient<MockSourceChain>;
+type TargetClient = MockClient<MockTargetChain>;
+
+fn set_outbound_lane<M: MockChainInfo>(
+    client: &MockClient<M>,
+    lane: LaneId,
+    received: u64,
+    generated: u64,
+) {
+    client.state().storage.outbound_lanes.insert(
+        lane,
+        OutboundLaneData {
+            oldest_unpruned_nonce: 1,
+            latest_received_nonce: received,
+            latest_generated_nonce: generated,
+        },
+    );
+}
+
+/// The messages after the last confirmed nonce are delivered by one unrewarded relayer entry
+fn set_last_delivered_nonce<M: MockChainInfo>(
+    client: &MockClient<M>,
+    lane: LaneId,
+    last_delivered_nonce: u64,
+) {
+    let mut state = client.state();
+    let inbound_lane_data = state.storage.inbound_lanes.entry(lane).or_default();
+    inbound_lane_data.relayers.clear();
+    let last_confirmed_nonce = inbound_lane_data.last_confirmed_nonce;
+    if last_delivered_nonce > last_confirmed_nonce {
+        inbound_lane_data.relayers.push_back(
+            SmartCodecMapper::map_to(&(
+                [0u8; 32],
+                last_confirmed_nonce + 1,
+                last_delivered_nonce,
+                Compact(0u32),
+            ))
+            .unwrap(),
+        );
+    }
+    assert_eq!(
+        inbound_lane_data.last_delivered_nonce(),
+        last_delivered_nonce
+    );
+}
+
+fn subquery() -> Subquery {
+    let config = SubqueryConfig {
+        endpoint: "http://127.0.0.1".to_string(),
+    };
+    SubqueryComponent::component(config, BridgeName::PangolinPangoro)
+}
+
+fn receiving_input(
+    lanes: Vec<LaneId>,
+    client_source: SourceClient,
+    client_target: TargetClient,
+    keepstate: Keepstate,
+) -> MessageReceivingInput<SourceClient, TargetClient> {
+    MessageReceivingInput {
+        lanes,
+        relayer_account: SmartCodecMapper::map_to(&[0u8; 32]).unwrap(),
+        client_source,
+        client_target,
+        subquery_source: subquery().into(),
+        subquery_target: subquery().into(),
+        bundle_header: false,
+        confirmation_policy: ConfirmationPolicy::default(),
+        dry_run: false,
+        keepstate,
+    }
+}
+
+fn delivery_runner(
+    lanes: Vec<LaneId>,
+    client_source: SourceClient,
+    client_target: TargetClient,
+    keepstate: Keepstate,
+) -> CommonDeliveryRunner<
+    SourceClient,
+    TargetClient,
+    SolochainSpecialClientApi<TargetClient>,
+    AlwaysRelayStrategy,
+> {
+    let input = receiving_input(lanes, client_source, client_target.clone(), keepstate);
+    let input = MessageDeliveryInput {
+        lanes: input.lanes,
+        nonces_limit: 11,
+        relayer_account: input.relayer_account,
+        client_source: input.client_source,
+        client_target: input.client_target,
+        subquery_source: input.subquery_source,
+        subquery_target: input.subquery_target,
+        relay_block_origin: OriginType::BridgePangoro,
+        relay_strategy: AlwaysRelayStrategy,
+        bundle_header: input.bundle_header,
+        dry_run: input.dry_run,
+        delivery_limits: Default::default(),
+        keepstate: input.keepstate,
+    };
+    let different = SolochainSpecialClientApi {
+        client: client_target,
+    };
+    CommonDeliveryRunner::new(input, different)
+}
+
+fn relayed(nonce: u64) -> RelayedNonce {
+    RelayedNonce {
+        nonce,
+        extrinsic_hash: array_bytes::bytes2hex("0x", [0u8; 32]),
+    }
+}
+
+#[tokio::test]
+async fn test_delivery_keepstate_per_lane() {
+    let (lane_a, lane_b) = ([0, 0, 0, 1], [0, 0, 0, 2]);
+    let source = SourceClient::new();
+    set_outbound_lane(&source, lane_a, 2, 10);
+    set_outbound_lane(&source, lane_b, 0, 3);
+    let keepstate = Keepstate::memory();
+    let runner = delivery_runner(
+        vec![lane_a, lane_b],
+        source.clone(),
+        TargetClient::new(),
+        keepstate.clone(),
+    );
+
+    // lane a delivered nonces to 5, but not received yet
+    keepstate
+        .set_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_a, relayed(5))
+        .unwrap();
+
+    let outbound_a = source.outbound_lanes(lane_a, None).await.unwrap();
+    let nonces_a = runner
+        .assemble_nonces(lane_a, 11, &outbound_a)
+        .await
+        .unwrap();
+    assert_eq!(nonces_a, None);
+
+    // lane b is not affected by lane a
+    let outbound_b = source.outbound_lanes(lane_b, None).await.unwrap();
+    let nonces_b = runner
+        .assemble_nonces(lane_b, 11, &outbound_b)
+        .await
+        .unwrap();
+    assert_eq!(nonces_b, Some(1..=3));
+    assert_eq!(
+        keepstate
+            .get_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_a)
+            .unwrap(),
+        Some(relayed(5))
+    );
+}
+
+#[tokio::test]
+async fn test_delivery_pending_nonce_per_lane() {
+    let (lane_a, lane_b) = ([0, 0, 1, 1], [0, 0, 1, 2]);
+    let target = TargetClient::new();
+    set_last_delivered_nonce(&target, lane_a, 5);
+    set_last_delivered_nonce(&target, lane_b, 0);
+    let keepstate = Keepstate::memory();
+    let runner = delivery_runner(
+        vec![lane_a, lane_b],
+        SourceClient::new(),
+        target,
+        keepstate.clone(),
+    );
+    keepstate
+        .set_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_a, relayed(5))
+        .unwrap();
+    keepstate
+        .set_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_b, relayed(3))
+        .unwrap();
+
+    runner.check_pending_relayed_nonce(lane_a).await.unwrap();
+    runner.check_pending_relayed_nonce(lane_b).await.unwrap();
+
+    // lane a finalized, lane b dropped
+    assert_eq!(
+        keepstate
+            .get_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_a)
+            .unwrap(),
+        Some(relayed(5))
+    );
+    assert_eq!(
+        keepstate
+            .get_last_delivery_relayed_nonce(SourceClient::CHAIN, lane_b)
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_delivery_pending_nonce_lookup() {
+    let lane = [0, 0, 1, 3];
+    let target = TargetClient::new();
+    let keepstate = Keepstate::memory();
+    let runner = delivery_runner(
+        vec![lane],
+        SourceClient::new(),
+        target.clone(),
+        keepstate.clone(),
+    );
+
+    // the extrinsic is still in transaction pool
+    let pooled = sp_core::H256([1u8; 32]);
+    target.state().pending_extrinsics.push(pooled);
+    let pending = RelayedNonce {
+        nonce: 3,
+        extrinsic_hash: array_bytes::bytes2hex("0x", pooled),
+    };
+    keepstate
+        .set_last_delivery_relayed_nonce(SourceClient::CHAIN, lane, pending.clone())
+        .unwrap();
+    runner.check_pending_relayed_nonce(lane).await.unwrap();
+    assert_eq!(
+        keepstate
+            .get_last_delivery_relayed_nonce(SourceClient::CHAIN, lane)
+            .unwrap(),
+        Some(pending)
+    );
+
+    // the extrinsic is included but the nonce is not delivered
+    let included = target
+        .state()
+        .include_extrinsic(MockCall::UpdateRelayFee { fee: 1 });
+    keepstate
+        .set_last_delivery_relayed_nonce(
+            SourceClient::CHAIN,
+            lane,
+            RelayedNonce {
+                nonce: 3,
+                extrinsic_hash: array_bytes::bytes2hex("0x", included),
+            },
+        )
+        .unwrap();
+    runner.check_pending_relayed_nonce(lane).await.unwrap();
+    assert_eq!(
+        keepstate
+            .get_last_delivery_relayed_nonce(SourceClient::CHAIN, lane)
+            .unwrap(),
+        None
+    );
+}
+
+#[tokio::test]
+async fn test_receiving_pending_nonce_per_lane() {
+    let (lane_a, lane_b) = ([0, 0, 2, 1], [0, 0, 2, 2]);
+    let source = SourceClient::new();
+    set_outbound_lane(&source, lane_a, 2, 10);
+    set_outbound_lane(&source, lane_b, 7, 7);
+    let keepstate = Keepstate::memory();
+    let input = receiving_input(
+        vec![lane_a, lane_b],
+        source.clone(),
+        TargetClient::new(),
+        keepstate.clone(),
+    );
+    let different = SolochainSpecialClientApi { client: source };
+    let runner = CommonReceivingRunner::new(input, different);
+    keepstate
+        .set_last_receiving_relayed_nonce(TargetClient::CHAIN, lane_a, relayed(4))
+        .unwrap();
+    keepstate
+        .set_last_receiving_relayed_nonce(TargetClient::CHAIN, lane_b, relayed(7))
+        .unwrap();
+
+    runner.check_pending_relayed_nonce(lane_a).await.unwrap();
+    runner.check_pending_relayed_nonce(lane_b).await.unwrap();
+
+    // lane a dropped, lane b finalized
+    assert_eq!(
+        keepstate
+            .get_last_receiving_relayed_nonce(TargetClient::CHAIN, lane_a)
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        keepstate
+            .get_last_receiving_relayed_nonce(TargetClient::CHAIN, lane_b)
+            .unwrap(),
+        Some(relayed(7))
+    );
+}
+
+fn unrewarded_relayers_state(entries: u64) -> UnrewardedRelayersState {
+    UnrewardedRelayersState {
+        unrewarded_relayer_entries: entries,
+        messages_in_oldest_entry: 1,
+        total_messages: entries,
+        last_delivered_nonce: entries,
+    }
+}
+
+#[test]
+fn test_receiving_confirmation_policy() {
+    let lane = [0, 0, 3, 1];
+    let runner = |confirmation_policy| {
+        let mut input = receiving_input(
+            vec![lane],
+            SourceClient::new(),
+            TargetClient::new(),
+            Keepstate::memory(),
+        );
+        input.confirmation_policy = confirmation_policy;
+        let different = SolochainSpecialClientApi {
+            client: SourceClient::new(),
+        };
+        CommonReceivingRunner::new(input, different)
+    };
+
+    // confirm as soon as delivered by default
+    let eager = runner(ConfirmationPolicy::default());
+    assert_eq!(
+        eager.confirm_reason(lane, 0, 1, &unrewarded_relayers_state(1), false),
+        Some("eager")
+    );
+
+    let lazy = runner(ConfirmationPolicy {
+        max_unconfirmed_messages: Some(5),
+        max_unrewarded_relayer_entries: Some(3),
+        max_delay_secs: Some(3600),
+        own_rewards: true,
+    });
+    assert_eq!(
+        lazy.confirm_reason(lane, 0, 4, &unrewarded_relayers_state(2), false),
+        None
+    );
+    assert_eq!(
+        lazy.confirm_reason(lane, 0, 5, &unrewarded_relayers_state(2), false),
+        Some("unconfirmed-messages")
+    );
+    assert_eq!(
+        lazy.confirm_reason(lane, 0, 4, &unrewarded_relayers_state(3), false),
+        Some("unrewarded-relayer-entries")
+    );
+    assert_eq!(
+        lazy.confirm_reason(lane, 0, 4, &unrewarded_relayers_state(2), true),
+        Some("own-rewards")
+    );
+
+    let delayed = runner(ConfirmationPolicy {
+        max_delay_secs: Some(0),
+        ..Default::default()
+    });
+    assert_eq!(
+        delayed.confirm_reason(lane, 0, 1, &unrewarded_relayers_state(1), false),
+        Some("delay")
+    );
+}
+
+#[test]
+fn test_delivery_lane_accepted_nonces() {
+    // messages 1..=4 are delivered by two relayers, none of them is confirmed
+    let inbound_lane_data: InboundLaneData<[u8; 32]> = InboundLaneData {
+        relayers: vec![
+            SmartCodecMapper::map_to(&([1u8; 32], 1u64, 2u64, Compact(0u32))).unwrap(),
+            SmartCodecMapper::map_to(&([2u8; 32], 3u64, 4u64, Compact(0u32))).unwrap(),
+        ]
+        .into(),
+        last_confirmed_nonce: 0,
+    };
+    let nonces = 5..=10;
+
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 0, &inbound_lane_data, &Default::default()),
+        Some(5..=10)
+    );
+
+    let limits = MessagesDeliveryLimits {
+        max_unconfirmed_messages: Some(8),
+        max_unrewarded_relayer_entries: Some(2),
+        ..Default::default()
+    };
+    // the lane is full of unrewarded relayer entries
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 0, &inbound_lane_data, &limits),
+        None
+    );
+    // the first entry is confirmed by the proof, and unconfirmed messages are limited
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 2, &inbound_lane_data, &limits),
+        Some(5..=10)
+    );
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 1, &inbound_lane_data, &limits),
+        None
+    );
+    let limits = MessagesDeliveryLimits {
+        max_unconfirmed_messages: Some(6),
+        ..Default::default()
+    };
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 2, &inbound_lane_data, &limits),
+        Some(5..=8)
+    );
+    assert_eq!(
+        lane_accepted_nonces(&nonces, 0, &inbound_lane_data, &limits),
+        Some(5..=6)
+    );
+}
@@ -38,6 +38,11 @@ impl<T: S2SParaBridgeClientSolochain> DifferentClientApi<T> for ParachainSpecial
             None => Ok(Default::default()),
         }
     }
+
+    /// para heads are relayed with proofs of relay chain storage, not by grandpa finality
+    fn supports_header_bundle(&self) -> bool {
+        false
+    }
 }
 
 pub struct SolochainSpecialClientApi<T: S2SClientRelay> {
@@ -53,4 +58,8 @@ impl<T: S2SClientRelay> DifferentClientApi<T> for SolochainSpecialClientApi<T> {
     {
         self.client.best_target_finalized(at_block).await
     }
+
+    fn supports_header_bundle(&self) -> bool {
+        true
+    }
 }
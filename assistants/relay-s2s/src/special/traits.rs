@@ -9,4 +9,7 @@ pub trait DifferentClientApi<T: S2SClientRelay> {
         &self,
         at_block: Option<<T::Chain as Chain>::Hash>,
     ) -> S2SClientResult<Option<(<T::Chain as Chain>::BlockNumber, <T::Chain as Chain>::Hash)>>;
+
+    /// whether the header can be submitted with message proofs in one extrinsic
+    fn supports_header_bundle(&self) -> bool;
 }
@@ -1,7 +1,7 @@
 use std::ops::RangeInclusive;
 
-use bridge_s2s_traits::error::S2SClientResult;
-use bridge_s2s_traits::strategy::{RelayReference, RelayStrategy};
+use bridge_s2s_traits::error::{S2SClientError, S2SClientResult};
+use bridge_s2s_traits::strategy::{BatchRelayReference, BatchRelayStrategy};
 
 use crate::types::LaneId;
 
@@ -10,37 +10,52 @@ pub struct EnforcementDecideReference {
     pub lane: LaneId,
     /// nonces
     pub nonces: RangeInclusive<u64>,
-    /// message size
+    /// message size, the bytes of storage proof
     pub message_size: usize,
     /// total weight
     pub total_weight: u64,
+    /// fee estimated by pre-flight check
+    pub estimated_fee: Option<u128>,
 }
 
-pub struct EnforcementRelayStrategy<Strategy: RelayStrategy> {
+pub struct EnforcementRelayStrategy<Strategy: BatchRelayStrategy> {
     strategy: Strategy,
 }
 
-impl<Strategy: RelayStrategy> EnforcementRelayStrategy<Strategy> {
+impl<Strategy: BatchRelayStrategy> EnforcementRelayStrategy<Strategy> {
     pub fn new(strategy: Strategy) -> Self {
         Self { strategy }
     }
 }
 
-impl<Strategy: RelayStrategy> EnforcementRelayStrategy<Strategy> {
-    pub async fn decide(&mut self, reference: EnforcementDecideReference) -> S2SClientResult<bool> {
-        let nonces = &reference.nonces;
-        let mut message_nonce = *nonces.start();
-        while message_nonce <= *nonces.end() {
-            let decide_reference = RelayReference {
-                lane: reference.lane,
-                nonce: message_nonce,
-            };
-            let result = self.strategy.decide(decide_reference).await?;
-            if !result {
-                return Ok(false);
-            }
-            message_nonce += 1;
+impl<Strategy: BatchRelayStrategy> EnforcementRelayStrategy<Strategy> {
+    /// decide nonces to relay, the accepted nonces must be a prefix of the reference nonces,
+    /// because messages of a lane can only be delivered in order.
+    pub async fn decide(
+        &mut self,
+        reference: EnforcementDecideReference,
+    ) -> S2SClientResult<Option<RangeInclusive<u64>>> {
+        let nonces = reference.nonces.clone();
+        let batch_reference = BatchRelayReference {
+            lane: reference.lane,
+            nonces: reference.nonces,
+            message_size: reference.message_size,
+            total_weight: reference.total_weight,
+            estimated_fee: reference.estimated_fee,
+        };
+        let accepted = match self.strategy.decide_batch(batch_reference).await? {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        if accepted.start() != nonces.start() || accepted.end() > nonces.end() {
+            return Err(S2SClientError::Custom(format!(
+                "The relay strategy accepted nonces {:?} isn't a prefix of {:?}",
+                accepted, nonces,
+            )));
         }
-        Ok(true)
+        if accepted.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(accepted))
     }
 }
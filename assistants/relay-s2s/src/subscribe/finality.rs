@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use bridge_s2s_traits::client::S2SClientGeneric;
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use sp_runtime::traits::Header;
+use subxt::rpc::Subscription;
+
+use support_toolkit::logk;
+
+use crate::error::{RelayError, RelayResult};
+use crate::keepstate::Keepstate;
+use crate::subscribe::justification::{INITIAL_BACKOFF, MAX_BACKOFF, SUBSCRIBE_TIMEOUT};
+use crate::types::FinalityInput;
+
+/// Subscribe finalized heads of one chain, the runners which depend on this chain are woken
+/// by them. Parachains have no justification, it's the only finality notification of them.
+pub struct SubscribeFinality<C: S2SClientGeneric> {
+    input: FinalityInput<C>,
+}
+
+impl<C: S2SClientGeneric> SubscribeFinality<C> {
+    pub fn new(input: FinalityInput<C>) -> Self {
+        Self { input }
+    }
+}
+
+impl<C: S2SClientGeneric> SubscribeFinality<C> {
+    /// Subscribe finalized heads forever, resubscribe with backoff when the subscription is lost
+    pub async fn start(self) -> RelayResult<()> {
+        let client = &self.input.client;
+        let keepstate = &self.input.keepstate;
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            let mut received = 0;
+            let reason = match subscribe_finality(client, keepstate, &mut received).await {
+                Ok(()) => "the subscription has been terminated".to_string(),
+                Err(err) => format!("{err:?}"),
+            };
+            if received > 0 {
+                backoff = INITIAL_BACKOFF;
+            }
+            tracing::warn!(
+                target: "relay-s2s",
+                "{} lost finalized heads subscription of {}: {}, resubscribe after {}s",
+                logk::prefix_multi("subscribe", vec![C::CHAIN]),
+                C::CHAIN,
+                reason,
+                backoff.as_secs(),
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+}
+
+/// Read finalized heads until the subscription ends, it returns error if the subscription
+/// failed or stayed silent too long
+async fn subscribe_finality<T: S2SClientGeneric>(
+    client: &T,
+    keepstate: &Keepstate,
+    received: &mut usize,
+) -> RelayResult<()> {
+    let mut subscribe = client.subscribe_finalized_heads().await?;
+    while let Some(header) = safe_read_header::<T>(SUBSCRIBE_TIMEOUT, &mut subscribe).await? {
+        tracing::trace!(
+            target: "relay-s2s",
+            "{} subscribed new finalized head of {} at block {}",
+            logk::prefix_multi("subscribe", vec![T::CHAIN]),
+            T::CHAIN,
+            header.number(),
+        );
+        *received += 1;
+        keepstate.notify_finality(T::CHAIN);
+    }
+    Ok(())
+}
+
+async fn safe_read_header<T: S2SClientGeneric>(
+    timeout: Duration,
+    subscribe: &mut Subscription<<T::Chain as Chain>::Header>,
+) -> RelayResult<Option<<T::Chain as Chain>::Header>> {
+    let timeout = tokio::time::sleep(timeout);
+    tokio::select! {
+        res = subscribe.next() => res.transpose().map_err(RelayError::Subxt),
+        _ = timeout => Err(RelayError::Custom("subscribe timeout".to_string()))
+    }
+}
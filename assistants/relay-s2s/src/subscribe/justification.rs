@@ -1,15 +1,24 @@
 use std::time::Duration;
 
 use bridge_s2s_traits::client::S2SClientGeneric;
-use bridge_s2s_traits::error::S2SClientError;
+use bridge_s2s_traits::types::bp_header_chain;
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use sp_runtime::codec;
 use subxt::rpc::Subscription;
 
-use support_toolkit::logk;
+use support_toolkit::{convert::SmartCodecMapper, logk};
 
 use crate::error::{RelayError, RelayResult};
-use crate::keepstate;
+use crate::keepstate::{Keepstate, RecentlyJustification};
 use crate::types::JustificationInput;
 
+/// The subscription is treated as lost if no justification is received in this duration
+pub(super) const SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Wait time before the first resubscribe, doubled after each failed subscription
+pub(super) const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The backoff never exceeds this duration
+pub(super) const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
 pub struct SubscribeJustification<C: S2SClientGeneric> {
     input: JustificationInput<C>,
 }
@@ -20,74 +29,104 @@ impl<C: S2SClientGeneric> SubscribeJustification<C> {
     }
 }
 
-impl<C: S2SClientGeneric> SubscribeJustification<C> {
-    pub async fn start(self) -> RelayResult<()> {
-        let client = self.input.client;
-        let join_a = tokio::spawn(run_until_connection_lost(client, |justification| {
-            keepstate::set_recently_justification(C::CHAIN, justification);
-        }));
-        join_a
-            .await
-            .map_err(|e| S2SClientError::RPC(format!("{e:?}")))??;
-        Ok(())
-    }
+/// Progress of subscriber across subscriptions
+#[derive(Default)]
+struct SubscribeProgress {
+    /// block number of the last received justification
+    last_block_number: Option<u32>,
+    /// justifications received by the current subscription
+    received: usize,
 }
 
-async fn run_until_connection_lost<T, F>(client: T, callback: F) -> RelayResult<()>
-where
-    T: S2SClientGeneric,
-    F: Send + Sync + Fn(sp_core::Bytes),
-{
-    if let Err(err) = subscribe_justification(&client, callback).await {
-        tracing::error!(
-            target: "relay-s2s",
-            "{} failed to get justification from {}: {:?}",
-            logk::prefix_multi("subscribe", vec![T::CHAIN]),
-            T::CHAIN,
-            err,
-        );
-        return Err(err);
+impl<C: S2SClientGeneric> SubscribeJustification<C> {
+    /// Subscribe justifications forever, resubscribe with backoff when the subscription is lost
+    pub async fn start(self) -> RelayResult<()> {
+        let client = &self.input.client;
+        let keepstate = &self.input.keepstate;
+        let mut progress = SubscribeProgress::default();
+        let mut backoff = INITIAL_BACKOFF;
+        loop {
+            progress.received = 0;
+            let reason = match subscribe_justification(client, keepstate, &mut progress).await {
+                Ok(()) => "the subscription has been terminated".to_string(),
+                Err(err) => format!("{err:?}"),
+            };
+            // the subscription worked for a while, it's not a persistent failure
+            if progress.received > 0 {
+                backoff = INITIAL_BACKOFF;
+            }
+            tracing::warn!(
+                target: "relay-s2s",
+                "{} lost justification subscription of {}: {}, resubscribe after {}s",
+                logk::prefix_multi("subscribe", vec![C::CHAIN]),
+                C::CHAIN,
+                reason,
+                backoff.as_secs(),
+            );
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
     }
-    Err(RelayError::Custom(format!(
-        "[{}] the subscription stopped",
-        T::CHAIN,
-    )))
 }
 
-async fn subscribe_justification<T, F>(client: &T, callback: F) -> RelayResult<()>
-where
-    T: S2SClientGeneric,
-    F: Send + Sync + Fn(sp_core::Bytes),
-{
+/// Read justifications until the subscription ends, it returns error if the subscription
+/// failed or stayed silent too long
+async fn subscribe_justification<T: S2SClientGeneric>(
+    client: &T,
+    keepstate: &Keepstate,
+    progress: &mut SubscribeProgress,
+) -> RelayResult<()> {
     let mut subscribe = client.subscribe_grandpa_justifications().await?;
-    let timeout = std::time::Duration::from_secs(30);
-    loop {
-        let next_justification = safe_read_justification(timeout, &mut subscribe).await?;
-        match next_justification {
-            Some(justification) => {
-                let justification =
-                    justification.map_err(|e| S2SClientError::RPC(format!("{e:?}")))?;
+    while let Some(justification) =
+        safe_read_justification(SUBSCRIBE_TIMEOUT, &mut subscribe).await?
+    {
+        let justification = decode_justification::<T>(justification?)?;
+        match progress.last_block_number {
+            Some(last) if progress.received == 0 && justification.block_number > last + 1 => {
                 tracing::info!(
                     target: "relay-s2s",
-                    "{} subscribed new justification for {}",
+                    "{} resubscribed justification of {} from block {}, the justifications after block {} may be missed",
                     logk::prefix_multi("subscribe", vec![T::CHAIN]),
                     T::CHAIN,
+                    justification.block_number,
+                    last,
                 );
-                callback(justification);
             }
-            None => {
-                tracing::error!(
+            _ => {
+                tracing::info!(
                     target: "relay-s2s",
-                    "{} the subscription has been terminated",
+                    "{} subscribed new justification for {} at block {}",
                     logk::prefix_multi("subscribe", vec![T::CHAIN]),
-                );
-                return Err(RelayError::Custom(format!(
-                    "[{}] the subscription has been terminated",
                     T::CHAIN,
-                )));
+                    justification.block_number,
+                );
             }
         }
+        progress.last_block_number = Some(justification.block_number);
+        progress.received += 1;
+        keepstate.set_recently_justification(T::CHAIN, justification)?;
     }
+    Ok(())
+}
+
+/// Decode the block finalized by justification
+fn decode_justification<T: S2SClientGeneric>(
+    justification: sp_core::Bytes,
+) -> RelayResult<RecentlyJustification> {
+    let grandpa_justification: bp_header_chain::justification::GrandpaJustification<
+        <T::Chain as Chain>::Header,
+    > = codec::Decode::decode(&mut justification.as_ref()).map_err(|err| {
+        RelayError::Custom(format!(
+            "Failed to decode justification of {}: {:?}",
+            T::CHAIN,
+            err,
+        ))
+    })?;
+    Ok(RecentlyJustification {
+        block_number: SmartCodecMapper::map_to(&grandpa_justification.commit.target_number)?,
+        block_hash: array_bytes::bytes2hex("0x", grandpa_justification.commit.target_hash),
+        justification,
+    })
 }
 
 async fn safe_read_justification(
@@ -1,3 +1,7 @@
+pub use self::finality::*;
 pub use self::justification::*;
+pub(crate) use self::wakeup::*;
 
+mod finality;
 mod justification;
+mod wakeup;
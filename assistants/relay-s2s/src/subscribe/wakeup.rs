@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::watch;
+
+use crate::keepstate::Keepstate;
+
+/// Runners poll on this interval if no finality of their chains is notified, e.g. the
+/// subscriptions are lost or not started
+const FALLBACK_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Wake up runner when new finality of the chains it depends on is notified, the finalized
+/// heads are subscribed once per chain by `SubscribeFinality` and shared by keepstate.
+pub(crate) struct FinalityWakeup {
+    chains: Vec<&'static str>,
+    finality: watch::Receiver<HashMap<String, u64>>,
+    /// finality counters of the chains at the last wakeup
+    seen: Vec<u64>,
+}
+
+impl FinalityWakeup {
+    pub(crate) fn new(keepstate: &Keepstate, chains: Vec<&'static str>) -> Self {
+        let finality = keepstate.subscribe_finality();
+        let seen = Self::counters(&chains, &finality);
+        Self {
+            chains,
+            finality,
+            seen,
+        }
+    }
+
+    fn counters(
+        chains: &[&'static str],
+        finality: &watch::Receiver<HashMap<String, u64>>,
+    ) -> Vec<u64> {
+        let finality = finality.borrow();
+        chains
+            .iter()
+            .map(|chain| finality.get(*chain).copied().unwrap_or_default())
+            .collect()
+    }
+
+    /// Wait until new finality of the chains since the last wakeup, or the fallback poll
+    /// interval elapsed
+    pub(crate) async fn wait(&mut self) {
+        let _ = tokio::time::timeout(FALLBACK_POLL_INTERVAL, self.wait_finality()).await;
+        self.seen = Self::counters(&self.chains, &self.finality);
+    }
+
+    async fn wait_finality(&mut self) {
+        loop {
+            if self.finality.changed().await.is_err() {
+                // the keepstate is dropped, no more notifications
+                return std::future::pending().await;
+            }
+            if Self::counters(&self.chains, &self.finality) != self.seen {
+                return;
+            }
+        }
+    }
+}
@@ -1,59 +1,151 @@
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+
 use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay};
 #[cfg(feature = "bridge-parachain")]
 use bridge_s2s_traits::client::{S2SParaBridgeClientRelaychain, S2SParaBridgeClientSolochain};
-use bridge_s2s_traits::strategy::RelayStrategy;
+use bridge_s2s_traits::strategy::BatchRelayStrategy;
 use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::MessagesDeliveryLimits;
+use serde::{Deserialize, Serialize, Serializer};
 
 use subquery::types::OriginType;
-use subquery::Subquery;
+
+use crate::indexer::Indexer;
+use crate::keepstate::Keepstate;
 
 pub(crate) static M_HEADER: &str = "header";
 #[cfg(feature = "bridge-parachain")]
 pub(crate) static M_PARA_HEAD: &str = "para-head";
 pub(crate) static M_DELIVERY: &str = "delivery";
 pub(crate) static M_RECEIVING: &str = "receiving";
+pub(crate) static M_INDEXER: &str = "indexer";
 
 pub type LaneId = [u8; 4];
 
 pub struct SolochainHeaderInput<SC: S2SClientRelay, TC: S2SClientRelay> {
     pub client_source: SC,
     pub client_target: TC,
-    pub subquery_source: Subquery,
+    pub subquery_source: Indexer,
     pub index_origin_type: OriginType,
     pub enable_mandatory: bool,
+    /// where to find justifications of mandatory headers, in priority order
+    pub justification_sources: Vec<JustificationSource>,
+    /// log the call instead of submitting it
+    pub dry_run: bool,
+    pub keepstate: Keepstate,
 }
 
 #[cfg(feature = "bridge-parachain")]
 pub struct RelaychainHeaderInput<SC: S2SClientGeneric, TC: S2SClientRelay> {
     pub client_relaychain: SC,
     pub client_solochain: TC,
-    pub subquery_relaychain: Subquery,
-    pub subquery_parachain: Subquery,
+    pub subquery_relaychain: Indexer,
+    pub subquery_parachain: Indexer,
     pub index_origin_type: OriginType,
     pub enable_mandatory: bool,
+    /// where to find justifications of mandatory headers, in priority order
+    pub justification_sources: Vec<JustificationSource>,
+    /// log the call instead of submitting it
+    pub dry_run: bool,
+    pub keepstate: Keepstate,
 }
 
 #[cfg(feature = "bridge-parachain")]
 pub struct ParaHeaderInput<SC: S2SParaBridgeClientRelaychain, TC: S2SParaBridgeClientSolochain> {
     pub client_relaychain: SC,
     pub client_solochain: TC,
-    pub para_id: u32,
+    /// parachains of the relay chain, all stale heads are submitted by one extrinsic
+    pub para_ids: Vec<u32>,
+    /// log the call instead of submitting it
+    pub dry_run: bool,
+    pub keepstate: Keepstate,
+}
+
+/// Source of justifications of mandatory headers
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum JustificationSource {
+    /// query justification mapping recorded by indexer
+    Indexer,
+    /// read justification from node, see `S2SClientGeneric::find_justification`
+    Node,
+}
+
+impl JustificationSource {
+    /// Indexer first, node is the fallback when indexer is behind or pruned
+    pub fn default_priority() -> Vec<Self> {
+        vec![Self::Indexer, Self::Node]
+    }
+}
+
+impl Display for JustificationSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Indexer => f.write_str("indexer"),
+            Self::Node => f.write_str("node"),
+        }
+    }
+}
+
+/// When the receiving runner submits confirmations, the delivered messages are confirmed once
+/// any of the configured conditions is met. Nothing configured means confirm as soon as any
+/// delivered message is unconfirmed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ConfirmationPolicy {
+    /// confirm when the count of delivered but unconfirmed messages reaches it
+    pub max_unconfirmed_messages: Option<u64>,
+    /// confirm when the unrewarded relayer entries of target inbound lane reach it, keep it
+    /// below `MaxUnrewardedRelayerEntriesAtInboundLane` of target runtime, otherwise the
+    /// delivery is blocked by the full inbound lane
+    pub max_unrewarded_relayer_entries: Option<u64>,
+    /// confirm when the unconfirmed messages have been waiting for these seconds
+    pub max_delay_secs: Option<u64>,
+    /// confirm when the messages delivered by our relayer are waiting for rewards
+    pub own_rewards: bool,
+}
+
+impl ConfirmationPolicy {
+    /// Whether no condition is configured
+    pub fn is_eager(&self) -> bool {
+        self == &Self::default()
+    }
 }
 
 pub struct JustificationInput<C: S2SClientGeneric> {
     pub client: C,
+    pub keepstate: Keepstate,
 }
 
-pub struct MessageDeliveryInput<SC: S2SClientRelay, TC: S2SClientRelay, Strategy: RelayStrategy> {
+pub struct FinalityInput<C: S2SClientGeneric> {
+    pub client: C,
+    pub keepstate: Keepstate,
+}
+
+pub struct MessageDeliveryInput<
+    SC: S2SClientRelay,
+    TC: S2SClientRelay,
+    Strategy: BatchRelayStrategy,
+> {
     pub lanes: Vec<LaneId>,
     pub nonces_limit: u64,
     pub relayer_account: <SC::Chain as Chain>::AccountId,
     pub client_source: SC,
     pub client_target: TC,
-    pub subquery_source: Subquery,
-    pub subquery_target: Subquery,
+    pub subquery_source: Indexer,
+    pub subquery_target: Indexer,
     pub relay_block_origin: OriginType,
     pub relay_strategy: Strategy,
+    /// submit the missing source header with the messages proof by `utility.batch_all`
+    /// instead of waiting for header relay, only solo chain headers can be bundled
+    pub bundle_header: bool,
+    /// log the call instead of submitting it
+    pub dry_run: bool,
+    /// limits of target runtime which override the limits read by target client, and fill
+    /// the limits the runtime doesn't expose
+    pub delivery_limits: MessagesDeliveryLimits,
+    pub keepstate: Keepstate,
 }
 
 pub struct MessageReceivingInput<SC: S2SClientRelay, TC: S2SClientRelay> {
@@ -61,6 +153,114 @@ pub struct MessageReceivingInput<SC: S2SClientRelay, TC: S2SClientRelay> {
     pub relayer_account: <SC::Chain as Chain>::AccountId,
     pub client_source: SC,
     pub client_target: TC,
-    pub subquery_source: Subquery,
-    pub subquery_target: Subquery,
+    pub subquery_source: Indexer,
+    pub subquery_target: Indexer,
+    /// submit the target header which proves the delivery with the delivery proof by
+    /// `utility.batch_all`, only solo chain headers can be bundled
+    pub bundle_header: bool,
+    pub confirmation_policy: ConfirmationPolicy,
+    /// log the call instead of submitting it
+    pub dry_run: bool,
+    pub keepstate: Keepstate,
+}
+
+/// Snapshot of message delivery of a lane, it's what the delivery runner sees
+#[derive(Clone, Debug, Serialize)]
+pub struct DeliveryStatus {
+    #[serde(serialize_with = "serialize_lane")]
+    pub lane: LaneId,
+    /// latest generated nonce of source chain outbound lane
+    pub latest_generated_nonce: u64,
+    /// latest received nonce of source chain outbound lane, it's the confirmed nonce
+    pub latest_received_nonce: u64,
+    /// last delivered nonce of target chain inbound lane
+    pub last_delivered_nonce: u64,
+    /// last confirmed nonce of target chain inbound lane
+    pub last_confirmed_nonce: u64,
+    /// unrewarded relayers of target chain inbound lane
+    pub unrewarded_relayers: Vec<UnrewardedRelayerStatus>,
+    /// source block number of the best finalized header imported by target chain
+    pub last_relayed_block: Option<u32>,
+    /// source block number which holds the last nonce to deliver
+    pub nonce_block: Option<u32>,
+    /// the nonce over the limits of target chain even if delivered alone, the lane is stuck
+    /// at it
+    pub over_limits_nonce: Option<u64>,
+    /// what the delivery runner will do
+    pub decision: DeliveryDecision,
+}
+
+fn serialize_lane<S: Serializer>(lane: &LaneId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&array_bytes::bytes2hex("0x", lane))
+}
+
+/// Unrewarded relayer and the nonces delivered by it
+#[derive(Clone, Debug, Serialize)]
+pub struct UnrewardedRelayerStatus {
+    pub relayer: String,
+    pub begin: u64,
+    pub end: u64,
+}
+
+/// Decision of delivery runner for a lane
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum DeliveryDecision {
+    /// deliver these nonces
+    Relay { nonces: RangeInclusive<u64> },
+    /// all generated nonces are delivered and confirmed
+    AllDelivered,
+    /// the last relayed nonce isn't received by target chain yet
+    WaitReceiving { last_relayed_nonce: u64 },
+    /// the block of nonce isn't stored by indexer
+    IndexerGap { nonce: u64 },
+    /// the bridge isn't initialized
+    NotInitialized,
+    /// the block of nonce isn't relayed to target chain yet
+    WaitHeader { nonce: u64 },
+    /// the relay strategy decide not relay these nonces
+    StrategyRefused { nonces: RangeInclusive<u64> },
+    /// the inbound lane of target chain can't receive more messages until the delivered
+    /// messages are confirmed
+    InboundLaneFull { last_delivered_nonce: u64 },
+    /// the message is over the limits of target runtime even if it's delivered alone
+    OverLimits { nonce: u64 },
+}
+
+impl Display for DeliveryDecision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Relay { nonces } => write!(f, "relay nonces {:?}", nonces),
+            Self::AllDelivered => write!(f, "all nonces delivered"),
+            Self::WaitReceiving { last_relayed_nonce } => write!(
+                f,
+                "wait receiving, last relayed nonce is {}",
+                last_relayed_nonce
+            ),
+            Self::IndexerGap { nonce } => {
+                write!(f, "the nonce({}) isn't storage by indexer", nonce)
+            }
+            Self::NotInitialized => write!(f, "the bridge not initialized"),
+            Self::WaitHeader { nonce } => {
+                write!(f, "wait header relay for the nonce({})", nonce)
+            }
+            Self::StrategyRefused { nonces } => {
+                write!(f, "the relay strategy refused nonces {:?}", nonces)
+            }
+            Self::InboundLaneFull {
+                last_delivered_nonce,
+            } => write!(
+                f,
+                "the inbound lane is full, last delivered nonce is {}",
+                last_delivered_nonce
+            ),
+            Self::OverLimits { nonce } => {
+                write!(
+                    f,
+                    "the message({}) is over the limits of target chain",
+                    nonce
+                )
+            }
+        }
+    }
 }
@@ -0,0 +1,239 @@
+#![cfg(feature = "bridge-parachain")]
+
+use std::time::Duration;
+
+use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay};
+use bridge_s2s_traits::strategy::AlwaysRelayStrategy;
+use client_mock::client::MockClient;
+use client_mock::subquery::FakeSubquery;
+use client_mock::types::{MockCall, MockRelayChain, MockSourceChain, MockTargetChain};
+use subquery::types::OriginType;
+use support_toolkit::convert::SmartCodecMapper;
+
+use relay_s2s::header::{ParaHeaderRunner, RelaychainHeaderRunner};
+use relay_s2s::keepstate::Keepstate;
+use relay_s2s::message::BridgeParachainDeliveryRunner;
+use relay_s2s::subscribe::{SubscribeFinality, SubscribeJustification};
+use relay_s2s::types::{
+    FinalityInput, JustificationInput, JustificationSource, MessageDeliveryInput, ParaHeaderInput,
+    RelaychainHeaderInput,
+};
+
+const LANE: [u8; 4] = [0, 0, 0, 0];
+const PARA_ID: u32 = 2105;
+const OTHER_PARA_ID: u32 = 2000;
+/// Shorter than the fallback poll interval of runners, the relay must be driven by finality
+const RELAY_TIMEOUT: Duration = Duration::from_secs(20);
+
+async fn wait_until(timeout: Duration, condition: impl Fn() -> bool) -> bool {
+    let started = tokio::time::Instant::now();
+    while started.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    condition()
+}
+
+#[tokio::test]
+async fn test_parachain_header_and_message_delivery() {
+    let client_relaychain = MockClient::<MockRelayChain>::new();
+    let client_parachain = MockClient::<MockSourceChain>::new();
+    let client_solochain = MockClient::<MockTargetChain>::new();
+    let subquery_relaychain = FakeSubquery::start().await.unwrap();
+    let subquery_parachain = FakeSubquery::start().await.unwrap();
+    let keepstate = Keepstate::memory();
+
+    let initialization_data = client_relaychain
+        .prepare_initialization_data()
+        .await
+        .unwrap();
+    client_solochain
+        .initialize(initialization_data)
+        .await
+        .unwrap();
+
+    tokio::spawn(
+        SubscribeJustification::new(JustificationInput {
+            client: client_relaychain.clone(),
+            keepstate: keepstate.clone(),
+        })
+        .start(),
+    );
+    tokio::spawn(
+        SubscribeFinality::new(FinalityInput {
+            client: client_parachain.clone(),
+            keepstate: keepstate.clone(),
+        })
+        .start(),
+    );
+    tokio::spawn(
+        SubscribeFinality::new(FinalityInput {
+            client: client_solochain.clone(),
+            keepstate: keepstate.clone(),
+        })
+        .start(),
+    );
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // message sent by parachain, and the para head is included by relaychain
+    let (nonce, para_header) = client_parachain.send_message(LANE, vec![1, 2, 3], 100);
+    subquery_parachain.index_on_demand(
+        OriginType::BridgePangolinParachain,
+        &para_header,
+        LANE,
+        nonce,
+    );
+    let relay_header = client_relaychain.set_para_head(PARA_ID, &para_header);
+    subquery_relaychain.index_candidate_included(&relay_header, PARA_ID, &para_header);
+
+    let relaychain_header = RelaychainHeaderRunner::new(RelaychainHeaderInput {
+        client_relaychain: client_relaychain.clone(),
+        client_solochain: client_solochain.clone(),
+        subquery_relaychain: subquery_relaychain.subquery().into(),
+        subquery_parachain: subquery_parachain.subquery().into(),
+        index_origin_type: OriginType::BridgePangolinParachain,
+        enable_mandatory: true,
+        justification_sources: JustificationSource::default_priority(),
+        dry_run: false,
+        keepstate: keepstate.clone(),
+    });
+    let para_header_relay = ParaHeaderRunner::new(ParaHeaderInput {
+        client_relaychain: client_relaychain.clone(),
+        client_solochain: client_solochain.clone(),
+        para_ids: vec![PARA_ID],
+        dry_run: false,
+        keepstate: keepstate.clone(),
+    });
+    let delivery = BridgeParachainDeliveryRunner::new(
+        MessageDeliveryInput {
+            lanes: vec![LANE],
+            nonces_limit: 11,
+            relayer_account: SmartCodecMapper::map_to(&[0u8; 32]).unwrap(),
+            client_source: client_parachain.clone(),
+            client_target: client_solochain.clone(),
+            subquery_source: subquery_parachain.subquery().into(),
+            subquery_target: subquery_relaychain.subquery().into(),
+            relay_block_origin: OriginType::BridgePangolinParachain,
+            relay_strategy: AlwaysRelayStrategy,
+            bundle_header: false,
+            dry_run: false,
+            delivery_limits: Default::default(),
+            keepstate: keepstate.clone(),
+        },
+        PARA_ID,
+    );
+    tokio::spawn(async move { relaychain_header.start().await });
+    tokio::spawn(async move { para_header_relay.start().await });
+    tokio::spawn(async move { delivery.start().await });
+
+    let delivered = wait_until(RELAY_TIMEOUT, || {
+        client_solochain
+            .state()
+            .storage
+            .inbound_lanes
+            .get(&LANE)
+            .map(|lane| lane.last_delivered_nonce() == nonce)
+            .unwrap_or_default()
+    })
+    .await;
+    assert!(delivered, "message not delivered");
+
+    let calls: Vec<MockCall> = client_solochain
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .collect();
+    assert!(calls
+        .iter()
+        .any(|call| matches!(call, MockCall::SubmitFinalityProof { .. })));
+    assert!(calls.iter().any(|call| matches!(
+        call,
+        MockCall::SubmitParachainHeads { parachains, .. } if parachains[0].0 == PARA_ID
+    )));
+}
+
+#[tokio::test]
+async fn test_parachain_heads_in_one_extrinsic() {
+    let client_relaychain = MockClient::<MockRelayChain>::new();
+    let client_parachain = MockClient::<MockSourceChain>::new();
+    let client_other_parachain = MockClient::<MockSourceChain>::new();
+    let client_solochain = MockClient::<MockTargetChain>::new();
+    let subquery_relaychain = FakeSubquery::start().await.unwrap();
+    let keepstate = Keepstate::memory();
+
+    let initialization_data = client_relaychain
+        .prepare_initialization_data()
+        .await
+        .unwrap();
+    client_solochain
+        .initialize(initialization_data)
+        .await
+        .unwrap();
+
+    tokio::spawn(
+        SubscribeJustification::new(JustificationInput {
+            client: client_relaychain.clone(),
+            keepstate: keepstate.clone(),
+        })
+        .start(),
+    );
+    tokio::spawn(
+        SubscribeFinality::new(FinalityInput {
+            client: client_solochain.clone(),
+            keepstate: keepstate.clone(),
+        })
+        .start(),
+    );
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // heads of both parachains are included by relaychain
+    let other_para_header = client_other_parachain.produce_block();
+    client_relaychain.set_para_head(OTHER_PARA_ID, &other_para_header);
+    let para_header = client_parachain.produce_block();
+    let relay_header = client_relaychain.set_para_head(PARA_ID, &para_header);
+    subquery_relaychain.index_candidate_included(&relay_header, PARA_ID, &para_header);
+
+    let relaychain_header = RelaychainHeaderRunner::new(RelaychainHeaderInput {
+        client_relaychain: client_relaychain.clone(),
+        client_solochain: client_solochain.clone(),
+        subquery_relaychain: subquery_relaychain.subquery().into(),
+        subquery_parachain: subquery_relaychain.subquery().into(),
+        index_origin_type: OriginType::BridgePangolinParachain,
+        enable_mandatory: true,
+        justification_sources: JustificationSource::default_priority(),
+        dry_run: false,
+        keepstate: keepstate.clone(),
+    });
+    let para_header_relay = ParaHeaderRunner::new(ParaHeaderInput {
+        client_relaychain: client_relaychain.clone(),
+        client_solochain: client_solochain.clone(),
+        para_ids: vec![PARA_ID, OTHER_PARA_ID],
+        dry_run: false,
+        keepstate: keepstate.clone(),
+    });
+    tokio::spawn(async move { relaychain_header.start().await });
+    tokio::spawn(async move { para_header_relay.start().await });
+
+    let relayed = wait_until(RELAY_TIMEOUT, || {
+        let state = client_solochain.state();
+        state.best_para_heads.contains_key(&PARA_ID)
+            && state.best_para_heads.contains_key(&OTHER_PARA_ID)
+    })
+    .await;
+    assert!(relayed, "parachain heads not relayed");
+
+    let submissions: Vec<MockCall> = client_solochain
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .filter(|call| matches!(call, MockCall::SubmitParachainHeads { .. }))
+        .collect();
+    assert!(matches!(
+        submissions.as_slice(),
+        [MockCall::SubmitParachainHeads { parachains, .. }]
+            if parachains.iter().map(|(para_id, _)| *para_id).collect::<Vec<_>>()
+                == vec![PARA_ID, OTHER_PARA_ID]
+    ));
+}
@@ -0,0 +1,757 @@
+use std::time::Duration;
+
+use bridge_s2s_traits::client::{S2SClientGeneric, S2SClientRelay};
+use bridge_s2s_traits::error::S2SClientResult;
+use bridge_s2s_traits::strategy::{
+    AlwaysRelayStrategy, BatchRelayStrategy, RelayReference, RelayStrategy,
+};
+use bridge_s2s_traits::types::bp_header_chain::AuthoritySet;
+use bridge_s2s_traits::types::bp_messages::MessageNonce;
+use bridge_s2s_traits::types::bp_runtime::Chain;
+use bridge_s2s_traits::types::MessagesDeliveryLimits;
+use client_mock::client::MockClient;
+use client_mock::subquery::FakeSubquery;
+use client_mock::types::{MockCall, MockChainInfo, MockSourceChain, MockTargetChain};
+use microkv::MicroKV;
+use sp_core::{ed25519, Pair};
+use sp_runtime::traits::Header;
+use subquery::types::OriginType;
+use support_toolkit::convert::SmartCodecMapper;
+
+use relay_s2s::error::RelayError;
+use relay_s2s::header::SolochainHeaderRunner;
+use relay_s2s::indexer::ChainScanIndexer;
+use relay_s2s::keepstate::Keepstate;
+use relay_s2s::message::{BridgeSolochainDeliveryRunner, BridgeSolochainReceivingRunner};
+use relay_s2s::subscribe::SubscribeJustification;
+use relay_s2s::types::{
+    ConfirmationPolicy, DeliveryDecision, JustificationInput, JustificationSource,
+    MessageDeliveryInput, MessageReceivingInput, SolochainHeaderInput,
+};
+
+const LANE: [u8; 4] = [0, 0, 0, 0];
+
+type SourceClient = MockClient<MockSourceChain>;
+type TargetClient = MockClient<MockTargetChain>;
+type SourceHeader = <<MockSourceChain as MockChainInfo>::Chain as Chain>::Header;
+
+/// Refuse to relay nonces from the given nonce
+#[derive(Clone)]
+struct RefuseFromStrategy {
+    from: u64,
+}
+
+#[async_trait::async_trait]
+impl RelayStrategy for RefuseFromStrategy {
+    async fn decide(&mut self, reference: RelayReference) -> S2SClientResult<bool> {
+        Ok(reference.nonce < self.from)
+    }
+}
+
+/// Another relayer delivers the nonces while the strategy is deciding
+#[derive(Clone)]
+struct CompetitorStrategy {
+    client_target: TargetClient,
+}
+
+#[async_trait::async_trait]
+impl RelayStrategy for CompetitorStrategy {
+    async fn decide(&mut self, reference: RelayReference) -> S2SClientResult<bool> {
+        let mut state = self.client_target.state();
+        let inbound_lane_data = state.storage.inbound_lanes.entry(LANE).or_default();
+        inbound_lane_data.last_confirmed_nonce =
+            inbound_lane_data.last_confirmed_nonce.max(reference.nonce);
+        Ok(true)
+    }
+}
+
+/// Mock chains and indexers of one test, the keepstate isn't shared with other tests
+struct TestBridge {
+    client_source: SourceClient,
+    client_target: TargetClient,
+    subquery_source: FakeSubquery,
+    subquery_target: FakeSubquery,
+    keepstate: Keepstate,
+}
+
+impl TestBridge {
+    async fn new() -> Self {
+        Self {
+            client_source: MockClient::new(),
+            client_target: MockClient::new(),
+            subquery_source: FakeSubquery::start().await.unwrap(),
+            subquery_target: FakeSubquery::start().await.unwrap(),
+            keepstate: Keepstate::memory(),
+        }
+    }
+
+    /// Initialize the bridge of source chain on target chain, and the reverse one if `both_sides`
+    async fn initialize(&self, both_sides: bool) {
+        let initialization_data = self
+            .client_source
+            .prepare_initialization_data()
+            .await
+            .unwrap();
+        self.client_target
+            .initialize(initialization_data)
+            .await
+            .unwrap();
+        if both_sides {
+            let initialization_data = self
+                .client_target
+                .prepare_initialization_data()
+                .await
+                .unwrap();
+            self.client_source
+                .initialize(initialization_data)
+                .await
+                .unwrap();
+        }
+    }
+
+    /// Subscribe justifications of source chain, and of target chain if `both_sides`
+    async fn subscribe_justification(&self, both_sides: bool) {
+        tokio::spawn(
+            SubscribeJustification::new(JustificationInput {
+                client: self.client_source.clone(),
+                keepstate: self.keepstate.clone(),
+            })
+            .start(),
+        );
+        if both_sides {
+            tokio::spawn(
+                SubscribeJustification::new(JustificationInput {
+                    client: self.client_target.clone(),
+                    keepstate: self.keepstate.clone(),
+                })
+                .start(),
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+
+    /// Send a message from source chain and index it
+    fn send_message(&self, payload: Vec<u8>) -> (MessageNonce, SourceHeader) {
+        let (nonce, message_header) = self.client_source.send_message(LANE, payload, 100);
+        self.subquery_source.index_on_demand(
+            OriginType::BridgePangoro,
+            &message_header,
+            LANE,
+            nonce,
+        );
+        (nonce, message_header)
+    }
+
+    fn header_input(
+        &self,
+        enable_mandatory: bool,
+    ) -> SolochainHeaderInput<SourceClient, TargetClient> {
+        SolochainHeaderInput {
+            client_source: self.client_source.clone(),
+            client_target: self.client_target.clone(),
+            subquery_source: self.subquery_source.subquery().into(),
+            index_origin_type: OriginType::BridgePangoro,
+            enable_mandatory,
+            justification_sources: JustificationSource::default_priority(),
+            dry_run: false,
+            keepstate: self.keepstate.clone(),
+        }
+    }
+
+    fn delivery_input<Strategy: BatchRelayStrategy>(
+        &self,
+        relay_strategy: Strategy,
+    ) -> MessageDeliveryInput<SourceClient, TargetClient, Strategy> {
+        MessageDeliveryInput {
+            lanes: vec![LANE],
+            nonces_limit: 11,
+            relayer_account: SmartCodecMapper::map_to(&[0u8; 32]).unwrap(),
+            client_source: self.client_source.clone(),
+            client_target: self.client_target.clone(),
+            subquery_source: self.subquery_source.subquery().into(),
+            subquery_target: self.subquery_target.subquery().into(),
+            relay_block_origin: OriginType::BridgePangoro,
+            relay_strategy,
+            bundle_header: false,
+            dry_run: false,
+            delivery_limits: Default::default(),
+            keepstate: self.keepstate.clone(),
+        }
+    }
+
+    fn receiving_input(&self) -> MessageReceivingInput<SourceClient, TargetClient> {
+        MessageReceivingInput {
+            lanes: vec![LANE],
+            relayer_account: SmartCodecMapper::map_to(&[0u8; 32]).unwrap(),
+            client_source: self.client_source.clone(),
+            client_target: self.client_target.clone(),
+            subquery_source: self.subquery_source.subquery().into(),
+            subquery_target: self.subquery_target.subquery().into(),
+            bundle_header: false,
+            confirmation_policy: ConfirmationPolicy::default(),
+            dry_run: false,
+            keepstate: self.keepstate.clone(),
+        }
+    }
+
+    fn delivered(&self, nonce: MessageNonce) -> bool {
+        self.client_target
+            .state()
+            .storage
+            .inbound_lanes
+            .get(&LANE)
+            .map(|lane| lane.last_delivered_nonce() == nonce)
+            .unwrap_or_default()
+    }
+
+    fn received(&self, nonce: MessageNonce) -> bool {
+        self.client_source
+            .state()
+            .storage
+            .outbound_lanes
+            .get(&LANE)
+            .map(|lane| lane.latest_received_nonce == nonce)
+            .unwrap_or_default()
+    }
+
+    fn no_delivery(&self) -> bool {
+        self.client_target
+            .extrinsics()
+            .iter()
+            .all(|extrinsic| !matches!(extrinsic.call, MockCall::ReceiveMessagesProof { .. }))
+    }
+
+    fn finality_proof_submitted(&self, number: u32) -> bool {
+        self.client_target.extrinsics().iter().any(|extrinsic| {
+            matches!(
+                extrinsic.call,
+                MockCall::SubmitFinalityProof { number: submitted, .. } if submitted == number
+            )
+        })
+    }
+}
+
+async fn wait_until(timeout: Duration, condition: impl Fn() -> bool) -> bool {
+    let started = tokio::time::Instant::now();
+    while started.elapsed() < timeout {
+        if condition() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    condition()
+}
+
+#[tokio::test]
+async fn test_solochain_header_and_message_relay() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(true).await;
+    bridge.subscribe_justification(true).await;
+
+    // mandatory header
+    let mandatory_header = bridge.client_source.produce_block();
+    bridge.subquery_source.index_mandatory(&mandatory_header);
+    bridge.subquery_source.index_justification(
+        &mandatory_header,
+        bridge.client_source.justification(&mandatory_header).0,
+        true,
+    );
+    // message
+    let (nonce, message_header) = bridge.send_message(vec![1, 2, 3]);
+
+    let header_source_to_target = SolochainHeaderRunner::new(bridge.header_input(true));
+    let header_target_to_source = SolochainHeaderRunner::new(SolochainHeaderInput {
+        client_source: bridge.client_target.clone(),
+        client_target: bridge.client_source.clone(),
+        subquery_source: bridge.subquery_target.subquery().into(),
+        index_origin_type: OriginType::BridgePangolin,
+        enable_mandatory: true,
+        justification_sources: JustificationSource::default_priority(),
+        dry_run: false,
+        keepstate: bridge.keepstate.clone(),
+    });
+    let delivery = BridgeSolochainDeliveryRunner::new(bridge.delivery_input(AlwaysRelayStrategy));
+    let receiving = BridgeSolochainReceivingRunner::new(bridge.receiving_input());
+    tokio::spawn(async move { header_source_to_target.start().await });
+    tokio::spawn(async move { header_target_to_source.start().await });
+    tokio::spawn(async move { delivery.start().await });
+    tokio::spawn(async move { receiving.start().await });
+
+    // delivery
+    let delivered = wait_until(Duration::from_secs(60), || bridge.delivered(nonce)).await;
+    assert!(delivered, "message not delivered");
+
+    // the target header contains delivered messages is needed to relay back
+    let delivered_header = bridge.client_target.produce_block();
+    bridge.subquery_target.index_on_demand(
+        OriginType::BridgePangolin,
+        &delivered_header,
+        LANE,
+        nonce,
+    );
+
+    // receiving
+    let received = wait_until(Duration::from_secs(60), || bridge.received(nonce)).await;
+    assert!(received, "message not confirmed");
+
+    let target_calls: Vec<MockCall> = bridge
+        .client_target
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .collect();
+    let mandatory_number = *mandatory_header.number();
+    let message_number = *message_header.number();
+    assert!(matches!(
+        target_calls.get(1),
+        Some(MockCall::SubmitFinalityProof { number, .. }) if *number == mandatory_number
+    ));
+    assert!(bridge.finality_proof_submitted(message_number));
+    assert_eq!(
+        target_calls
+            .iter()
+            .filter(|call| matches!(call, MockCall::ReceiveMessagesProof { .. }))
+            .count(),
+        1
+    );
+    assert_eq!(
+        bridge
+            .client_source
+            .extrinsics()
+            .iter()
+            .filter(|extrinsic| matches!(
+                extrinsic.call,
+                MockCall::ReceiveMessagesDeliveryProof { .. }
+            ))
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_delivery_accepted_prefix() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+    bridge.subscribe_justification(false).await;
+
+    // the third message is refused by strategy
+    let mut last_nonce = 0;
+    for _ in 0..3 {
+        let (nonce, _) = bridge.send_message(vec![1, 2, 3]);
+        last_nonce = nonce;
+    }
+
+    let header = SolochainHeaderRunner::new(bridge.header_input(false));
+    let delivery = BridgeSolochainDeliveryRunner::new(
+        bridge.delivery_input(RefuseFromStrategy { from: last_nonce }),
+    );
+    tokio::spawn(async move { header.start().await });
+    tokio::spawn(async move { delivery.start().await });
+
+    let delivered = wait_until(Duration::from_secs(60), || bridge.delivered(last_nonce - 1)).await;
+    assert!(delivered, "accepted messages not delivered");
+
+    let deliveries: Vec<MockCall> = bridge
+        .client_target
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .filter(|call| matches!(call, MockCall::ReceiveMessagesProof { .. }))
+        .collect();
+    assert!(matches!(
+        deliveries.as_slice(),
+        [MockCall::ReceiveMessagesProof { nonces, messages_count, messages_fee, .. }]
+            if *nonces == (1..=last_nonce - 1) && *messages_count == 2 && *messages_fee == 200
+    ));
+}
+
+#[tokio::test]
+async fn test_delivery_within_target_limits() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+    bridge.subscribe_justification(false).await;
+
+    for _ in 0..4 {
+        bridge.send_message(vec![1, 2]);
+    }
+    // the limits read from target runtime are overridden by input
+    bridge.client_target.state().delivery_limits = MessagesDeliveryLimits {
+        max_extrinsic_weight: Some(100),
+        ..Default::default()
+    };
+
+    let header = SolochainHeaderRunner::new(bridge.header_input(false));
+    let delivery = BridgeSolochainDeliveryRunner::new(MessageDeliveryInput {
+        // the dispatch weight of mock message is the length of payload
+        delivery_limits: MessagesDeliveryLimits {
+            max_extrinsic_weight: Some(5),
+            ..Default::default()
+        },
+        ..bridge.delivery_input(AlwaysRelayStrategy)
+    });
+    tokio::spawn(async move { header.start().await });
+    tokio::spawn(async move { delivery.start().await });
+
+    let delivered = wait_until(Duration::from_secs(60), || {
+        bridge
+            .client_target
+            .state()
+            .storage
+            .inbound_lanes
+            .get(&LANE)
+            .map(|lane| lane.last_delivered_nonce() > 0)
+            .unwrap_or_default()
+    })
+    .await;
+    assert!(delivered, "messages not delivered");
+
+    // only the messages fit the max extrinsic weight are delivered
+    let deliveries: Vec<MockCall> = bridge
+        .client_target
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .filter(|call| matches!(call, MockCall::ReceiveMessagesProof { .. }))
+        .collect();
+    assert!(matches!(
+        deliveries.as_slice(),
+        [MockCall::ReceiveMessagesProof { nonces, messages_count, dispatch_weight, .. }]
+            if *nonces == (1..=2) && *messages_count == 2 && *dispatch_weight == 4
+    ));
+}
+
+#[tokio::test]
+async fn test_delivery_status() {
+    let bridge = TestBridge::new().await;
+    let delivery = BridgeSolochainDeliveryRunner::new(bridge.delivery_input(AlwaysRelayStrategy));
+
+    let status = delivery.status(LANE).await.unwrap();
+    assert_eq!(status.decision, DeliveryDecision::AllDelivered);
+    assert_eq!(status.latest_generated_nonce, 0);
+
+    bridge.initialize(false).await;
+
+    // the message isn't indexed
+    let (nonce, message_header) = bridge.client_source.send_message(LANE, vec![1, 2, 3], 100);
+    let status = delivery.status(LANE).await.unwrap();
+    assert_eq!(status.decision, DeliveryDecision::IndexerGap { nonce });
+    assert_eq!(status.latest_generated_nonce, nonce);
+    assert_eq!(status.last_delivered_nonce, 0);
+
+    // the header of message isn't relayed
+    bridge
+        .subquery_source
+        .index_on_demand(OriginType::BridgePangoro, &message_header, LANE, nonce);
+    let status = delivery.status(LANE).await.unwrap();
+    assert_eq!(status.decision, DeliveryDecision::WaitHeader { nonce });
+    assert_eq!(status.nonce_block, Some(*message_header.number()));
+    assert_eq!(status.over_limits_nonce, None);
+    assert_eq!(status.last_relayed_block, Some(0));
+
+    // the message is over the limits of target chain even if it's delivered alone
+    bridge.client_target.state().best_target_finalized =
+        Some((*message_header.number(), message_header.hash()));
+    bridge.client_target.state().delivery_limits = MessagesDeliveryLimits {
+        max_extrinsic_weight: Some(2),
+        ..Default::default()
+    };
+    let status = delivery.status(LANE).await.unwrap();
+    assert_eq!(status.decision, DeliveryDecision::OverLimits { nonce });
+    assert_eq!(status.over_limits_nonce, Some(nonce));
+    assert!(bridge.no_delivery());
+}
+
+#[tokio::test]
+async fn test_delivery_dry_run() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+    bridge.subscribe_justification(false).await;
+
+    let (nonce, _) = bridge.send_message(vec![1, 2, 3]);
+
+    let header = SolochainHeaderRunner::new(bridge.header_input(false));
+    let observer = BridgeSolochainDeliveryRunner::new(bridge.delivery_input(AlwaysRelayStrategy));
+    let dry_run = BridgeSolochainDeliveryRunner::new(MessageDeliveryInput {
+        dry_run: true,
+        ..bridge.delivery_input(AlwaysRelayStrategy)
+    });
+    tokio::spawn(async move { header.start().await });
+    tokio::spawn(async move { dry_run.start().await });
+
+    // wait until the header of message is relayed, then give the dry-run delivery a full round
+    let expected = DeliveryDecision::Relay {
+        nonces: nonce..=nonce,
+    };
+    let mut ready = false;
+    for _ in 0..600 {
+        let status = observer.status(LANE).await.unwrap();
+        if status.decision == expected {
+            ready = true;
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(ready, "the header of message not relayed");
+    tokio::time::sleep(Duration::from_secs(6)).await;
+
+    assert!(bridge.no_delivery());
+    assert!(bridge
+        .keepstate
+        .get_last_delivery_relayed_nonce(MockSourceChain::CHAIN, LANE)
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_delivery_lost_race() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+    bridge.subscribe_justification(false).await;
+
+    bridge.send_message(vec![1, 2, 3]);
+
+    let header = SolochainHeaderRunner::new(bridge.header_input(false));
+    let delivery = BridgeSolochainDeliveryRunner::new(bridge.delivery_input(CompetitorStrategy {
+        client_target: bridge.client_target.clone(),
+    }));
+    tokio::spawn(async move { header.start().await });
+    tokio::spawn(async move { delivery.start().await });
+
+    let lost = wait_until(Duration::from_secs(60), || {
+        support_metrics::metrics::render()
+            .map(|text| {
+                text.contains(
+                    r#"bridger_lost_races_total{call="receive_messages_proof",chain="mock-target",stage="before-submit"} 1"#,
+                )
+            })
+            .unwrap_or_default()
+    })
+    .await;
+    assert!(lost, "the lost race is not reported");
+
+    assert!(bridge.no_delivery());
+    assert!(bridge
+        .keepstate
+        .get_last_delivery_relayed_nonce(MockSourceChain::CHAIN, LANE)
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_bundle_header_with_message_proofs() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(true).await;
+    bridge.subscribe_justification(true).await;
+
+    let (nonce, message_header) = bridge.send_message(vec![1, 2, 3]);
+
+    // no header runner, the headers are submitted with message proofs
+    let delivery = BridgeSolochainDeliveryRunner::new(MessageDeliveryInput {
+        bundle_header: true,
+        ..bridge.delivery_input(AlwaysRelayStrategy)
+    });
+    let receiving = BridgeSolochainReceivingRunner::new(MessageReceivingInput {
+        bundle_header: true,
+        ..bridge.receiving_input()
+    });
+    tokio::spawn(async move { delivery.start().await });
+
+    let delivered = wait_until(Duration::from_secs(60), || bridge.delivered(nonce)).await;
+    assert!(delivered, "message not delivered");
+
+    // the justification of this target header proves the delivery
+    bridge.client_target.produce_block();
+    tokio::spawn(async move { receiving.start().await });
+    let received = wait_until(Duration::from_secs(60), || bridge.received(nonce)).await;
+    assert!(received, "message not confirmed");
+
+    let message_number = *message_header.number();
+    let target_calls: Vec<MockCall> = bridge
+        .client_target
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .filter(|call| !matches!(call, MockCall::Initialize { .. }))
+        .collect();
+    assert!(matches!(
+        target_calls.as_slice(),
+        [MockCall::BatchAll { calls }] if matches!(
+            calls.as_slice(),
+            [
+                MockCall::SubmitFinalityProof { number, .. },
+                MockCall::ReceiveMessagesProof { .. },
+            ] if *number >= message_number
+        )
+    ));
+    let source_calls: Vec<MockCall> = bridge
+        .client_source
+        .extrinsics()
+        .into_iter()
+        .map(|extrinsic| extrinsic.call)
+        .filter(|call| !matches!(call, MockCall::Initialize { .. }))
+        .collect();
+    assert!(matches!(
+        source_calls.as_slice(),
+        [MockCall::BatchAll { calls }] if matches!(
+            calls.as_slice(),
+            [
+                MockCall::SubmitFinalityProof { .. },
+                MockCall::ReceiveMessagesDeliveryProof { .. },
+            ]
+        )
+    ));
+}
+
+#[tokio::test]
+async fn test_mandatory_justification_from_node() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+
+    // the indexer knows the mandatory header but not its justification
+    let mandatory_header = bridge.client_source.produce_block();
+    bridge.subquery_source.index_mandatory(&mandatory_header);
+
+    let header = SolochainHeaderRunner::new(bridge.header_input(true));
+    tokio::spawn(async move { header.start().await });
+
+    let mandatory_number = *mandatory_header.number();
+    let relayed = wait_until(Duration::from_secs(30), || {
+        bridge.finality_proof_submitted(mandatory_number)
+    })
+    .await;
+    assert!(relayed, "mandatory header not relayed");
+}
+
+#[tokio::test]
+async fn test_reject_invalid_justification() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+    // the target expects justifications signed by another authority
+    let other_authority = ed25519::Pair::from_seed(&[2u8; 32]);
+    bridge.client_target.state().bridged_authority_set = Some(AuthoritySet::new(
+        vec![(other_authority.public().into(), 1)],
+        0,
+    ));
+
+    let mandatory_header = bridge.client_source.produce_block();
+    bridge.subquery_source.index_mandatory(&mandatory_header);
+
+    let header = SolochainHeaderRunner::new(SolochainHeaderInput {
+        justification_sources: vec![JustificationSource::Node],
+        ..bridge.header_input(true)
+    });
+    let result = tokio::time::timeout(Duration::from_secs(30), header.start())
+        .await
+        .expect("header relay should stop with the verification error");
+    assert!(matches!(result, Err(RelayError::Justification(_))));
+    assert!(!bridge
+        .client_target
+        .extrinsics()
+        .iter()
+        .any(|extrinsic| matches!(extrinsic.call, MockCall::SubmitFinalityProof { .. })));
+}
+
+#[tokio::test]
+async fn test_chain_scan_indexer() {
+    let bridge = TestBridge::new().await;
+    bridge.initialize(false).await;
+
+    // the microkv isn't committed, nothing is written to the temp dir
+    let microkv = MicroKV::open_with_base_path(
+        format!("relay-s2s-chain-scan-{}", std::process::id()),
+        std::env::temp_dir(),
+    )
+    .unwrap();
+    let indexer = ChainScanIndexer::new(
+        microkv.namespace("mock-bridge"),
+        MockSourceChain::CHAIN,
+        OriginType::BridgePangoro,
+    );
+
+    let mandatory_header = bridge.client_source.schedule_authorities_change();
+    let (nonce, message_header) = bridge.client_source.send_message(LANE, vec![1, 2, 3], 100);
+    let scanner = indexer.clone();
+    let client = bridge.client_source.clone();
+    let client_bridged = bridge.client_target.clone();
+    tokio::spawn(async move { scanner.start(client, client_bridged, vec![LANE]).await });
+
+    let scanned = wait_until(Duration::from_secs(30), || {
+        matches!(indexer.query_need_relay(LANE, nonce), Ok(Some(_)))
+    })
+    .await;
+    assert!(scanned, "message block not scanned");
+    let mandatory_number = *mandatory_header.number();
+    assert_eq!(
+        indexer
+            .next_mandatory_header(0)
+            .unwrap()
+            .map(|block| block.block_number),
+        Some(mandatory_number),
+    );
+    assert!(indexer
+        .next_mandatory_header(mandatory_number)
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        indexer
+            .next_needed_header()
+            .unwrap()
+            .map(|block| block.block_number),
+        Some(*message_header.number()),
+    );
+
+    // the mandatory header is relayed with the justification recorded by scanner
+    let header = SolochainHeaderRunner::new(SolochainHeaderInput {
+        subquery_source: indexer.into(),
+        justification_sources: vec![JustificationSource::Indexer],
+        ..bridge.header_input(true)
+    });
+    tokio::spawn(async move { header.start().await });
+
+    let relayed = wait_until(Duration::from_secs(30), || {
+        bridge.finality_proof_submitted(mandatory_number)
+    })
+    .await;
+    assert!(relayed, "mandatory header not relayed");
+}
+
+fn justification_subscribed<H: Header<Number = u32>>(keepstate: &Keepstate, header: &H) -> bool {
+    let block_hash = array_bytes::bytes2hex("0x", header.hash());
+    keepstate
+        .get_justification_by_hash(MockSourceChain::CHAIN, &block_hash)
+        .unwrap()
+        .map(|justification| justification.block_number == *header.number())
+        .unwrap_or_default()
+}
+
+#[tokio::test]
+async fn test_resubscribe_justification() {
+    let bridge = TestBridge::new().await;
+    bridge.subscribe_justification(false).await;
+
+    let header = bridge.client_source.produce_block();
+    let subscribed = wait_until(Duration::from_secs(5), || {
+        justification_subscribed(&bridge.keepstate, &header)
+    })
+    .await;
+    assert!(subscribed, "justification not subscribed");
+
+    // blocks produced before resubscribing are missed, keep producing until one is subscribed
+    bridge.client_source.close_justification_subscriptions();
+    let mut resubscribed = false;
+    for _ in 0..50 {
+        let header = bridge.client_source.produce_block();
+        if wait_until(Duration::from_millis(200), || {
+            justification_subscribed(&bridge.keepstate, &header)
+        })
+        .await
+        {
+            resubscribed = true;
+            break;
+        }
+    }
+    assert!(
+        resubscribed,
+        "justification not subscribed after resubscribe"
+    );
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use support_metrics::metrics;
+
 use crate::types::{
     CollectedEnoughAuthoritiesChangeSignaturesEvent, CollectedEnoughNewMessageRootSignaturesEvent,
     CollectingAuthoritiesChangeSignaturesEvent, CollectingNewMessageRootSignaturesEvent,
@@ -12,6 +14,10 @@ impl Subquery {
         &self,
         block: u32,
     ) -> SubqueryComponentResult<Option<CollectedEnoughAuthoritiesChangeSignaturesEvent>> {
+        let _timer = metrics::indexer_query_timer(
+            "subquery",
+            "next_collected_enough_authorities_change_signatures_event",
+        );
         let query = self.read_graphql(
             "bridge_ethv2_next_collected_enough_authorities_change_signatures_event.query.graphql",
         )?;
@@ -34,6 +40,10 @@ impl Subquery {
         &self,
         block: u32,
     ) -> SubqueryComponentResult<Option<CollectedEnoughNewMessageRootSignaturesEvent>> {
+        let _timer = metrics::indexer_query_timer(
+            "subquery",
+            "next_collected_enough_new_message_root_signatures_event",
+        );
         let query = self.read_graphql(
             "bridge_ethv2_next_collected_enough_new_message_root_signatures_event.query.graphql",
         )?;
@@ -56,6 +66,10 @@ impl Subquery {
         &self,
         block: u32,
     ) -> SubqueryComponentResult<Option<CollectingAuthoritiesChangeSignaturesEvent>> {
+        let _timer = metrics::indexer_query_timer(
+            "subquery",
+            "next_collecting_authorities_change_signatures_event",
+        );
         let query = self.read_graphql(
             "bridge_ethv2_next_collecting_authorities_change_signatures_event.query.graphql",
         )?;
@@ -78,6 +92,10 @@ impl Subquery {
         &self,
         block: u32,
     ) -> SubqueryComponentResult<Option<CollectingNewMessageRootSignaturesEvent>> {
+        let _timer = metrics::indexer_query_timer(
+            "subquery",
+            "next_collecting_new_message_root_signatures_event",
+        );
         let query = self.read_graphql(
             "bridge_ethv2_next_collecting_new_message_root_signatures_event.query.graphql",
         )?;
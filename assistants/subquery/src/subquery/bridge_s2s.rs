@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+use support_metrics::metrics;
+
 #[cfg(feature = "bridge-parachain")]
 use crate::types::{CandidateIncludedEvent, QueryNextCandidateIncludedEventWithParaHeadVars};
 use crate::types::{
@@ -14,6 +16,7 @@ impl Subquery {
         &self,
         block_number: u32,
     ) -> SubqueryComponentResult<Option<NeedRelayBlock>> {
+        let _timer = metrics::indexer_query_timer("subquery", "next_mandatory_header");
         let query = self.read_graphql("bridge_s2s_next_header.query.graphql")?;
         let vars = QueryNextRelayBlockVars {
             block: block_number,
@@ -37,6 +40,7 @@ impl Subquery {
         &self,
         origin: OriginType,
     ) -> SubqueryComponentResult<Option<NeedRelayBlock>> {
+        let _timer = metrics::indexer_query_timer("subquery", "next_needed_header");
         let query = self.read_graphql("bridge_s2s_next_needed_header.query.graphql")?;
         let vars = QueryNextOnDemandBlockVars { origin };
         let data = self
@@ -57,6 +61,7 @@ impl Subquery {
         block_hash: impl AsRef<str>,
         is_mandatory: bool,
     ) -> SubqueryComponentResult<Option<JustificationMapping>> {
+        let _timer = metrics::indexer_query_timer("subquery", "find_justification");
         let query_by_hash =
             self.read_graphql("bridge_s2s_justification_mapping_by_hash.query.graphql")?;
         let query_latest =
@@ -85,6 +90,7 @@ impl Subquery {
         lane: [u8; 4],
         nonce: u64,
     ) -> SubqueryComponentResult<Option<NeedRelayBlock>> {
+        let _timer = metrics::indexer_query_timer("subquery", "query_need_relay");
         let query = self.read_graphql("bridge_s2s_query_need_relay.graphql")?;
         let lane_hex = array_bytes::bytes2hex("", &lane);
         let vars = QueryNeedRelay {
@@ -113,6 +119,7 @@ impl Subquery {
         &self,
         para_head_hash: impl AsRef<str>,
     ) -> SubqueryComponentResult<Option<CandidateIncludedEvent>> {
+        let _timer = metrics::indexer_query_timer("subquery", "get_block_with_para_head");
         let query = self.read_graphql(
             "bridge_s2s_next_candidate_included_event_with_para_head.query.graphql",
         )?;
@@ -1,3 +1,5 @@
+use support_metrics::metrics;
+
 use crate::error::{TheGraphLikethComponentError, ThegraphComponentReuslt};
 use crate::thegraph::Thegraph;
 use crate::types::{MessageAcceptedEvent, QueryMessageEventVars, TheGraphResponse};
@@ -8,6 +10,7 @@ impl Thegraph {
         &self,
         nonce: u64,
     ) -> ThegraphComponentReuslt<Option<MessageAcceptedEvent>> {
+        let _timer = metrics::indexer_query_timer("thegraph", "query_message_accepted");
         let query = self.read_graphql("message_accepted_event.query.graphql")?;
         let vars = QueryMessageEventVars { nonce };
         let data = self
@@ -1,5 +1,9 @@
+pub use self::feemarket::*;
 pub use self::init::*;
 pub use self::relay::*;
+pub use self::status::*;
 
+mod feemarket;
 mod init;
 mod relay;
+mod status;
@@ -1,7 +1,9 @@
-use bin_s2s::bridge::config::RelayConfig;
+use bin_s2s::bridge::config::{FeemarketConfig, RelayConfig};
 use bin_s2s::error::{BinS2SError, BinS2SResult};
 use bin_s2s::traits::{S2SBasicChainInfo, S2SParaBridgeRelayChainInfo, S2SParaBridgeSoloChainInfo};
 use bridge_s2s_traits::types::bp_runtime;
+use client_common_traits::reconnect::Endpoints;
+use client_common_traits::transaction::TransactionConfig;
 use client_common_traits::ClientCommon;
 use client_crab::component::CrabClientComponent;
 use client_darwinia::component::DarwiniaClientComponent;
@@ -10,6 +12,7 @@ use client_polkadot::component::PolkadotClientComponent;
 use serde::{Deserialize, Serialize};
 use subquery::types::OriginType;
 use subquery::SubqueryConfig;
+use support_metrics::config::MetricsConfig;
 
 use support_types::mark::ChainName;
 
@@ -20,6 +23,13 @@ pub enum BridgeFlow {
     CrabToDarwinia,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize, Serialize, strum::EnumString)]
+#[strum(serialize_all = "kebab_case")]
+pub enum FeemarketChain {
+    Darwinia,
+    Crab,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RawBridgeConfig {
     pub darwinia: DarwiniaChainConfig,
@@ -27,35 +37,58 @@ pub struct RawBridgeConfig {
     pub polkadot: PolkadotChainConfig,
     pub kusama: KusamaChainConfig,
     pub relay: RelayConfig,
+    #[serde(default)]
+    pub feemarket: FeemarketConfig,
     pub index: RawIndexConfig,
+    /// Prometheus metrics endpoint, disabled when not configured
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct DarwiniaChainConfig {
-    /// Endpoint
-    pub endpoint: String,
+    /// Endpoint, or a list of endpoints of the same chain to fail over
+    pub endpoint: Endpoints,
     pub signer: String,
+    /// The real account behind the signer, relay calls are dispatched through proxy if set
+    #[serde(default)]
+    pub real_account: Option<String>,
+    /// Tip and mortality of extrinsics
+    #[serde(default)]
+    pub transaction: TransactionConfig,
     pub para_id: u32,
+    /// Other parachains of the relay chain, their heads are relayed together with `para_id`
+    #[serde(default)]
+    pub extra_para_ids: Vec<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CrabChainConfig {
-    /// Endpoint
-    pub endpoint: String,
+    /// Endpoint, or a list of endpoints of the same chain to fail over
+    pub endpoint: Endpoints,
     pub signer: String,
+    /// The real account behind the signer, relay calls are dispatched through proxy if set
+    #[serde(default)]
+    pub real_account: Option<String>,
+    /// Tip and mortality of extrinsics
+    #[serde(default)]
+    pub transaction: TransactionConfig,
     pub para_id: u32,
+    /// Other parachains of the relay chain, their heads are relayed together with `para_id`
+    #[serde(default)]
+    pub extra_para_ids: Vec<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct KusamaChainConfig {
-    /// Endpoint
-    pub endpoint: String,
+    /// Endpoint, or a list of endpoints of the same chain to fail over
+    pub endpoint: Endpoints,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct PolkadotChainConfig {
-    /// Endpoint
-    pub endpoint: String,
+    /// Endpoint, or a list of endpoints of the same chain to fail over
+    pub endpoint: Endpoints,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -83,16 +116,20 @@ impl S2SParaBridgeSoloChainInfo for DarwiniaChainConfig {
     fn account(
         &self,
     ) -> BinS2SResult<<<Self::Client as ClientCommon>::Chain as bp_runtime::Chain>::AccountId> {
-        let account = client_darwinia::types::DarwiniaAccount::new(self.signer.clone(), None)
-            .map_err(|e| BinS2SError::Client(format!("{e:?}")))?;
-        Ok(*account.account_id())
+        let account = client_darwinia::types::DarwiniaAccount::new(
+            self.signer.clone(),
+            self.real_account.clone(),
+        )
+        .map_err(|e| BinS2SError::Client(format!("{e:?}")))?;
+        Ok(*account.real_account())
     }
 
     async fn client(&self) -> BinS2SResult<Self::Client> {
         let config = client_darwinia::config::ClientConfig {
             endpoint: self.endpoint.clone(),
             relayer_private_key: self.signer.clone(),
-            relayer_real_account: None,
+            relayer_real_account: self.real_account.clone(),
+            transaction: self.transaction.clone(),
         };
         Ok(DarwiniaClientComponent::component(config)
             .await
@@ -115,16 +152,20 @@ impl S2SParaBridgeSoloChainInfo for CrabChainConfig {
     fn account(
         &self,
     ) -> BinS2SResult<<<Self::Client as ClientCommon>::Chain as bp_runtime::Chain>::AccountId> {
-        let account = client_crab::types::DarwiniaAccount::new(self.signer.clone(), None)
-            .map_err(|e| BinS2SError::Client(format!("{e:?}")))?;
-        Ok(*account.account_id())
+        let account = client_crab::types::DarwiniaAccount::new(
+            self.signer.clone(),
+            self.real_account.clone(),
+        )
+        .map_err(|e| BinS2SError::Client(format!("{e:?}")))?;
+        Ok(*account.real_account())
     }
 
     async fn client(&self) -> BinS2SResult<Self::Client> {
         let config = client_crab::config::ClientConfig {
             endpoint: self.endpoint.clone(),
             relayer_private_key: self.signer.clone(),
-            relayer_real_account: None,
+            relayer_real_account: self.real_account.clone(),
+            transaction: self.transaction.clone(),
         };
         Ok(CrabClientComponent::component(config)
             .await
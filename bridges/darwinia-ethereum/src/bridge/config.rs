@@ -1,6 +1,7 @@
 use client_darwinia::client::DarwiniaClient;
 use client_darwinia::component::DarwiniaClientComponent;
 use serde::{Deserialize, Serialize};
+use support_metrics::config::MetricsConfig;
 
 use bin_e2e::config::{
     BeaconApiConfig, EVMChainConfig, ExecutionLayerInfoConfig, GeneralConfig, IndexConfig,
@@ -14,6 +15,9 @@ pub struct BridgeConfig {
     pub ethereum: ExecutionLayerInfoConfig,
     pub beacon: BeaconApiConfig,
     pub index: IndexConfig,
+    /// Prometheus metrics endpoint, disabled when not configured
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,9 +29,10 @@ pub struct DarwiniaSubstrateConfig {
 impl From<DarwiniaSubstrateConfig> for client_darwinia::config::ClientConfig {
     fn from(config: DarwiniaSubstrateConfig) -> Self {
         client_darwinia::config::ClientConfig {
-            endpoint: config.endpoint,
+            endpoint: config.endpoint.into(),
             relayer_private_key: config.private_key,
             relayer_real_account: None,
+            transaction: Default::default(),
         }
     }
 }
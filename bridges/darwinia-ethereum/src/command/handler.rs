@@ -10,6 +10,9 @@ use crate::bridge::BridgeConfig as RawBridgeConfig;
 pub async fn handle_start() -> color_eyre::Result<()> {
     tracing::info!("Start bridge darwinia-ethereum");
     let raw_config: RawBridgeConfig = Config::restore(Names::BridgeDarwiniaEthereum)?;
+    if let Some(metrics) = raw_config.metrics.clone() {
+        support_metrics::server::spawn(metrics);
+    }
     let bridge_config = BridgeConfig {
         name: BridgeName::DarwiniaEthereum.name().into(),
         general: raw_config.general,
@@ -5,6 +5,12 @@ use crate::command::types::Opts;
 pub async fn execute(opts: Opts) -> color_eyre::Result<()> {
     match opts {
         Opts::Init { bridge } => handler::handle_init(bridge).await,
-        Opts::Start => handler::handle_relay().await,
+        Opts::Start { dry_run } => handler::handle_relay(dry_run).await,
+        Opts::Status { output } => handler::handle_status(output).await,
+        Opts::Feemarket {
+            chain,
+            output,
+            command,
+        } => handler::handle_feemarket(chain, output, command).await,
     }
 }
@@ -0,0 +1,245 @@
+use bin_s2s::traits::S2SParaBridgeSoloChainInfo;
+use bin_s2s::types::HexLaneId;
+use feemarket_s2s_traits::api::{FeemarketApiQuote, FeemarketApiRelay};
+use feemarket_s2s_traits::types::{Chain, Relayer};
+use serde::Serialize;
+use term_table::row::Row;
+use term_table::table_cell::{Alignment, TableCell};
+use term_table::{Table, TableStyle};
+
+use support_common::config::{Config, Names};
+use support_terminal::output;
+use support_terminal::output::OutputFormat;
+use support_toolkit::convert::SmartCodecMapper;
+
+use crate::command::types::FeemarketOpts;
+use crate::types::{FeemarketChain, RawBridgeConfig};
+
+pub async fn handle_feemarket(
+    chain: FeemarketChain,
+    output: OutputFormat,
+    opts: FeemarketOpts,
+) -> color_eyre::Result<()> {
+    let bridge_config: RawBridgeConfig = Config::restore(Names::BridgePangolinPangoro)?;
+    match chain {
+        FeemarketChain::Pangolin => {
+            let chain_config = bridge_config.pangolin;
+            let client = chain_config.client().await?;
+            let account = chain_config.account()?;
+            handle_opts(client, account, output, opts).await
+        }
+        FeemarketChain::Pangoro => {
+            let chain_config = bridge_config.pangoro;
+            let client = chain_config.client().await?;
+            let account = chain_config.account()?;
+            handle_opts(client, account, output, opts).await
+        }
+    }
+}
+
+async fn handle_opts<A: FeemarketApiQuote + FeemarketApiRelay>(
+    api: A,
+    account: <A::Chain as Chain>::AccountId,
+    output: OutputFormat,
+    opts: FeemarketOpts,
+) -> color_eyre::Result<()> {
+    match opts {
+        FeemarketOpts::Relayers => {
+            let mut relayers = Vec::new();
+            for id in api.relayers().await? {
+                if let Some(relayer) = api.relayer(id).await? {
+                    relayers.push(RelayerView::new::<A>(relayer)?);
+                }
+            }
+            output_relayers(relayers, output)
+        }
+        FeemarketOpts::AssignedRelayers => {
+            let relayers = api
+                .assigned_relayers()
+                .await?
+                .into_iter()
+                .map(RelayerView::new::<A>)
+                .collect::<color_eyre::Result<Vec<RelayerView>>>()?;
+            output_relayers(relayers, output)
+        }
+        FeemarketOpts::Relayer => match api.relayer(account.clone()).await? {
+            Some(relayer) => output_relayers(vec![RelayerView::new::<A>(relayer)?], output),
+            None => output::output_err_and_exit(format!(
+                "The account {:?} is not a relayer, please enroll first",
+                account
+            )),
+        },
+        FeemarketOpts::UpdateFee { amount } => {
+            let amount = SmartCodecMapper::map_to(&amount)?;
+            api.update_relay_fee(amount).await?;
+            output::output_ok();
+            Ok(())
+        }
+        FeemarketOpts::UpdateCollateral { amount } => {
+            let amount = SmartCodecMapper::map_to(&amount)?;
+            api.update_locked_collateral(amount).await?;
+            output::output_ok();
+            Ok(())
+        }
+        FeemarketOpts::Order { lane, nonce } => match api.order(lane.0, nonce).await? {
+            Some(order) => {
+                let mut assigned_relayers = Vec::with_capacity(order.assigned_relayers.len());
+                for relayer in order.assigned_relayers {
+                    assigned_relayers.push(AssignedRelayerView {
+                        id: format!("{:?}", relayer.id),
+                        fee: SmartCodecMapper::map_to(&relayer.fee)?,
+                        valid_from: SmartCodecMapper::map_to(&relayer.valid_range.start)?,
+                        valid_to: SmartCodecMapper::map_to(&relayer.valid_range.end)?,
+                    });
+                }
+                let view = OrderView {
+                    lane: HexLaneId(order.lane).to_string(),
+                    nonce: order.message,
+                    sent_time: SmartCodecMapper::map_to(&order.sent_time)?,
+                    confirm_time: match order.confirm_time {
+                        Some(v) => Some(SmartCodecMapper::map_to(&v)?),
+                        None => None,
+                    },
+                    assigned_relayers,
+                };
+                output_order(view, output)
+            }
+            None => output::output_err_and_exit(format!(
+                "Not found order by lane {} and nonce {}",
+                lane, nonce
+            )),
+        },
+    }
+}
+
+#[derive(Serialize)]
+struct RelayerView {
+    id: String,
+    collateral: u128,
+    fee: u128,
+}
+
+impl RelayerView {
+    fn new<A: FeemarketApiQuote>(
+        relayer: Relayer<<A::Chain as Chain>::AccountId, <A::Chain as Chain>::Balance>,
+    ) -> color_eyre::Result<Self> {
+        Ok(Self {
+            id: format!("{:?}", relayer.id),
+            collateral: SmartCodecMapper::map_to(&relayer.collateral)?,
+            fee: SmartCodecMapper::map_to(&relayer.fee)?,
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct AssignedRelayerView {
+    id: String,
+    fee: u128,
+    valid_from: u32,
+    valid_to: u32,
+}
+
+#[derive(Serialize)]
+struct OrderView {
+    lane: String,
+    nonce: u64,
+    sent_time: u32,
+    confirm_time: Option<u32>,
+    assigned_relayers: Vec<AssignedRelayerView>,
+}
+
+fn output_relayers(
+    relayers: Vec<RelayerView>,
+    output_format: OutputFormat,
+) -> color_eyre::Result<()> {
+    match output_format {
+        OutputFormat::Raw => {
+            for relayer in relayers {
+                output::output_text(format!(
+                    "{} collateral: {} fee: {}",
+                    relayer.id, relayer.collateral, relayer.fee
+                ));
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&relayers)?;
+            output::output_text(json);
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.max_column_width = 70;
+            table.style = TableStyle::simple();
+            table.add_row(text_row(vec!["relayer", "collateral", "fee"]));
+            for relayer in relayers {
+                table.add_row(text_row(vec![
+                    relayer.id,
+                    relayer.collateral.to_string(),
+                    relayer.fee.to_string(),
+                ]));
+            }
+            output::output_text(table.render());
+        }
+    }
+    Ok(())
+}
+
+fn output_order(order: OrderView, output_format: OutputFormat) -> color_eyre::Result<()> {
+    let confirm_time = order
+        .confirm_time
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    match output_format {
+        OutputFormat::Raw => {
+            output::output_text(format!("lane: {}", order.lane));
+            output::output_text(format!("nonce: {}", order.nonce));
+            output::output_text(format!("sent_time: {}", order.sent_time));
+            output::output_text(format!("confirm_time: {}", confirm_time));
+            for relayer in order.assigned_relayers {
+                output::output_text(format!(
+                    "assigned relayer: {} fee: {} slot: [{}, {})",
+                    relayer.id, relayer.fee, relayer.valid_from, relayer.valid_to
+                ));
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&order)?;
+            output::output_text(json);
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.max_column_width = 70;
+            table.style = TableStyle::simple();
+            table.add_row(text_row(vec!["lane".to_string(), order.lane]));
+            table.add_row(text_row(vec!["nonce".to_string(), order.nonce.to_string()]));
+            table.add_row(text_row(vec![
+                "sent_time".to_string(),
+                order.sent_time.to_string(),
+            ]));
+            table.add_row(text_row(vec!["confirm_time".to_string(), confirm_time]));
+            output::output_text(table.render());
+
+            let mut table = Table::new();
+            table.max_column_width = 70;
+            table.style = TableStyle::simple();
+            table.add_row(text_row(vec!["assigned relayer", "fee", "slot"]));
+            for relayer in order.assigned_relayers {
+                table.add_row(text_row(vec![
+                    relayer.id,
+                    relayer.fee.to_string(),
+                    format!("[{}, {})", relayer.valid_from, relayer.valid_to),
+                ]));
+            }
+            output::output_text(table.render());
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn text_row<T: ToString>(cells: Vec<T>) -> Row<'static> {
+    Row::new(
+        cells
+            .into_iter()
+            .map(|cell| TableCell::new_with_alignment(cell.to_string(), 1, Alignment::Left))
+            .collect::<Vec<TableCell>>(),
+    )
+}
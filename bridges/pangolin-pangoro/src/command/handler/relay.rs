@@ -6,17 +6,43 @@ use bin_s2s::types::BasicSubqueryInfo;
 use support_common::config::{Config, Names};
 use support_types::mark::BridgeName;
 
-use crate::types::RawBridgeConfig;
+use crate::types::{
+    MoonbaseChainConfig, PangolinChainConfig, PangoroChainConfig, RococoChainConfig, RawBridgeConfig,
+};
 
-pub async fn handle_relay() -> color_eyre::Result<()> {
+pub async fn handle_relay(dry_run: bool) -> color_eyre::Result<()> {
     tracing::info!(target: "pangolin-pangoro", "start bridge pangolin-pangoro");
-    let raw_bridge_config: RawBridgeConfig = Config::restore(Names::BridgePangolinPangoro)?;
+    let mut raw_bridge_config: RawBridgeConfig = Config::restore(Names::BridgePangolinPangoro)?;
+    raw_bridge_config.relay.dry_run |= dry_run;
+    if let Some(metrics) = raw_bridge_config.metrics.clone() {
+        support_metrics::server::spawn(metrics);
+    }
+    let _task = BridgeTask::new(bridge_config(raw_bridge_config))?;
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+    }
+}
+
+/// Bridge config of para with para bridge
+pub fn bridge_config(
+    raw_bridge_config: RawBridgeConfig,
+) -> BridgeConfig<
+    RococoChainConfig,
+    PangolinChainConfig,
+    MoonbaseChainConfig,
+    PangoroChainConfig,
+    BasicSubqueryInfo,
+> {
     let raw_config_index = raw_bridge_config.index;
     let (source_para_id, target_para_id) = (
         raw_bridge_config.pangolin.para_id,
         raw_bridge_config.pangoro.para_id,
     );
-    let bridge_config = BridgeConfig {
+    let (source_extra_para_ids, target_extra_para_ids) = (
+        raw_bridge_config.pangolin.extra_para_ids.clone(),
+        raw_bridge_config.pangoro.extra_para_ids.clone(),
+    );
+    BridgeConfig {
         chain: ChainConfig {
             source_para: raw_bridge_config.pangolin,
             source_relay: raw_bridge_config.rococo,
@@ -24,9 +50,12 @@ pub async fn handle_relay() -> color_eyre::Result<()> {
             target_relay: raw_bridge_config.moonbase,
         },
         relay: raw_bridge_config.relay,
+        feemarket: raw_bridge_config.feemarket,
         para_config: ParaWithParaConfig {
             source_para_id,
             target_para_id,
+            source_extra_para_ids,
+            target_extra_para_ids,
         },
         index: IndexConfig {
             source_para: BasicSubqueryInfo::new(
@@ -40,9 +69,5 @@ pub async fn handle_relay() -> color_eyre::Result<()> {
             target_para: BasicSubqueryInfo::new(BridgeName::PangolinPangoro, raw_config_index.pangoro),
             target_relay: BasicSubqueryInfo::new(BridgeName::PangolinPangoro, raw_config_index.moonbase),
         },
-    };
-    let _task = BridgeTask::new(bridge_config)?;
-    loop {
-        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
     }
 }
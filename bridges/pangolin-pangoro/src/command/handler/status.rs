@@ -0,0 +1,101 @@
+use bin_s2s::bridge::para_with_para::BridgeTask;
+use bin_s2s::types::HexLaneId;
+use term_table::{Table, TableStyle};
+
+use support_common::config::{Config, Names};
+use support_terminal::output;
+use support_terminal::output::OutputFormat;
+
+use crate::command::handler::feemarket::text_row;
+use crate::command::handler::relay::bridge_config;
+use crate::types::RawBridgeConfig;
+
+pub async fn handle_status(output_format: OutputFormat) -> color_eyre::Result<()> {
+    let raw_bridge_config: RawBridgeConfig = Config::restore(Names::BridgePangolinPangoro)?;
+    let statuses = BridgeTask::status(bridge_config(raw_bridge_config)).await?;
+    match output_format {
+        OutputFormat::Raw => {
+            for item in statuses {
+                let status = item.status;
+                output::output_text(format!(
+                    "[{}-to-{}] lane {}",
+                    item.source.name(),
+                    item.target.name(),
+                    HexLaneId(status.lane),
+                ));
+                output::output_text(format!(
+                    "  outbound lane: generated {} received {}",
+                    status.latest_generated_nonce, status.latest_received_nonce,
+                ));
+                output::output_text(format!(
+                    "  inbound lane: delivered {} confirmed {}",
+                    status.last_delivered_nonce, status.last_confirmed_nonce,
+                ));
+                for relayer in status.unrewarded_relayers {
+                    output::output_text(format!(
+                        "  unrewarded relayer: {} [{}, {}]",
+                        relayer.relayer, relayer.begin, relayer.end,
+                    ));
+                }
+                output::output_text(format!(
+                    "  last relayed header: {} nonce block: {}",
+                    optional_text(status.last_relayed_block),
+                    optional_text(status.nonce_block),
+                ));
+                if let Some(nonce) = status.over_limits_nonce {
+                    output::output_text(format!(
+                        "  stuck at nonce {}: over the limits of target chain",
+                        nonce,
+                    ));
+                }
+                output::output_text(format!("  decision: {}", status.decision));
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&statuses)?;
+            output::output_text(json);
+        }
+        OutputFormat::Table => {
+            let mut table = Table::new();
+            table.max_column_width = 40;
+            table.style = TableStyle::simple();
+            table.add_row(text_row(vec![
+                "direction",
+                "lane",
+                "generated",
+                "received",
+                "delivered",
+                "confirmed",
+                "unrewarded relayers",
+                "last relayed header",
+                "nonce block",
+                "stuck nonce",
+                "decision",
+            ]));
+            for item in statuses {
+                let status = item.status;
+                table.add_row(text_row(vec![
+                    format!("{}-to-{}", item.source.name(), item.target.name()),
+                    HexLaneId(status.lane).to_string(),
+                    status.latest_generated_nonce.to_string(),
+                    status.latest_received_nonce.to_string(),
+                    status.last_delivered_nonce.to_string(),
+                    status.last_confirmed_nonce.to_string(),
+                    status.unrewarded_relayers.len().to_string(),
+                    optional_text(status.last_relayed_block),
+                    optional_text(status.nonce_block),
+                    optional_text(status.over_limits_nonce),
+                    status.decision.to_string(),
+                ]));
+            }
+            output::output_text(table.render());
+        }
+    }
+    Ok(())
+}
+
+fn optional_text<T: ToString>(value: Option<T>) -> String {
+    value
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
@@ -1,6 +1,9 @@
+use bin_s2s::types::HexLaneId;
 use structopt::StructOpt;
 
-use crate::types::BridgeFlow;
+use support_terminal::output::OutputFormat;
+
+use crate::types::{BridgeFlow, FeemarketChain};
 
 /// Bridge pangolin-pangoro operations
 #[derive(Debug, StructOpt)]
@@ -13,5 +16,59 @@ pub enum Opts {
         bridge: BridgeFlow,
     },
     /// Start bridge
-    Start,
+    Start {
+        /// Go through the whole relay decision path and log the calls instead of submitting them
+        #[structopt(long)]
+        dry_run: bool,
+    },
+    /// Show message delivery status of all lanes
+    Status {
+        /// Output mode, support raw|table|json
+        #[structopt(short, long, default_value = "raw")]
+        output: OutputFormat,
+    },
+    /// Fee market operations
+    Feemarket {
+        /// The chain of fee market, [pangolin | pangoro]
+        #[structopt(short, long)]
+        chain: FeemarketChain,
+        /// Output mode, support raw|table|json
+        #[structopt(short, long, default_value = "raw")]
+        output: OutputFormat,
+        /// Fee market commands
+        #[structopt(subcommand)]
+        command: FeemarketOpts,
+    },
+}
+
+/// Fee market options
+#[derive(Debug, StructOpt)]
+pub enum FeemarketOpts {
+    /// List all enrolled relayers
+    Relayers,
+    /// List assigned relayers
+    AssignedRelayers,
+    /// Show relayer info of the signer
+    Relayer,
+    /// Update relay fee of the signer
+    UpdateFee {
+        /// The new relay fee
+        #[structopt()]
+        amount: u128,
+    },
+    /// Update locked collateral of the signer
+    UpdateCollateral {
+        /// The new locked collateral
+        #[structopt()]
+        amount: u128,
+    },
+    /// Inspect an order
+    Order {
+        /// Lane id, e.g. 0x726f6c69
+        #[structopt(short, long)]
+        lane: HexLaneId,
+        /// Message nonce
+        #[structopt(short, long)]
+        nonce: u64,
+    },
 }
@@ -1,6 +1,7 @@
 use client_pangoro::client::PangoroClient;
 use client_pangoro::component::PangoroClientComponent;
 use serde::{Deserialize, Serialize};
+use support_metrics::config::MetricsConfig;
 
 use bin_e2e::config::{
     BeaconApiConfig, EVMChainConfig, ExecutionLayerInfoConfig, GeneralConfig, IndexConfig,
@@ -14,6 +15,9 @@ pub struct BridgeConfig {
     pub goerli: ExecutionLayerInfoConfig,
     pub beacon: BeaconApiConfig,
     pub index: IndexConfig,
+    /// Prometheus metrics endpoint, disabled when not configured
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,9 +29,10 @@ pub struct PangoroSubstrateConfig {
 impl From<PangoroSubstrateConfig> for client_pangoro::config::ClientConfig {
     fn from(config: PangoroSubstrateConfig) -> Self {
         client_pangoro::config::ClientConfig {
-            endpoint: config.endpoint,
+            endpoint: config.endpoint.into(),
             relayer_private_key: config.private_key,
             relayer_real_account: None,
+            transaction: Default::default(),
         }
     }
 }
@@ -9,6 +9,9 @@ use crate::bridge::BridgeConfig as RawBridgeConfig;
 pub async fn handle_start() -> color_eyre::Result<()> {
     tracing::info!("Start bridge pangoro-goerli");
     let raw_config: RawBridgeConfig = Config::restore(Names::BridgePangoroGoerli)?;
+    if let Some(metrics) = raw_config.metrics.clone() {
+        support_metrics::server::spawn(metrics);
+    }
     let bridge_config = BridgeConfig {
         name: BridgeName::PangoroGoerli.name().into(),
         general: raw_config.general,
@@ -0,0 +1,10 @@
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+/// Metrics endpoint config
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MetricsConfig {
+    /// Listen address of the http server, metrics are served at `http://<listen>/metrics`
+    pub listen: SocketAddr,
+}
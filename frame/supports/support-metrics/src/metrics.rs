@@ -0,0 +1,222 @@
+use once_cell::sync::Lazy;
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramTimer, HistogramVec, IntCounterVec, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+/// Metrics of all bridge services, registered at the first report
+static METRICS: Lazy<Metrics> =
+    Lazy::new(|| Metrics::new().expect("Failed to register bridger metrics"));
+
+struct Metrics {
+    registry: Registry,
+    best_finalized_header: IntGaugeVec,
+    lane_nonce: IntGaugeVec,
+    extrinsics_submitted: IntCounterVec,
+    extrinsics_failed: IntCounterVec,
+    strategy_decisions: IntCounterVec,
+    lost_races: IntCounterVec,
+    indexer_query_duration: HistogramVec,
+    beacon_api_errors: IntCounterVec,
+    ecdsa_signatures_submitted: IntCounterVec,
+    ecdsa_gas_used: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> prometheus::Result<Self> {
+        let registry = Registry::new_custom(Some("bridger".to_string()), None)?;
+        let metrics = Self {
+            best_finalized_header: IntGaugeVec::new(
+                Opts::new(
+                    "best_finalized_header",
+                    "Block number of the best source chain header relayed to target chain",
+                ),
+                &["source", "target"],
+            )?,
+            lane_nonce: IntGaugeVec::new(
+                Opts::new(
+                    "lane_nonce",
+                    "Message nonces of lane, the type is generated, delivered, confirmed or over-limits",
+                ),
+                &["source", "target", "lane", "type"],
+            )?,
+            extrinsics_submitted: IntCounterVec::new(
+                Opts::new(
+                    "extrinsics_submitted_total",
+                    "Extrinsics submitted to chain",
+                ),
+                &["chain", "call"],
+            )?,
+            extrinsics_failed: IntCounterVec::new(
+                Opts::new(
+                    "extrinsics_failed_total",
+                    "Extrinsics failed to submit to chain",
+                ),
+                &["chain", "call"],
+            )?,
+            strategy_decisions: IntCounterVec::new(
+                Opts::new(
+                    "strategy_decisions_total",
+                    "Decisions of message relay strategy",
+                ),
+                &["source", "target", "decision"],
+            )?,
+            lost_races: IntCounterVec::new(
+                Opts::new(
+                    "lost_races_total",
+                    "Relay work already done by other relayers, before submission or after inclusion",
+                ),
+                &["chain", "call", "stage"],
+            )?,
+            indexer_query_duration: HistogramVec::new(
+                HistogramOpts::new(
+                    "indexer_query_duration_seconds",
+                    "Latency of subquery and thegraph queries",
+                ),
+                &["indexer", "query"],
+            )?,
+            beacon_api_errors: IntCounterVec::new(
+                Opts::new("beacon_api_errors_total", "Failed requests of beacon api"),
+                &["kind"],
+            )?,
+            ecdsa_signatures_submitted: IntCounterVec::new(
+                Opts::new(
+                    "ecdsa_signatures_submitted_total",
+                    "ECDSA signatures submitted by authority",
+                ),
+                &["operation"],
+            )?,
+            ecdsa_gas_used: IntCounterVec::new(
+                Opts::new(
+                    "ecdsa_gas_used_total",
+                    "Gas used to import collected ECDSA signatures",
+                ),
+                &["operation"],
+            )?,
+            registry,
+        };
+        let collectors: Vec<Box<dyn Collector>> = vec![
+            Box::new(metrics.best_finalized_header.clone()),
+            Box::new(metrics.lane_nonce.clone()),
+            Box::new(metrics.extrinsics_submitted.clone()),
+            Box::new(metrics.extrinsics_failed.clone()),
+            Box::new(metrics.strategy_decisions.clone()),
+            Box::new(metrics.lost_races.clone()),
+            Box::new(metrics.indexer_query_duration.clone()),
+            Box::new(metrics.beacon_api_errors.clone()),
+            Box::new(metrics.ecdsa_signatures_submitted.clone()),
+            Box::new(metrics.ecdsa_gas_used.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector)?;
+        }
+        Ok(metrics)
+    }
+}
+
+/// Best finalized header of source chain which is imported by target chain
+pub fn header_relayed(source: &str, target: &str, block_number: u32) {
+    METRICS
+        .best_finalized_header
+        .with_label_values(&[source, target])
+        .set(block_number as i64);
+}
+
+/// Nonce of message lane, the kind is one of `generated`, `delivered`, `confirmed` and
+/// `over-limits`, the `over-limits` nonce is 0 if the lane isn't stuck
+pub fn lane_nonce(source: &str, target: &str, lane: &str, kind: &str, nonce: u64) {
+    METRICS
+        .lane_nonce
+        .with_label_values(&[source, target, lane, kind])
+        .set(nonce as i64);
+}
+
+/// Count the result of an extrinsic submission
+pub fn record_extrinsic<T, E>(chain: &str, call: &str, result: &Result<T, E>) {
+    let counter = match result {
+        Ok(_) => &METRICS.extrinsics_submitted,
+        Err(_) => &METRICS.extrinsics_failed,
+    };
+    counter.with_label_values(&[chain, call]).inc();
+}
+
+/// Decision of message relay strategy, e.g. `accepted`, `partially-accepted` or `refused`
+pub fn strategy_decision(source: &str, target: &str, decision: &str) {
+    METRICS
+        .strategy_decisions
+        .with_label_values(&[source, target, decision])
+        .inc();
+}
+
+/// Relay work lost to another relayer, the stage is `before-submit` when the submission
+/// is skipped and `after-inclusion` when our extrinsic failed because of it
+pub fn lost_race(chain: &str, call: &str, stage: &str) {
+    METRICS
+        .lost_races
+        .with_label_values(&[chain, call, stage])
+        .inc();
+}
+
+/// Start a timer of indexer query, the latency is observed when the timer dropped
+pub fn indexer_query_timer(indexer: &str, query: &str) -> HistogramTimer {
+    METRICS
+        .indexer_query_duration
+        .with_label_values(&[indexer, query])
+        .start_timer()
+}
+
+/// Failed beacon api request, the kind is `request` for transport errors and `response`
+/// for error responses
+pub fn beacon_api_error(kind: &str) {
+    METRICS.beacon_api_errors.with_label_values(&[kind]).inc();
+}
+
+/// ECDSA signature submitted by authority
+pub fn ecdsa_signature_submitted(operation: &str) {
+    METRICS
+        .ecdsa_signatures_submitted
+        .with_label_values(&[operation])
+        .inc();
+}
+
+/// Gas used by the transaction which imports collected ECDSA signatures
+pub fn ecdsa_gas_used(operation: &str, gas: u64) {
+    METRICS
+        .ecdsa_gas_used
+        .with_label_values(&[operation])
+        .inc_by(gas);
+}
+
+/// Encode all metrics in prometheus text format
+pub fn render() -> color_eyre::Result<String> {
+    let mut buffer = Vec::new();
+    TextEncoder::new().encode(&METRICS.registry.gather(), &mut buffer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        header_relayed("pangolin", "pangoro", 10);
+        lane_nonce("pangolin", "pangoro", "0x726f6c69", "generated", 3);
+        record_extrinsic::<(), ()>("pangoro", "receive_messages_proof", &Ok(()));
+        record_extrinsic::<(), ()>("pangoro", "receive_messages_proof", &Err(()));
+
+        let text = render().unwrap();
+        assert!(text
+            .contains(r#"bridger_best_finalized_header{source="pangolin",target="pangoro"} 10"#));
+        assert!(text.contains(
+            r#"bridger_lane_nonce{lane="0x726f6c69",source="pangolin",target="pangoro",type="generated"} 3"#
+        ));
+        assert!(text.contains(
+            r#"bridger_extrinsics_submitted_total{call="receive_messages_proof",chain="pangoro"} 1"#
+        ));
+        assert!(text.contains(
+            r#"bridger_extrinsics_failed_total{call="receive_messages_proof",chain="pangoro"} 1"#
+        ));
+    }
+}
@@ -0,0 +1,61 @@
+use std::convert::Infallible;
+
+use hyper::header::CONTENT_TYPE;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Method, Request, Response, Server, StatusCode};
+
+use crate::config::MetricsConfig;
+use crate::metrics;
+
+/// Serve metrics in background, the server error is only logged, it shouldn't stop bridge
+pub fn spawn(config: MetricsConfig) {
+    tokio::spawn(async move {
+        if let Err(e) = serve(config).await {
+            tracing::error!(target: "metrics", "metrics endpoint stopped: {:?}", e);
+        }
+    });
+}
+
+/// Serve metrics at `/metrics`
+pub async fn serve(config: MetricsConfig) -> color_eyre::Result<()> {
+    let make_service =
+        make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(handle_request)) });
+    let server = Server::try_bind(&config.listen)?.serve(make_service);
+    tracing::info!(
+        target: "metrics",
+        "metrics endpoint listening on http://{}/metrics",
+        config.listen,
+    );
+    server.await?;
+    Ok(())
+}
+
+async fn handle_request(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if request.method() != Method::GET || request.uri().path() != "/metrics" {
+        return Ok(response(StatusCode::NOT_FOUND, "Not found".to_string()));
+    }
+    match metrics::render() {
+        Ok(text) => Ok(response(StatusCode::OK, text)),
+        Err(e) => {
+            tracing::error!(target: "metrics", "failed to render metrics: {:?}", e);
+            Ok(response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("{e:?}"),
+            ))
+        }
+    }
+}
+
+fn response(status: StatusCode, body: String) -> Response<Body> {
+    let mut response = Response::new(Body::from(body));
+    *response.status_mut() = status;
+    if status == StatusCode::OK {
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            prometheus::TEXT_FORMAT
+                .parse()
+                .expect("The content type of prometheus is valid"),
+        );
+    }
+    response
+}
@@ -10,6 +10,7 @@ use subxt::rpc::Subscription;
 
 use crate::error::S2SClientResult;
 use crate::types::bp_runtime::Chain;
+use crate::types::{ExtrinsicLocation, MessagesDeliveryLimits};
 
 /// S2S bridge client types defined
 pub trait S2SClientBase: ClientCommon {
@@ -35,6 +36,11 @@ pub trait S2SClientGeneric: S2SClientBase {
         &self,
     ) -> S2SClientResult<Subscription<sp_core::Bytes>>;
 
+    /// subscribe finalized heads
+    async fn subscribe_finalized_heads(
+        &self,
+    ) -> S2SClientResult<Subscription<<Self::Chain as Chain>::Header>>;
+
     /// query header by hash
     async fn header(
         &self,
@@ -48,6 +54,22 @@ pub trait S2SClientGeneric: S2SClientBase {
         hash: Option<<Self::Chain as Chain>::Hash>,
     ) -> S2SClientResult<Option<SignedBlock<Block<<Self::Chain as Chain>::Header, Self::Extrinsic>>>>;
 
+    /// query hash of the best finalized block
+    async fn finalized_head(&self) -> S2SClientResult<<Self::Chain as Chain>::Hash>;
+
+    /// query block hash by number
+    async fn block_hash(
+        &self,
+        number: <Self::Chain as Chain>::BlockNumber,
+    ) -> S2SClientResult<Option<<Self::Chain as Chain>::Hash>>;
+
+    /// find grandpa justification which finalizes the block from node, the justification
+    /// stored with block is preferred, otherwise it's proved by `grandpa_proveFinality`
+    async fn find_justification(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<Option<sp_core::Bytes>>;
+
     /// read proof
     async fn read_proof(
         &self,
@@ -78,6 +100,10 @@ pub trait S2SClientRelay: S2SClientGeneric {
         nonces: RangeInclusive<u64>,
     ) -> S2SClientResult<u64>;
 
+    /// query limits of `receive_messages_proof` in the runtime, the messages proof delivered
+    /// to this chain should fit them
+    async fn messages_delivery_limits(&self) -> S2SClientResult<MessagesDeliveryLimits>;
+
     /// query best target finalized at source
     async fn best_target_finalized(
         &self,
@@ -89,6 +115,13 @@ pub trait S2SClientRelay: S2SClientGeneric {
         )>,
     >;
 
+    /// query current grandpa authority set of bridged chain, it's used to verify the
+    /// justifications before `submit_finality_proof`
+    async fn current_authority_set(
+        &self,
+        at_block: Option<<Self::Chain as Chain>::Hash>,
+    ) -> S2SClientResult<Option<bp_header_chain::AuthoritySet>>;
+
     /// initialize bridge
     async fn initialize(
         &self,
@@ -125,7 +158,8 @@ pub trait S2SClientRelay: S2SClientGeneric {
         hash: Option<<Self::Chain as Chain>::Hash>,
     ) -> S2SClientResult<Option<bp_messages::MessageData<u128>>>;
 
-    /// send receive messages proof extrinsics
+    /// send receive messages proof extrinsics, the `messages_fee` is the total fee of
+    /// relayed messages which may scale the tip of extrinsic
     async fn receive_messages_proof(
         &self,
         relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
@@ -134,8 +168,28 @@ pub trait S2SClientRelay: S2SClientGeneric {
         >,
         messages_count: u32,
         dispatch_weight: Weight,
+        messages_fee: u128,
     ) -> S2SClientResult<<Self::Chain as Chain>::Hash>;
 
+    /// look up the extrinsic submitted by this client, it's searched in the transaction pool
+    /// and the recent blocks within the mortality period of extrinsics
+    async fn find_extrinsic(
+        &self,
+        hash: <Self::Chain as Chain>::Hash,
+    ) -> S2SClientResult<ExtrinsicLocation<<Self::Chain as Chain>::Hash>>;
+
+    /// estimate fee of `receive_messages_proof` extrinsics, the tip is excluded. It returns
+    /// none if the pre-flight check is disabled
+    async fn estimate_receive_messages_proof_fee(
+        &self,
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+    ) -> S2SClientResult<Option<u128>>;
+
     /// receive messages delivery proof
     async fn receive_messages_delivery_proof(
         &self,
@@ -144,6 +198,37 @@ pub trait S2SClientRelay: S2SClientGeneric {
         >,
         relayers_state: bp_messages::UnrewardedRelayersState,
     ) -> S2SClientResult<<Self::Chain as Chain>::Hash>;
+
+    /// send `submit_finality_proof` of the header which the messages proof is read at and
+    /// `receive_messages_proof` in one `utility.batch_all` extrinsic
+    async fn receive_messages_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        relayer_id_at_bridged_chain: <Self::Chain as Chain>::AccountId,
+        proof: bridge_runtime_common::messages::target::FromBridgedChainMessagesProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        messages_count: u32,
+        dispatch_weight: Weight,
+        messages_fee: u128,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash>;
+
+    /// send `submit_finality_proof` of the header which the delivery proof is read at and
+    /// `receive_messages_delivery_proof` in one `utility.batch_all` extrinsic
+    async fn receive_messages_delivery_proof_with_finality(
+        &self,
+        finality: (
+            <Self::Chain as Chain>::Header,
+            bp_header_chain::justification::GrandpaJustification<<Self::Chain as Chain>::Header>,
+        ),
+        proof: bridge_runtime_common::messages::source::FromBridgedChainMessagesDeliveryProof<
+            <Self::Chain as Chain>::Hash,
+        >,
+        relayers_state: bp_messages::UnrewardedRelayersState,
+    ) -> S2SClientResult<<Self::Chain as Chain>::Hash>;
 }
 
 /// S2S with parachain bridge api for solo chain
@@ -1,3 +1,5 @@
+use std::ops::RangeInclusive;
+
 use crate::error::S2SClientResult;
 use crate::types::bp_messages::LaneId;
 
@@ -13,6 +15,68 @@ pub struct RelayReference {
     pub lane: LaneId,
     /// nonces
     pub nonce: u64,
+    /// count of messages delivered together with this nonce
+    pub messages_count: u64,
+    /// size of messages proof delivered together with this nonce
+    pub message_size: usize,
+    /// total dispatch weight of messages delivered together with this nonce
+    pub total_weight: u64,
+    /// fee of the delivery extrinsic estimated by `payment_queryInfo`, it's none if the
+    /// pre-flight check is disabled
+    pub estimated_fee: Option<u128>,
+}
+
+/// relay decide for a batch of nonces
+#[async_trait::async_trait]
+pub trait BatchRelayStrategy: 'static + Clone + Send + Sync {
+    /// decide which nonces to relay, the result should be a prefix of the reference nonces,
+    /// return `None` means don't relay any nonce.
+    async fn decide_batch(
+        &mut self,
+        reference: BatchRelayReference,
+    ) -> S2SClientResult<Option<RangeInclusive<u64>>>;
+}
+
+/// batch decide reference
+pub struct BatchRelayReference {
+    pub lane: LaneId,
+    /// nonces
+    pub nonces: RangeInclusive<u64>,
+    /// size of messages proof
+    pub message_size: usize,
+    /// total dispatch weight of messages
+    pub total_weight: u64,
+    /// estimated fee of the delivery extrinsic, see `RelayReference::estimated_fee`
+    pub estimated_fee: Option<u128>,
+}
+
+/// Each relay strategy can be used as batch relay strategy, the nonces are decided one by one,
+/// and the accepted nonces before the first refused nonce will be relayed.
+#[async_trait::async_trait]
+impl<T: RelayStrategy> BatchRelayStrategy for T {
+    async fn decide_batch(
+        &mut self,
+        reference: BatchRelayReference,
+    ) -> S2SClientResult<Option<RangeInclusive<u64>>> {
+        let nonces = &reference.nonces;
+        let messages_count = nonces.end() - nonces.start() + 1;
+        let mut accepted = None;
+        for nonce in nonces.clone() {
+            let decide_reference = RelayReference {
+                lane: reference.lane,
+                nonce,
+                messages_count,
+                message_size: reference.message_size,
+                total_weight: reference.total_weight,
+                estimated_fee: reference.estimated_fee,
+            };
+            if !self.decide(decide_reference).await? {
+                break;
+            }
+            accepted = Some(*nonces.start()..=nonce);
+        }
+        Ok(accepted)
+    }
 }
 
 #[derive(Clone)]
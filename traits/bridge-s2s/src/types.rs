@@ -2,10 +2,42 @@ pub use bp_header_chain;
 pub use bp_messages;
 pub use bp_runtime;
 pub use bridge_runtime_common;
+pub use client_common_traits::ExtrinsicLocation;
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "bridge-parachain")]
 pub use self::bridge_parachain::*;
 
+/// Limits of target runtime which a `receive_messages_proof` extrinsic should fit, none means
+/// the limit is unknown.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct MessagesDeliveryLimits {
+    /// max messages which are delivered but not confirmed at an inbound lane
+    pub max_unconfirmed_messages: Option<u64>,
+    /// max unrewarded relayer entries at an inbound lane
+    pub max_unrewarded_relayer_entries: Option<u64>,
+    /// max encoded size of a normal extrinsic
+    pub max_extrinsic_size: Option<u32>,
+    /// max ref time of a normal extrinsic
+    pub max_extrinsic_weight: Option<u64>,
+}
+
+impl MessagesDeliveryLimits {
+    /// Fill the unknown limits by `other`
+    pub fn or(self, other: &Self) -> Self {
+        Self {
+            max_unconfirmed_messages: self
+                .max_unconfirmed_messages
+                .or(other.max_unconfirmed_messages),
+            max_unrewarded_relayer_entries: self
+                .max_unrewarded_relayer_entries
+                .or(other.max_unrewarded_relayer_entries),
+            max_extrinsic_size: self.max_extrinsic_size.or(other.max_extrinsic_size),
+            max_extrinsic_weight: self.max_extrinsic_weight.or(other.max_extrinsic_weight),
+        }
+    }
+}
+
 #[cfg(feature = "bridge-parachain")]
 mod bridge_parachain {
     use serde::{Deserialize, Serialize};
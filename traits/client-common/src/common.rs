@@ -7,3 +7,14 @@ pub trait ClientCommon: 'static + Send + Sync + Clone {
     /// chain types
     type Chain: Chain;
 }
+
+/// Where the submitted extrinsic is found
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtrinsicLocation<Hash> {
+    /// waiting in transaction pool
+    Pool,
+    /// included by the block of best chain
+    Block(Hash),
+    /// neither in transaction pool nor in the blocks within mortality period
+    NotFound,
+}
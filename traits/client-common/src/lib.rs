@@ -1,7 +1,7 @@
 pub use self::common::*;
-#[cfg(feature = "subxt-darwinia")]
+#[cfg(any(feature = "subxt-darwinia", feature = "subxt-reconnect"))]
 pub use self::subxt::*;
 
 mod common;
-#[cfg(feature = "subxt-darwinia")]
+#[cfg(any(feature = "subxt-darwinia", feature = "subxt-reconnect"))]
 mod subxt;
@@ -0,0 +1,22 @@
+use codec::Decode;
+use subxt::constants::StaticConstantAddress;
+use subxt::metadata::{DecodeStaticType, MetadataError};
+use subxt::{Config, OnlineClient};
+
+/// Read a runtime constant which isn't known by the generated api, the constant is
+/// looked up by name, none if the runtime doesn't expose it
+pub fn optional_constant<T: Config, V: Decode>(
+    client: &OnlineClient<T>,
+    pallet: &'static str,
+    constant: &'static str,
+) -> Result<Option<V>, subxt::Error> {
+    let address = StaticConstantAddress::<DecodeStaticType<V>>::new(pallet, constant, [0u8; 32])
+        .unvalidated();
+    match client.constants().at(&address) {
+        Ok(value) => Ok(Some(value)),
+        Err(subxt::Error::Metadata(
+            MetadataError::PalletNotFound | MetadataError::ConstantNotFound,
+        )) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
@@ -1 +1,10 @@
+#[cfg(feature = "subxt-darwinia")]
+pub mod constant;
+#[cfg(feature = "subxt-reconnect")]
+pub mod reconnect;
+#[cfg(feature = "subxt-darwinia")]
+pub mod submitter;
+#[cfg(feature = "subxt-darwinia")]
 pub mod subxt_darwinia_like;
+#[cfg(feature = "subxt-darwinia")]
+pub mod transaction;
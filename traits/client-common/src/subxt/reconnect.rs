@@ -0,0 +1,324 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonrpsee::client_transport::ws::{Uri, WsTransportClientBuilder};
+use jsonrpsee::core::client::{Client, ClientBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use subxt::error::RpcError;
+use subxt::rpc::{RpcClientT, RpcFuture, RpcSubscription};
+use thiserror::Error as ThisError;
+use tokio::sync::RwLock;
+
+/// Rounds of trying all endpoints before the rpc error is returned to caller
+const MAX_RECONNECT_ROUNDS: u32 = 6;
+/// Wait time of the first reconnect round, doubled each round
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+/// The backoff never exceeds this duration
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Websocket endpoints of chain, accepts a single url or a list of urls in config
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(from = "RawEndpoints", into = "Vec<String>")]
+pub struct Endpoints(Vec<String>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEndpoints {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl From<RawEndpoints> for Endpoints {
+    fn from(raw: RawEndpoints) -> Self {
+        match raw {
+            RawEndpoints::Single(endpoint) => Self(vec![endpoint]),
+            RawEndpoints::Multiple(endpoints) => Self(endpoints),
+        }
+    }
+}
+
+impl From<Endpoints> for Vec<String> {
+    fn from(endpoints: Endpoints) -> Self {
+        endpoints.0
+    }
+}
+
+impl From<String> for Endpoints {
+    fn from(endpoint: String) -> Self {
+        Self(vec![endpoint])
+    }
+}
+
+impl From<&str> for Endpoints {
+    fn from(endpoint: &str) -> Self {
+        Self(vec![endpoint.to_string()])
+    }
+}
+
+impl From<Vec<String>> for Endpoints {
+    fn from(endpoints: Vec<String>) -> Self {
+        Self(endpoints)
+    }
+}
+
+impl Endpoints {
+    /// Iterate endpoints in configured order
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.0.iter()
+    }
+}
+
+/// Error of reconnecting rpc client
+#[derive(ThisError, Debug)]
+pub enum ReconnectError {
+    #[error("No endpoint configured")]
+    NoEndpoint,
+
+    #[error("Genesis hash of {endpoint} is {actual}, but {expected} is expected")]
+    GenesisMismatch {
+        endpoint: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Endpoint {0} is syncing")]
+    Syncing(String),
+
+    #[error("Failed to connect {endpoint}: {reason}")]
+    Connect { endpoint: String, reason: String },
+
+    #[error("All endpoints are unavailable after {0} rounds")]
+    Unavailable(u32),
+}
+
+impl From<ReconnectError> for RpcError {
+    fn from(error: ReconnectError) -> Self {
+        RpcError::ClientError(Box::new(error))
+    }
+}
+
+struct Connection {
+    client: Arc<Client>,
+    /// index of connected endpoint
+    index: usize,
+    /// increased at each reconnect, so concurrent callers reconnect only once
+    generation: u64,
+}
+
+/// Rpc client of subxt which reconnects with backoff when the connection is lost,
+/// rotating to the next healthy endpoint of the same chain
+pub struct ReconnectingRpcClient {
+    endpoints: Vec<String>,
+    genesis_hash: String,
+    connection: RwLock<Connection>,
+}
+
+impl ReconnectingRpcClient {
+    /// Connect to the first healthy endpoint, all reachable endpoints must report the same
+    /// genesis hash
+    pub async fn connect(endpoints: Vec<String>) -> Result<Self, RpcError> {
+        if endpoints.is_empty() {
+            return Err(ReconnectError::NoEndpoint.into());
+        }
+        let (client, index, genesis_hash) = connect_any(&endpoints, 0, None).await?;
+        for (other, endpoint) in endpoints.iter().enumerate() {
+            if other == index {
+                continue;
+            }
+            match connect_endpoint(endpoint, Some(&genesis_hash)).await {
+                Ok(_) => {}
+                Err(error @ ReconnectError::GenesisMismatch { .. }) => return Err(error.into()),
+                Err(error) => {
+                    tracing::warn!(target: "client-common", "[reconnect] {}", error);
+                }
+            }
+        }
+        Ok(Self {
+            endpoints,
+            genesis_hash,
+            connection: RwLock::new(Connection {
+                client: Arc::new(client),
+                index,
+                generation: 0,
+            }),
+        })
+    }
+
+    async fn current(&self) -> (Arc<Client>, u64) {
+        let connection = self.connection.read().await;
+        (connection.client.clone(), connection.generation)
+    }
+
+    /// Reconnect unless another caller already did it after `generation`
+    async fn reconnect(&self, generation: u64) -> Result<(), RpcError> {
+        let mut connection = self.connection.write().await;
+        if connection.generation != generation {
+            return Ok(());
+        }
+        tracing::warn!(
+            target: "client-common",
+            "[reconnect] lost connection to {}, reconnecting",
+            self.endpoints[connection.index],
+        );
+        let start = (connection.index + 1) % self.endpoints.len();
+        let (client, index, _) =
+            connect_any(&self.endpoints, start, Some(&self.genesis_hash)).await?;
+        tracing::info!(
+            target: "client-common",
+            "[reconnect] reconnected to {}",
+            self.endpoints[index],
+        );
+        *connection = Connection {
+            client: Arc::new(client),
+            index,
+            generation: generation + 1,
+        };
+        Ok(())
+    }
+}
+
+impl RpcClientT for ReconnectingRpcClient {
+    fn request_raw<'a>(
+        &'a self,
+        method: &'a str,
+        params: Option<Box<RawValue>>,
+    ) -> RpcFuture<'a, Box<RawValue>> {
+        Box::pin(async move {
+            loop {
+                let (client, generation) = self.current().await;
+                match client.request_raw(method, params.clone()).await {
+                    Err(_) if !client.is_connected() => self.reconnect(generation).await?,
+                    result => return result,
+                }
+            }
+        })
+    }
+
+    fn subscribe_raw<'a>(
+        &'a self,
+        sub: &'a str,
+        params: Option<Box<RawValue>>,
+        unsub: &'a str,
+    ) -> RpcFuture<'a, RpcSubscription> {
+        Box::pin(async move {
+            loop {
+                let (client, generation) = self.current().await;
+                match client.subscribe_raw(sub, params.clone(), unsub).await {
+                    Err(_) if !client.is_connected() => self.reconnect(generation).await?,
+                    result => return result,
+                }
+            }
+        })
+    }
+}
+
+/// Try endpoints from `start` in turn, waiting with backoff after each failed round
+async fn connect_any(
+    endpoints: &[String],
+    start: usize,
+    genesis_hash: Option<&str>,
+) -> Result<(Client, usize, String), ReconnectError> {
+    let mut backoff = INITIAL_BACKOFF;
+    for round in 1..=MAX_RECONNECT_ROUNDS {
+        for offset in 0..endpoints.len() {
+            let index = (start + offset) % endpoints.len();
+            match connect_endpoint(&endpoints[index], genesis_hash).await {
+                Ok((client, genesis_hash)) => return Ok((client, index, genesis_hash)),
+                // the endpoint belongs to another chain, retrying is meaningless
+                Err(error @ ReconnectError::GenesisMismatch { .. }) => return Err(error),
+                Err(error) => {
+                    tracing::warn!(
+                        target: "client-common",
+                        "[reconnect] round {}: {}",
+                        round,
+                        error,
+                    );
+                }
+            }
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+    Err(ReconnectError::Unavailable(MAX_RECONNECT_ROUNDS))
+}
+
+/// Connect to the endpoint, the endpoint is healthy if it is not syncing and its genesis hash
+/// is the expected one
+async fn connect_endpoint(
+    endpoint: &str,
+    expected_genesis_hash: Option<&str>,
+) -> Result<(Client, String), ReconnectError> {
+    let connect_error = |reason: String| ReconnectError::Connect {
+        endpoint: endpoint.to_string(),
+        reason,
+    };
+    let url: Uri = endpoint
+        .parse()
+        .map_err(|e| connect_error(format!("{e:?}")))?;
+    let (sender, receiver) = WsTransportClientBuilder::default()
+        .build(url)
+        .await
+        .map_err(|e| connect_error(format!("{e:?}")))?;
+    let client = ClientBuilder::default().build_with_tokio(sender, receiver);
+
+    let health = request_json(&client, "system_health", None)
+        .await
+        .map_err(connect_error)?;
+    if health["isSyncing"].as_bool().unwrap_or(false) {
+        return Err(ReconnectError::Syncing(endpoint.to_string()));
+    }
+
+    let params = RawValue::from_string("[0]".to_string()).ok();
+    let genesis_hash = request_json(&client, "chain_getBlockHash", params)
+        .await
+        .map_err(connect_error)?
+        .as_str()
+        .map(ToString::to_string)
+        .ok_or_else(|| connect_error("Missing genesis hash".to_string()))?;
+    if let Some(expected) = expected_genesis_hash {
+        if expected != genesis_hash {
+            return Err(ReconnectError::GenesisMismatch {
+                endpoint: endpoint.to_string(),
+                expected: expected.to_string(),
+                actual: genesis_hash,
+            });
+        }
+    }
+    Ok((client, genesis_hash))
+}
+
+async fn request_json(
+    client: &Client,
+    method: &str,
+    params: Option<Box<RawValue>>,
+) -> Result<serde_json::Value, String> {
+    let response = client
+        .request_raw(method, params)
+        .await
+        .map_err(|e| format!("{e:?}"))?;
+    serde_json::from_str(response.get()).map_err(|e| format!("{e:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Config {
+        endpoint: Endpoints,
+    }
+
+    #[test]
+    fn test_deserialize_endpoints() {
+        let single: Config = serde_json::from_str(r#"{"endpoint": "wss://a"}"#).unwrap();
+        assert_eq!(single.endpoint, Endpoints::from("wss://a"));
+
+        let multiple: Config =
+            serde_json::from_str(r#"{"endpoint": ["wss://a", "wss://b"]}"#).unwrap();
+        assert_eq!(
+            multiple.endpoint.iter().collect::<Vec<_>>(),
+            vec!["wss://a", "wss://b"]
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codec::{Decode, Encode};
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use sp_runtime::traits::{Hash as _, Header};
+use sp_runtime::transaction_validity::{InvalidTransaction, TransactionValidityError};
+use sp_runtime::{ApplyExtrinsicResult, DispatchError};
+use subxt::ext::sp_core::Bytes;
+use subxt::rpc::rpc_params;
+use subxt::tx::{Signer, SubmittableExtrinsic, TxPayload, TxProgress, TxStatus};
+use subxt::{Config, Metadata, OnlineClient};
+use tokio::sync::{watch, Mutex};
+
+use crate::common::ExtrinsicLocation;
+use crate::subxt_darwinia_like::{DarwiniaLikeExtrinsicParams, DarwiniaLikeExtrinsicParamsBuilder};
+use crate::transaction::TransactionConfig;
+
+/// Max times to resubmit an extrinsic which is dropped
+const MAX_RESUBMIT: u32 = 3;
+/// Interval to check whether the tip of pending extrinsic should be bumped
+const TIP_BUMP_CHECK_INTERVAL: Duration = Duration::from_secs(6);
+
+type SharedNonce = Arc<Mutex<Option<bp_darwinia_core::Nonce>>>;
+
+/// Local nonces of accounts, keyed by genesis hash and account, so that all clients
+/// with the same signer share one nonce
+static NONCES: Lazy<std::sync::Mutex<HashMap<(bp_darwinia_core::Hash, Vec<u8>), SharedNonce>>> =
+    Lazy::new(Default::default);
+
+/// Status of submitted extrinsic
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExtrinsicStatus {
+    /// accepted by transaction pool
+    Submitted(bp_darwinia_core::Hash),
+    /// included in block, (extrinsic hash, block hash)
+    InBlock(bp_darwinia_core::Hash, bp_darwinia_core::Hash),
+    /// finalized, (extrinsic hash, block hash)
+    Finalized(bp_darwinia_core::Hash, bp_darwinia_core::Hash),
+    /// dispatch failed or dropped after all resubmissions
+    Failed(String),
+}
+
+/// Handle of submitted extrinsic, the status is reported by background task
+#[derive(Clone, Debug)]
+pub struct SubmittedExtrinsic {
+    hash: bp_darwinia_core::Hash,
+    status: watch::Receiver<ExtrinsicStatus>,
+}
+
+impl SubmittedExtrinsic {
+    /// hash of the first submission
+    pub fn hash(&self) -> bp_darwinia_core::Hash {
+        self.hash
+    }
+
+    /// current status
+    pub fn status(&self) -> ExtrinsicStatus {
+        self.status.borrow().clone()
+    }
+
+    /// wait until the extrinsic included in block, returns the extrinsic hash,
+    /// it may differ from the first submission if resubmitted
+    pub async fn wait_for_in_block(&mut self) -> Result<bp_darwinia_core::Hash, String> {
+        self.wait_for(|status| match status {
+            ExtrinsicStatus::InBlock(hash, _) | ExtrinsicStatus::Finalized(hash, _) => Some(*hash),
+            _ => None,
+        })
+        .await
+    }
+
+    /// wait until the extrinsic finalized, returns the extrinsic hash
+    pub async fn wait_for_finalized(&mut self) -> Result<bp_darwinia_core::Hash, String> {
+        self.wait_for(|status| match status {
+            ExtrinsicStatus::Finalized(hash, _) => Some(*hash),
+            _ => None,
+        })
+        .await
+    }
+
+    async fn wait_for(
+        &mut self,
+        reached: impl Fn(&ExtrinsicStatus) -> Option<bp_darwinia_core::Hash>,
+    ) -> Result<bp_darwinia_core::Hash, String> {
+        loop {
+            {
+                let status = self.status.borrow_and_update();
+                if let Some(hash) = reached(&status) {
+                    return Ok(hash);
+                }
+                if let ExtrinsicStatus::Failed(reason) = &*status {
+                    return Err(reason.clone());
+                }
+            }
+            if self.status.changed().await.is_err() {
+                return Err(format!("Status of extrinsic {:?} is lost", self.hash));
+            }
+        }
+    }
+}
+
+/// Call data which is already encoded
+struct EncodedCall(Vec<u8>);
+
+impl TxPayload for EncodedCall {
+    fn encode_call_data(
+        &self,
+        _metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        out.extend_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+/// Dispatch info returned by `payment_queryInfo`, the balance may be serialized as
+/// number, decimal string or hex string depends on node version
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuntimeDispatchInfo {
+    partial_fee: serde_json::Value,
+}
+
+impl RuntimeDispatchInfo {
+    fn partial_fee(&self) -> Option<u128> {
+        match &self.partial_fee {
+            serde_json::Value::Number(v) => v.as_u64().map(u128::from),
+            serde_json::Value::String(v) => match v.strip_prefix("0x") {
+                Some(hex) => u128::from_str_radix(hex, 16).ok(),
+                None => v.parse().ok(),
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Extrinsic which is watched by background task
+struct PendingExtrinsic {
+    call: EncodedCall,
+    nonce: bp_darwinia_core::Nonce,
+    tip: bp_darwinia_core::Balance,
+    /// best block number when the extrinsic signed
+    signed_at: bp_darwinia_core::BlockNumber,
+}
+
+/// Submit extrinsics with local nonce, the next extrinsic can be submitted without
+/// waiting for the previous one. Dropped extrinsics are resubmitted with the nonce resynced
+/// from chain by the background task, and the tip is bumped if configured.
+pub struct DarwiniaLikeSubmitter<T, S>
+where
+    T: Config,
+{
+    client: OnlineClient<T>,
+    signer: S,
+    nonce: SharedNonce,
+    config: TransactionConfig,
+}
+
+impl<T, S> Clone for DarwiniaLikeSubmitter<T, S>
+where
+    T: Config,
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            client: self.client.clone(),
+            signer: self.signer.clone(),
+            nonce: self.nonce.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<T, S> Debug for DarwiniaLikeSubmitter<T, S>
+where
+    T: Config,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DarwiniaLikeSubmitter { signer: <..>, ")?;
+        f.write_str(&format!("config: {:?} }}", self.config))
+    }
+}
+
+impl<T, S> DarwiniaLikeSubmitter<T, S>
+where
+    T: Config<
+        Index = bp_darwinia_core::Nonce,
+        Hash = bp_darwinia_core::Hash,
+        Header = bp_darwinia_core::Header,
+        ExtrinsicParams = DarwiniaLikeExtrinsicParams,
+    >,
+    T::AccountId: Encode,
+    S: Signer<T> + Clone + Send + Sync + 'static,
+{
+    pub fn new(client: OnlineClient<T>, signer: S, config: TransactionConfig) -> Self {
+        let key = (client.genesis_hash(), signer.account_id().encode());
+        let nonce = NONCES
+            .lock()
+            .expect("Nonces of submitter is poisoned")
+            .entry(key)
+            .or_default()
+            .clone();
+        Self {
+            client,
+            signer,
+            nonce,
+            config,
+        }
+    }
+
+    /// Whether the extrinsics are checked before submitting
+    pub fn preflight_enabled(&self) -> bool {
+        self.config.preflight
+    }
+
+    /// Estimate the fee of call by `payment_queryInfo`, the tip is excluded. The call is
+    /// signed with the current local nonce, or the nonce read from chain if there is no
+    /// local one, but never submitted.
+    pub async fn estimate_fee<Call: TxPayload>(&self, call: &Call) -> Result<u128, subxt::Error> {
+        let local = *self.nonce.lock().await;
+        let nonce = match local {
+            Some(v) => v,
+            None => self.chain_nonce().await?,
+        };
+        let (signed, _) = self.create_signed(call, nonce, 0).await?;
+        let info: RuntimeDispatchInfo = self
+            .client
+            .rpc()
+            .request(
+                "payment_queryInfo",
+                rpc_params![Bytes(signed.encoded().to_vec())],
+            )
+            .await?;
+        info.partial_fee().ok_or_else(|| {
+            subxt::Error::Other(format!(
+                "Unexpected partial fee returned by payment_queryInfo: {}",
+                info.partial_fee
+            ))
+        })
+    }
+
+    /// Submit a call, the status of extrinsic is watched in background. The `messages_fee`
+    /// is the fee of messages relayed by this call, it's used by the scaled tip strategy.
+    pub async fn submit<Call: TxPayload>(
+        &self,
+        call: &Call,
+        messages_fee: u128,
+    ) -> Result<SubmittedExtrinsic, subxt::Error> {
+        self.client.tx().validate(call)?;
+        let mut call_data = Vec::new();
+        call.encode_call_data(&self.client.metadata(), &mut call_data)?;
+        let call = EncodedCall(call_data);
+        let tip = self.config.tip.tip(messages_fee);
+
+        let (progress, pending) = self.sign_and_submit(call, tip, false).await?;
+        let hash = progress.extrinsic_hash();
+        let (sender, status) = watch::channel(ExtrinsicStatus::Submitted(hash));
+        let submitter = self.clone();
+        tokio::spawn(async move { submitter.watch(pending, progress, sender).await });
+        Ok(SubmittedExtrinsic { hash, status })
+    }
+
+    /// Look up the extrinsic in transaction pool by `author_pendingExtrinsics`, and then in
+    /// the best chain blocks within the mortality period, the extrinsics signed by this
+    /// submitter are dead after that.
+    pub async fn find_extrinsic(
+        &self,
+        hash: bp_darwinia_core::Hash,
+    ) -> Result<ExtrinsicLocation<bp_darwinia_core::Hash>, subxt::Error> {
+        let hash_of =
+            |extrinsic: &[u8]| <bp_darwinia_core::Header as Header>::Hashing::hash(extrinsic);
+        let pending: Vec<Bytes> = self
+            .client
+            .rpc()
+            .request("author_pendingExtrinsics", rpc_params![])
+            .await?;
+        if pending
+            .iter()
+            .any(|extrinsic| hash_of(&extrinsic.0[..]) == hash)
+        {
+            return Ok(ExtrinsicLocation::Pool);
+        }
+        let mut block_hash = Some(self.best_header().await?.hash());
+        for _ in 0..=self.config.mortality_period {
+            let block = match block_hash {
+                Some(v) => self.client.rpc().block(Some(v)).await?,
+                None => break,
+            };
+            let block = match block {
+                Some(v) => v.block,
+                None => break,
+            };
+            let this_hash = block.header.hash();
+            if block
+                .extrinsics
+                .iter()
+                .any(|extrinsic| hash_of(&extrinsic.0[..]) == hash)
+            {
+                return Ok(ExtrinsicLocation::Block(this_hash));
+            }
+            block_hash = if block.header.number == 0 {
+                None
+            } else {
+                Some(block.header.parent_hash)
+            };
+        }
+        Ok(ExtrinsicLocation::NotFound)
+    }
+
+    /// Read the next nonce of signer by `system_accountNextIndex`, it counts the extrinsics
+    /// in transaction pool, so the nonces of in-flight extrinsics are not reused.
+    async fn chain_nonce(&self) -> Result<bp_darwinia_core::Nonce, subxt::Error> {
+        self.client
+            .rpc()
+            .system_account_next_index(self.signer.account_id())
+            .await
+    }
+
+    /// Sign the call with the next local nonce, the local nonce is resynced from chain
+    /// first if `resync` is true
+    async fn sign_and_submit(
+        &self,
+        call: EncodedCall,
+        tip: bp_darwinia_core::Balance,
+        resync: bool,
+    ) -> Result<(TxProgress<T, OnlineClient<T>>, PendingExtrinsic), subxt::Error> {
+        let mut nonce = self.nonce.lock().await;
+        let next = match *nonce {
+            Some(v) if !resync => v,
+            _ => self.chain_nonce().await?,
+        };
+        match self.sign_with_nonce(&call, next, tip).await {
+            Ok((progress, signed_at)) => {
+                *nonce = Some(next + 1);
+                let pending = PendingExtrinsic {
+                    call,
+                    nonce: next,
+                    tip,
+                    signed_at,
+                };
+                Ok((progress, pending))
+            }
+            Err(e) => {
+                // the local nonce may be outdated, resync it from chain, it's read again at
+                // next submission if failed
+                *nonce = self.chain_nonce().await.ok();
+                Err(e)
+            }
+        }
+    }
+
+    /// Sign the call with given nonce and tip, returns the best block number when signed
+    async fn sign_with_nonce(
+        &self,
+        call: &EncodedCall,
+        nonce: bp_darwinia_core::Nonce,
+        tip: bp_darwinia_core::Balance,
+    ) -> Result<
+        (
+            TxProgress<T, OnlineClient<T>>,
+            bp_darwinia_core::BlockNumber,
+        ),
+        subxt::Error,
+    > {
+        let (signed, signed_at) = self.create_signed(call, nonce, tip).await?;
+        if self.config.preflight {
+            self.dry_run(&signed).await?;
+        }
+        let progress = signed.submit_and_watch().await?;
+        Ok((progress, signed_at))
+    }
+
+    /// Sign the call which is mortal since the best block, returns the best block number
+    async fn create_signed<Call: TxPayload>(
+        &self,
+        call: &Call,
+        nonce: bp_darwinia_core::Nonce,
+        tip: bp_darwinia_core::Balance,
+    ) -> Result<
+        (
+            SubmittableExtrinsic<T, OnlineClient<T>>,
+            bp_darwinia_core::BlockNumber,
+        ),
+        subxt::Error,
+    > {
+        let best = self.best_header().await?;
+        let era = bp_runtime::TransactionEra::new(
+            bp_runtime::HeaderId(best.number, best.hash()),
+            Some(self.config.mortality_period),
+        );
+        let other_params = DarwiniaLikeExtrinsicParamsBuilder::default()
+            .era(era)
+            .tip(tip);
+        let signed =
+            self.client
+                .tx()
+                .create_signed_with_nonce(call, &self.signer, nonce, other_params)?;
+        Ok((signed, best.number))
+    }
+
+    /// Apply the extrinsic on best block by `system_dryRun`, it fails if the extrinsic is
+    /// invalid or the dispatch fails. The extrinsic with future nonce can't be applied
+    /// until the previous extrinsics included, so it's passed without checking dispatch.
+    async fn dry_run(
+        &self,
+        signed: &SubmittableExtrinsic<T, OnlineClient<T>>,
+    ) -> Result<(), subxt::Error> {
+        let result: Bytes = self
+            .client
+            .rpc()
+            .request(
+                "system_dryRun",
+                rpc_params![Bytes(signed.encoded().to_vec())],
+            )
+            .await?;
+        let result = ApplyExtrinsicResult::decode(&mut result.as_ref())?;
+        let reason = match result {
+            Ok(Ok(())) => return Ok(()),
+            Err(TransactionValidityError::Invalid(InvalidTransaction::Future)) => {
+                tracing::debug!(
+                    target: "client-common",
+                    "Skip dry run of extrinsic, the previous extrinsics of signer are pending",
+                );
+                return Ok(());
+            }
+            Ok(Err(DispatchError::Module(error))) => {
+                match self.client.metadata().error(error.index, error.error[0]) {
+                    Ok(details) => {
+                        format!("dispatch error {}::{}", details.pallet(), details.error())
+                    }
+                    Err(_) => format!("dispatch error {:?}", error),
+                }
+            }
+            Ok(Err(e)) => format!("dispatch error {e:?}"),
+            Err(e) => format!("invalid transaction {e:?}"),
+        };
+        Err(subxt::Error::Other(format!(
+            "Dry run of extrinsic failed, it's not submitted: {reason}"
+        )))
+    }
+
+    async fn best_header(&self) -> Result<bp_darwinia_core::Header, subxt::Error> {
+        self.client
+            .rpc()
+            .header(None)
+            .await?
+            .ok_or_else(|| subxt::Error::Other("Best header not found".to_string()))
+    }
+
+    /// Re-sign the pending extrinsic with a higher tip if it is not included in time,
+    /// the new extrinsic replaces the old one in transaction pool since the nonce is same.
+    async fn bump_tip(
+        &self,
+        pending: &mut PendingExtrinsic,
+    ) -> Result<Option<TxProgress<T, OnlineClient<T>>>, subxt::Error> {
+        let tip_bump = match &self.config.tip_bump {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let best = self.best_header().await?;
+        if best.number < pending.signed_at + tip_bump.after_blocks {
+            return Ok(None);
+        }
+        let tip = match tip_bump.bump(pending.tip) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let (progress, signed_at) = self
+            .sign_with_nonce(&pending.call, pending.nonce, tip)
+            .await?;
+        tracing::info!(
+            target: "client-common",
+            "The extrinsic with nonce {} is not included since block {}, bump tip from {} to {}",
+            pending.nonce,
+            pending.signed_at,
+            pending.tip,
+            tip,
+        );
+        pending.tip = tip;
+        pending.signed_at = signed_at;
+        Ok(Some(progress))
+    }
+
+    async fn watch(
+        &self,
+        mut pending: PendingExtrinsic,
+        mut progress: TxProgress<T, OnlineClient<T>>,
+        sender: watch::Sender<ExtrinsicStatus>,
+    ) {
+        let bump_enabled = self.config.tip_bump.is_some();
+        let mut included = false;
+        let mut resubmitted = 0;
+        loop {
+            let hash = progress.extrinsic_hash();
+            let item = tokio::select! {
+                item = progress.next_item() => item,
+                _ = tokio::time::sleep(TIP_BUMP_CHECK_INTERVAL), if bump_enabled && !included => {
+                    match self.bump_tip(&mut pending).await {
+                        Ok(Some(bumped)) => progress = bumped,
+                        Ok(None) => continue,
+                        Err(e) => {
+                            tracing::warn!(
+                                target: "client-common",
+                                "Failed to bump tip of extrinsic {:?}: {:?}",
+                                hash,
+                                e,
+                            );
+                            continue;
+                        }
+                    }
+                    let _ = sender.send(ExtrinsicStatus::Submitted(progress.extrinsic_hash()));
+                    continue;
+                }
+            };
+            let status = match item {
+                Some(Ok(status)) => status,
+                Some(Err(e)) => {
+                    Self::fail(&sender, format!("{e:?}"));
+                    return;
+                }
+                None => {
+                    Self::fail(
+                        &sender,
+                        format!("Subscription of extrinsic {hash:?} closed"),
+                    );
+                    return;
+                }
+            };
+            match status {
+                TxStatus::InBlock(in_block) => {
+                    included = true;
+                    let block_hash = in_block.block_hash();
+                    if let Err(e) = in_block.wait_for_success().await {
+                        Self::fail(&sender, format!("{e:?}"));
+                        return;
+                    }
+                    let _ = sender.send(ExtrinsicStatus::InBlock(hash, block_hash));
+                }
+                TxStatus::Retracted(_) => {
+                    included = false;
+                }
+                TxStatus::Finalized(in_block) => {
+                    tracing::debug!(
+                        target: "client-common",
+                        "The extrinsic {:?} is finalized in block {:?}",
+                        hash,
+                        in_block.block_hash(),
+                    );
+                    let _ = sender.send(ExtrinsicStatus::Finalized(hash, in_block.block_hash()));
+                    return;
+                }
+                TxStatus::Invalid => {
+                    // the later extrinsics can't be included until the nonce is reused
+                    *self.nonce.lock().await = self.chain_nonce().await.ok();
+                    Self::fail(&sender, format!("Extrinsic {hash:?} is invalid"));
+                    return;
+                }
+                TxStatus::Usurped(by) => {
+                    Self::fail(&sender, format!("Extrinsic {hash:?} is usurped by {by:?}"));
+                    return;
+                }
+                TxStatus::Dropped => {
+                    if resubmitted >= MAX_RESUBMIT {
+                        Self::fail(
+                            &sender,
+                            format!(
+                                "Extrinsic {hash:?} is dropped after {resubmitted} resubmissions"
+                            ),
+                        );
+                        return;
+                    }
+                    resubmitted += 1;
+                    tracing::warn!(
+                        target: "client-common",
+                        "The extrinsic {:?} is dropped, resubmit it ({}/{})",
+                        hash,
+                        resubmitted,
+                        MAX_RESUBMIT,
+                    );
+                    (progress, pending) =
+                        match self.sign_and_submit(pending.call, pending.tip, true).await {
+                            Ok(v) => v,
+                            Err(e) => {
+                                Self::fail(&sender, format!("{e:?}"));
+                                return;
+                            }
+                        };
+                    included = false;
+                    let _ = sender.send(ExtrinsicStatus::Submitted(progress.extrinsic_hash()));
+                }
+                TxStatus::FinalityTimeout(_) => {
+                    Self::fail(&sender, format!("Finality timeout of extrinsic {hash:?}"));
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn fail(sender: &watch::Sender<ExtrinsicStatus>, reason: String) {
+        tracing::warn!(target: "client-common", "Extrinsic failed: {}", reason);
+        let _ = sender.send(ExtrinsicStatus::Failed(reason));
+    }
+}
@@ -60,3 +60,20 @@ impl Default for DarwiniaLikeExtrinsicParamsBuilder {
         }
     }
 }
+
+impl DarwiniaLikeExtrinsicParamsBuilder {
+    /// Set the era of extrinsic, immortal by default
+    pub fn era(
+        mut self,
+        era: bp_runtime::TransactionEraOf<bp_darwinia_core::DarwiniaLike>,
+    ) -> Self {
+        self.era = era;
+        self
+    }
+
+    /// Set the tip of extrinsic, no tip by default
+    pub fn tip(mut self, tip: bp_darwinia_core::Balance) -> Self {
+        self.tip = tip;
+        self
+    }
+}
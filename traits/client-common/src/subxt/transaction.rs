@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Default mortality period of extrinsics, in blocks
+pub const DEFAULT_MORTALITY_PERIOD: u32 = 64;
+
+/// Transaction parameters of substrate extrinsics
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TransactionConfig {
+    /// mortality period in blocks, the extrinsic is invalid after this period
+    #[serde(default = "default_mortality_period")]
+    pub mortality_period: u32,
+    /// tip of extrinsics
+    #[serde(default)]
+    pub tip: TipStrategy,
+    /// bump tip if the extrinsic is not included in time
+    #[serde(default)]
+    pub tip_bump: Option<TipBump>,
+    /// dry run extrinsics by `system_dryRun` before submitting, and estimate fee of
+    /// relay calls by `payment_queryInfo`
+    #[serde(default)]
+    pub preflight: bool,
+}
+
+impl Default for TransactionConfig {
+    fn default() -> Self {
+        Self {
+            mortality_period: DEFAULT_MORTALITY_PERIOD,
+            tip: Default::default(),
+            tip_bump: None,
+            preflight: false,
+        }
+    }
+}
+
+fn default_mortality_period() -> u32 {
+    DEFAULT_MORTALITY_PERIOD
+}
+
+/// Tip strategy, the amount is in the smallest unit of chain token
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "type")]
+pub enum TipStrategy {
+    /// no tip
+    #[default]
+    None,
+    /// the same tip for all extrinsics
+    Fixed { amount: u128 },
+    /// percent of the fee of messages which are relayed by the extrinsic,
+    /// extrinsics not relaying messages have no tip
+    Scaled { percent: u32 },
+}
+
+impl TipStrategy {
+    /// Tip of the extrinsic which relays messages with `messages_fee`
+    pub fn tip(&self, messages_fee: u128) -> u128 {
+        match self {
+            Self::None => 0,
+            Self::Fixed { amount } => *amount,
+            Self::Scaled { percent } => messages_fee.saturating_mul(*percent as u128) / 100,
+        }
+    }
+}
+
+/// Re-sign the extrinsic with a higher tip if it is not included in `after_blocks` blocks
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TipBump {
+    /// blocks to wait before bumping
+    pub after_blocks: u32,
+    /// tip increased at each bump
+    pub step: u128,
+    /// the tip never exceeds this amount
+    pub max_tip: u128,
+}
+
+impl TipBump {
+    /// The bumped tip, none if the max tip is reached
+    pub fn bump(&self, tip: u128) -> Option<u128> {
+        if tip >= self.max_tip {
+            return None;
+        }
+        Some(tip.saturating_add(self.step).min(self.max_tip))
+    }
+}